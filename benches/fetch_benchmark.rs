@@ -2,7 +2,8 @@
 //! 
 //! Run with: cargo bench
 
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
 use binance_connector::{BinanceClient, BinanceConfig, Interval};
 use tokio::runtime::Runtime;
 
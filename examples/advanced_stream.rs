@@ -3,8 +3,7 @@
 //! Usage:
 //!   cargo run --example advanced_stream
 
-use binance_connector::{BinanceWebSocket, BinanceConfig, Interval};
-use std::collections::HashMap;
+use binance_connector::{indicators::KlineTracker, BinanceWebSocket, BinanceConfig, Interval};
 use tokio::time::{sleep, Duration};
 
 #[tokio::main]
@@ -23,9 +22,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 async fn monitor_klines_with_stats(ws: &BinanceWebSocket) -> Result<(), Box<dyn std::error::Error>> {
-    let mut stream = ws.kline_stream("BTCUSDT", Interval::Minutes1).await?;
+    let mut stream = ws.kline_stream("BTCUSDT", Interval::Minutes1, None).await?;
     
     let mut stats = KlineStats::new();
+    let mut tracker = KlineTracker::new();
     let mut error_count = 0;
     let start_time = tokio::time::Instant::now();
     
@@ -37,23 +37,25 @@ async fn monitor_klines_with_stats(ws: &BinanceWebSocket) -> Result<(), Box<dyn
                 error_count = 0; // Reset error count on success
                 stats.update(&kline);
                 
-                if kline.is_closed {
-                    println!("✅ Candle CLOSED at {}:", kline.close_time.format("%H:%M:%S"));
+                // Binance may repeat a candle's closing frame; report it once
+                let finalized = tracker.update(&kline);
+                for candle in &finalized {
+                    println!("✅ Candle CLOSED at {}:", candle.close_time.format("%H:%M:%S"));
                     println!("   O: ${:.2} | H: ${:.2} | L: ${:.2} | C: ${:.2}",
-                        kline.open, kline.high, kline.low, kline.close);
-                    println!("   Volume: {:.4} BTC | Trades: {}", kline.volume, kline.trades);
+                        candle.open, candle.high, candle.low, candle.close);
+                    println!("   Volume: {:.4} BTC | Trades: {}", candle.volume, candle.trades);
                     println!("   Change: ${:.2} ({:.2}%)\n",
-                        kline.close - kline.open,
-                        ((kline.close - kline.open) / kline.open) * 100.0
+                        candle.close - candle.open,
+                        ((candle.close - candle.open) / candle.open) * 100.0
                     );
                     
                     stats.print_summary();
-                } else {
-                    // Print periodic updates for current candle
-                    if stats.update_count % 10 == 0 {
-                        println!("📈 Current candle (updating): C=${:.2} | V={:.4} BTC",
-                            kline.close, kline.volume);
-                    }
+                }
+                
+                // Print periodic updates for current candle
+                if finalized.is_empty() && stats.update_count.is_multiple_of(10) {
+                    println!("📈 Current candle (updating): C=${:.2} | V={:.4} BTC",
+                        kline.close, kline.volume);
                 }
             }
             Some(Err(e)) => {
@@ -65,7 +67,7 @@ async fn monitor_klines_with_stats(ws: &BinanceWebSocket) -> Result<(), Box<dyn
                     sleep(Duration::from_secs(5)).await;
                     
                     // Reconnect
-                    match ws.kline_stream("BTCUSDT", Interval::Minutes1).await {
+                    match ws.kline_stream("BTCUSDT", Interval::Minutes1, None).await {
                         Ok(new_stream) => {
                             stream = new_stream;
                             error_count = 0;
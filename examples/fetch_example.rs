@@ -49,7 +49,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     match client.get_ticker_price("BTCUSDT").await {
         Ok(ticker) => {
             println!("   Price: ${}", ticker.price);
-            println!("   Time: {}\n", ticker.timestamp);
+            println!("   Received: {}\n", ticker.received_at);
         }
         Err(e) => println!("   ❌ Error: {}\n", e),
     }
@@ -69,7 +69,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("   Quote Volume: ${}", ticker.quote_volume);
             println!("   Bid: ${}", ticker.bid_price);
             println!("   Ask: ${}", ticker.ask_price);
-            println!("   Spread: ${:.2}", ticker.spread());
+            if let Some(spread) = ticker.try_spread() {
+                println!("   Spread: ${:.2}", spread);
+            }
             println!("   Trades: {}\n", ticker.count);
         }
         Err(e) => println!("   ❌ Error: {}\n", e),
@@ -135,8 +137,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     match client.get_recent_trades("BTCUSDT", 5).await {
         Ok(trades) => {
             for (i, trade) in trades.iter().enumerate() {
-                let side = if trade.is_buyer_maker { "SELL" } else { "BUY " };
-                println!("   [{}] {} ${:.2} × {:.6} at {}",
+                let side = trade.aggressor_side();
+                println!("   [{}] {:<4} ${:.2} × {:.6} at {}",
                     i + 1,
                     side,
                     trade.price,
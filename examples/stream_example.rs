@@ -4,7 +4,6 @@
 //!   cargo run --example stream_example
 
 use binance_connector::{BinanceWebSocket, BinanceConfig, Interval};
-use futures_util::StreamExt;
 use tokio::time::{timeout, Duration};
 
 #[tokio::main]
@@ -93,7 +92,7 @@ async fn stream_ticker_example(ws: &BinanceWebSocket) -> Result<(), Box<dyn std:
 }
 
 async fn stream_kline_example(ws: &BinanceWebSocket) -> Result<(), Box<dyn std::error::Error>> {
-    let mut stream = ws.kline_stream("ETHUSDT", Interval::Minutes1).await?;
+    let mut stream = ws.kline_stream("ETHUSDT", Interval::Minutes1, None).await?;
     
     let mut count = 0;
     let result = timeout(Duration::from_secs(10), async {
@@ -138,8 +137,8 @@ async fn stream_trade_example(ws: &BinanceWebSocket) -> Result<(), Box<dyn std::
             match result {
                 Ok(trade) => {
                     count += 1;
-                    let side = if trade.is_buyer_maker { "SELL" } else { "BUY " };
-                    println!("   [{}] {} ${:.2} × {:.4} BNB at {}",
+                    let side = trade.aggressor_side();
+                    println!("   [{}] {:<4} ${:.2} × {:.4} BNB at {}",
                         count,
                         side,
                         trade.price,
@@ -228,7 +227,7 @@ async fn stream_multiple_example(ws: &BinanceWebSocket) -> Result<(), Box<dyn st
                 Ok(Some(Ok(ticker))) => {
                     let count = counts.entry(symbol.clone()).or_insert(0);
                     *count += 1;
-                    println!("   {} → ${:.2}", ticker.symbol, ticker.price);
+                    println!("   {} → ${:.2}", ticker.symbol, ticker.price());
                 }
                 Ok(Some(Err(e))) => eprintln!("   {} ❌ Error: {}", symbol, e),
                 Ok(None) => break,
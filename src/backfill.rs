@@ -0,0 +1,174 @@
+//! Planning and running large kline backfills
+//!
+//! [`BinanceClient::plan_klines_backfill`] turns a `(symbol, interval,
+//! start, end)` request into a [`BackfillPlan`] that reports how many pages
+//! and how much request weight the job needs, and how long the rate limiter
+//! will stretch it to, before anything is sent.
+
+use crate::{
+    client::BinanceClient,
+    endpoints::Endpoints,
+    error::{Error, Result},
+    models::{Interval, Kline},
+};
+use futures::{stream, Stream, TryStreamExt};
+use std::fmt;
+use std::time::Duration;
+
+/// Preview of a kline backfill over `start_time..=end_time`
+///
+/// Built by [`BinanceClient::plan_klines_backfill`]. The figures assume the
+/// symbol traded for the whole range; pages before its listing come back
+/// short or empty, so they are upper bounds.
+#[derive(Clone)]
+pub struct BackfillPlan {
+    pub symbol: String,
+    pub interval: Interval,
+    /// First open time covered, in milliseconds
+    pub start_time: i64,
+    /// Last open time covered, in milliseconds
+    pub end_time: i64,
+    /// Candles opening within the range
+    pub candles: u64,
+    /// Requests of up to `max_kline_limit` candles each
+    pub pages: u64,
+    /// Request weight charged for all pages
+    pub total_weight: u64,
+    /// Time the configured rate limit alone takes to admit every page
+    ///
+    /// Latency, retries and other requests sharing the limiter come on top.
+    pub estimated_duration: Duration,
+    client: BinanceClient,
+}
+
+impl BackfillPlan {
+    pub(crate) fn new(
+        client: BinanceClient,
+        symbol: String,
+        interval: Interval,
+        start_time: i64,
+        end_time: i64,
+    ) -> Self {
+        let config = client.config();
+        let candles = interval.candles_opening_within(start_time, end_time);
+        let pages = candles.div_ceil(config.max_kline_limit as u64);
+        let total_weight = pages * u64::from(config.weights.weight(Endpoints::klines(), None));
+
+        // The limiter admits a one-second burst at once, then refills evenly
+        let per_minute = u64::from(config.requests_per_minute);
+        let burst = per_minute.div_ceil(60);
+        let estimated_duration = Duration::from_secs_f64(
+            total_weight.saturating_sub(burst) as f64 * 60.0 / per_minute as f64,
+        );
+
+        Self {
+            symbol,
+            interval,
+            start_time,
+            end_time,
+            candles,
+            pages,
+            total_weight,
+            estimated_duration,
+            client,
+        }
+    }
+
+    /// Fetch the planned klines, oldest first
+    ///
+    /// Pages are requested one after another as the stream is polled, each
+    /// through the client's rate limiter.
+    pub fn execute(&self) -> impl Stream<Item = Result<Kline>> + Send + 'static {
+        let client = self.client.clone();
+        let symbol = self.symbol.clone();
+        let interval = self.interval;
+        let end_time = self.end_time;
+        let page_size = client.config().max_kline_limit;
+
+        stream::try_unfold(Some(self.start_time), move |start| {
+            let client = client.clone();
+            let symbol = symbol.clone();
+            async move {
+                let Some(start) = start.filter(|&start| start <= end_time) else {
+                    return Ok::<_, Error>(None);
+                };
+
+                let page = client.klines_page(&symbol, interval, start, Some(end_time)).await?;
+                let next = match page.last() {
+                    Some(last) if page.len() == page_size => {
+                        Some(last.close_time.timestamp_millis() + 1)
+                    }
+                    _ => None,
+                };
+
+                Ok((!page.is_empty()).then_some((page, next)))
+            }
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+    }
+}
+
+impl fmt::Debug for BackfillPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BackfillPlan")
+            .field("symbol", &self.symbol)
+            .field("interval", &self.interval)
+            .field("start_time", &self.start_time)
+            .field("end_time", &self.end_time)
+            .field("candles", &self.candles)
+            .field("pages", &self.pages)
+            .field("total_weight", &self.total_weight)
+            .field("estimated_duration", &self.estimated_duration)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BinanceConfig;
+
+    const JAN_1_2022: i64 = 1_640_995_200_000;
+    const DAY: i64 = 86_400_000;
+
+    fn plan(config: BinanceConfig, interval: Interval, start: i64, end: i64) -> BackfillPlan {
+        BinanceClient::new(config)
+            .unwrap()
+            .plan_klines_backfill("btcusdt", interval, start, end)
+    }
+
+    #[test]
+    fn test_backfill_plan_pages_and_weight() {
+        // One day of minutes, both ends inclusive: 1441 candles
+        let day = plan(BinanceConfig::new(false), Interval::Minutes1, JAN_1_2022, JAN_1_2022 + DAY);
+        assert_eq!(day.symbol, "BTCUSDT");
+        assert_eq!(day.candles, 1441);
+        assert_eq!(day.pages, 2);
+        assert_eq!(day.total_weight, 4);
+        // Within the one-second burst
+        assert_eq!(day.estimated_duration, Duration::ZERO);
+
+        // 2022 in 1s candles: 31,536,000 candles in 31,536 pages of weight 2
+        let year = plan(
+            BinanceConfig::new(false),
+            Interval::Seconds1,
+            JAN_1_2022,
+            JAN_1_2022 + 365 * DAY - 1,
+        );
+        assert_eq!(year.candles, 31_536_000);
+        assert_eq!(year.pages, 31_536);
+        assert_eq!(year.total_weight, 63_072);
+        // 1200 weight/minute after a burst of 20
+        assert_eq!(year.estimated_duration.as_secs(), (63_072 - 20) * 60 / 1200);
+
+        // Smaller pages and a slower limit
+        let mut config = BinanceConfig::new(false);
+        config.max_kline_limit = 500;
+        config.requests_per_minute = 600;
+        let slow = plan(config, Interval::Minutes1, JAN_1_2022, JAN_1_2022 + DAY);
+        assert_eq!(slow.pages, 3);
+        assert_eq!(slow.total_weight, 6);
+        assert_eq!(slow.estimated_duration, Duration::ZERO);
+    }
+}
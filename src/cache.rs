@@ -0,0 +1,216 @@
+//! Client wrapper that caches historical kline ranges
+//!
+//! Backtests tend to request the same closed ranges over and over. Closed
+//! candles never change, so [`CachingClient`] keeps them in a small
+//! least-recently-used cache instead of asking the API again.
+
+use crate::{
+    client::BinanceClient,
+    error::Result,
+    models::{normalize_symbol, Interval, Kline},
+};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+/// Cache key: `(symbol, interval, start_time, end_time)`
+type KlineKey = (String, Interval, i64, i64);
+
+/// [`BinanceClient`] with an in-memory LRU cache for
+/// [`get_klines_range`](Self::get_klines_range)
+///
+/// Only ranges that are entirely in the past and contain no open candle are
+/// cached; anything touching the present goes to the API every time. All
+/// other client methods are reachable through `Deref` and are not cached.
+/// Clones share the same cache.
+///
+/// # Example
+/// ```no_run
+/// use binance_connector::{BinanceClient, BinanceConfig, CachingClient, Interval};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = CachingClient::new(BinanceClient::new(BinanceConfig::new(false))?, 256);
+///
+///     // The second call is served from memory
+///     let start = 1_640_000_000_000;
+///     let end = start + 3_600_000;
+///     let first = client.get_klines_range("BTCUSDT", Interval::Minutes1, start, end).await?;
+///     let again = client.get_klines_range("BTCUSDT", Interval::Minutes1, start, end).await?;
+///     assert_eq!(first.len(), again.len());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CachingClient {
+    client: BinanceClient,
+    cache: Arc<Mutex<KlineCache>>,
+}
+
+impl CachingClient {
+    /// Wrap `client`, keeping at most `capacity` ranges (0 disables caching)
+    pub fn new(client: BinanceClient, capacity: usize) -> Self {
+        Self {
+            client,
+            cache: Arc::new(Mutex::new(KlineCache::new(capacity))),
+        }
+    }
+
+    /// Get klines with time range, served from the cache when possible
+    ///
+    /// Same arguments and result as [`BinanceClient::get_klines_range`].
+    pub async fn get_klines_range(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<Vec<Kline>> {
+        let key = (normalize_symbol(symbol), interval, start_time, end_time);
+        if let Some(klines) = self.lock().get(&key) {
+            return Ok(klines.as_ref().clone());
+        }
+
+        let klines = self.client.get_klines_range(symbol, interval, start_time, end_time).await?;
+
+        let now = chrono::Utc::now().timestamp_millis() + self.client.time_offset_ms();
+        let settled = end_time < now
+            && klines
+                .iter()
+                .all(|k| k.is_closed && k.close_time.timestamp_millis() < now);
+        if settled {
+            self.lock().insert(key, Arc::new(klines.clone()));
+        }
+
+        Ok(klines)
+    }
+
+    /// Drop every cached range
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Number of cached ranges
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Maximum number of cached ranges
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+
+    /// Change the maximum number of cached ranges, evicting the least
+    /// recently used ones if the cache is over the new limit
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut cache = self.lock();
+        cache.capacity = capacity;
+        while cache.entries.len() > capacity {
+            cache.evict_oldest();
+        }
+    }
+
+    /// The wrapped client
+    pub fn client(&self) -> &BinanceClient {
+        &self.client
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, KlineCache> {
+        // The cache holds no invariants a panic could break
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Deref for CachingClient {
+    type Target = BinanceClient;
+
+    fn deref(&self) -> &BinanceClient {
+        &self.client
+    }
+}
+
+/// LRU map from range to klines, ordered by a use counter
+struct KlineCache {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<KlineKey, (u64, Arc<Vec<Kline>>)>,
+}
+
+impl KlineCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, key: &KlineKey) -> Option<Arc<Vec<Kline>>> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(key).map(|(used, klines)| {
+            *used = tick;
+            Arc::clone(klines)
+        })
+    }
+
+    fn insert(&mut self, key: KlineKey, klines: Arc<Vec<Kline>>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            self.evict_oldest();
+        }
+        self.tick += 1;
+        self.entries.insert(key, (self.tick, klines));
+    }
+
+    fn evict_oldest(&mut self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|(_, (used, _))| *used)
+            .map(|(key, _)| key.clone());
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(start: i64) -> KlineKey {
+        ("BTCUSDT".to_string(), Interval::Minutes1, start, start + 60_000)
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut cache = KlineCache::new(2);
+        cache.insert(key(1), Arc::new(Vec::new()));
+        cache.insert(key(2), Arc::new(Vec::new()));
+
+        // Touch 1 so 2 becomes least recently used
+        assert!(cache.get(&key(1)).is_some());
+        cache.insert(key(3), Arc::new(Vec::new()));
+
+        assert!(cache.get(&key(1)).is_some());
+        assert!(cache.get(&key(2)).is_none());
+        assert!(cache.get(&key(3)).is_some());
+        assert_eq!(cache.entries.len(), 2);
+
+        let mut disabled = KlineCache::new(0);
+        disabled.insert(key(1), Arc::new(Vec::new()));
+        assert!(disabled.entries.is_empty());
+    }
+}
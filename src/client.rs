@@ -1,15 +1,34 @@
 //! Binance REST API client implementation
 
 use crate::{
+    backfill::BackfillPlan,
     config::BinanceConfig,
     endpoints::Endpoints,
     error::{Error, Result},
     models::*,
     rate_limiter::RateLimiter,
+    retry::BackoffPolicy,
+    telemetry,
+    websocket::{shutdown_signalled, BinanceWebSocket, StreamHandle},
 };
-use reqwest::{Client as HttpClient, Response, StatusCode};
-use std::sync::Arc;
-use tokio::time::{sleep, Duration};
+use futures::{stream, Stream, TryStreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::{header::CONTENT_TYPE, Client as HttpClient, Method, Response, StatusCode};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, Semaphore, SemaphorePermit};
+use tokio::time::{sleep, Duration, Instant};
+
+/// Clock offset (in ms) above which time sync logs a warning
+const TIME_DRIFT_WARN_MS: i64 = 1_000;
+
+/// Longest time span Binance accepts for one aggTrades request
+const AGG_TRADES_MAX_WINDOW_MS: i64 = 3_600_000;
+
+/// Most aggregate trades returned by one aggTrades request
+const AGG_TRADES_MAX_LIMIT: usize = 1000;
 
 /// Binance API client
 #[derive(Clone)]
@@ -17,6 +36,14 @@ pub struct BinanceClient {
     http_client: HttpClient,
     config: Arc<BinanceConfig>,
     rate_limiter: Arc<RateLimiter>,
+    time_offset_ms: Arc<AtomicI64>,
+    /// When `time_offset_ms` was last measured
+    last_time_sync: Arc<Mutex<Option<Instant>>>,
+    used_weight: Arc<AtomicU32>,
+    /// Rotation state for `api_keys`
+    key_rotation: Arc<KeyRotation>,
+    /// Permits for in-flight requests when `max_concurrent_requests` is set
+    concurrency: Option<Arc<Semaphore>>,
 }
 
 impl BinanceClient {
@@ -24,20 +51,96 @@ impl BinanceClient {
     pub fn new(config: BinanceConfig) -> Result<Self> {
         config.validate()?;
         
-        let http_client = HttpClient::builder()
-            .timeout(config.timeout())
-            .build()
-            .map_err(Error::HttpError)?;
-        
+        let http_client = build_http_client(&config)?;
         let rate_limiter = Arc::new(RateLimiter::new(config.requests_per_minute));
+        let concurrency = config.max_concurrent_requests.map(|n| Arc::new(Semaphore::new(n)));
+        let key_rotation = Arc::new(KeyRotation::new(config.api_keys.len()));
+        
+        Ok(Self {
+            http_client,
+            config: Arc::new(config),
+            rate_limiter,
+            time_offset_ms: Arc::new(AtomicI64::new(0)),
+            last_time_sync: Arc::new(Mutex::new(None)),
+            used_weight: Arc::new(AtomicU32::new(0)),
+            key_rotation,
+            concurrency,
+        })
+    }
+    
+    /// Clone this client with a modified copy of its configuration
+    /// 
+    /// The new client shares the rate limiter, time offset and used-weight
+    /// tracking with this one, so both count against the same budget. A new
+    /// rate limiter is only created if `requests_per_minute` changes, a new
+    /// concurrency limit only if `max_concurrent_requests` changes, a new
+    /// key rotation only if `api_keys` changes, and a
+    /// new HTTP client only if the timeout, User-Agent, proxy or local
+    /// address changes.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig};
+    /// 
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BinanceClient::new(BinanceConfig::new(false))?;
+    /// let patient = client.clone_with_config(|c| {
+    ///     c.timeout_seconds = 60;
+    ///     c.enable_retries = false;
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clone_with_config(&self, f: impl FnOnce(&mut BinanceConfig)) -> Result<Self> {
+        let mut config = (*self.config).clone();
+        f(&mut config);
+        config.validate()?;
+        
+        let http_client = if config.timeout_seconds == self.config.timeout_seconds
+            && config.user_agent == self.config.user_agent
+            && config.proxy_url == self.config.proxy_url
+            && config.local_address == self.config.local_address
+        {
+            self.http_client.clone()
+        } else {
+            build_http_client(&config)?
+        };
+        
+        let rate_limiter = if config.requests_per_minute == self.config.requests_per_minute {
+            Arc::clone(&self.rate_limiter)
+        } else {
+            Arc::new(RateLimiter::new(config.requests_per_minute))
+        };
+        
+        let concurrency = if config.max_concurrent_requests == self.config.max_concurrent_requests {
+            self.concurrency.clone()
+        } else {
+            config.max_concurrent_requests.map(|n| Arc::new(Semaphore::new(n)))
+        };
+        
+        let key_rotation = if config.api_keys == self.config.api_keys {
+            Arc::clone(&self.key_rotation)
+        } else {
+            Arc::new(KeyRotation::new(config.api_keys.len()))
+        };
         
         Ok(Self {
             http_client,
             config: Arc::new(config),
             rate_limiter,
+            time_offset_ms: Arc::clone(&self.time_offset_ms),
+            last_time_sync: Arc::clone(&self.last_time_sync),
+            used_weight: Arc::clone(&self.used_weight),
+            key_rotation,
+            concurrency,
         })
     }
     
+    /// Configuration this client was built with
+    pub fn config(&self) -> &BinanceConfig {
+        &self.config
+    }
+    
     /// Get current price for a symbol
     /// 
     /// # Arguments
@@ -58,12 +161,11 @@ impl BinanceClient {
     /// }
     /// ```
     pub async fn get_ticker_price(&self, symbol: &str) -> Result<Ticker> {
+        let symbol = &normalize_symbol(symbol);
         let endpoint = Endpoints::ticker_price();
         let url = format!("{}{}?symbol={}", self.config.get_base_url(), endpoint, symbol);
         
-        let response = self.request_with_retry(|| async {
-            self.rate_limiter.acquire().await;
-            
+        let response = self.request_with_retry(endpoint, Some(1), || async {
             self.http_client
                 .get(&url)
                 .send()
@@ -71,7 +173,8 @@ impl BinanceClient {
         }).await?;
         
         let ticker_response: BinanceTickerResponse = self.handle_response(response).await?;
-        Ok(ticker_response.to_ticker())
+        check_symbol(symbol, &ticker_response.symbol)?;
+        ticker_response.to_ticker()
     }
     
     /// Get prices for all symbols
@@ -79,9 +182,7 @@ impl BinanceClient {
         let endpoint = Endpoints::ticker_price();
         let url = format!("{}{}", self.config.get_base_url(), endpoint);
         
-        let response = self.request_with_retry(|| async {
-            self.rate_limiter.acquire().await;
-            
+        let response = self.request_with_retry(endpoint, None, || async {
             self.http_client
                 .get(&url)
                 .send()
@@ -89,7 +190,39 @@ impl BinanceClient {
         }).await?;
         
         let tickers: Vec<BinanceTickerResponse> = self.handle_response(response).await?;
-        Ok(tickers.into_iter().map(|t| t.to_ticker()).collect())
+        tickers.iter().map(|t| t.to_ticker()).collect()
+    }
+    
+    /// Stream prices for all symbols, parsed as the body arrives
+    /// 
+    /// Same data as [`get_all_ticker_prices`](Self::get_all_ticker_prices),
+    /// but tickers are decoded one at a time from the response body instead
+    /// of buffering and deserializing the whole array, keeping peak memory
+    /// low for the ~2000-symbol payload.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig};
+    /// use futures::TryStreamExt;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BinanceClient::new(BinanceConfig::new(false))?;
+    ///     
+    ///     let mut tickers = std::pin::pin!(client.get_all_ticker_prices_stream());
+    ///     while let Some(ticker) = tickers.try_next().await? {
+    ///         println!("{}: {}", ticker.symbol, ticker.price);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_all_ticker_prices_stream(&self) -> impl Stream<Item = Result<Ticker>> + Send + 'static {
+        let endpoint = Endpoints::ticker_price();
+        let url = format!("{}{}", self.config.get_base_url(), endpoint);
+        
+        self.clone()
+            .json_array_stream::<BinanceTickerResponse>(endpoint, url)
+            .and_then(|t| async move { t.to_ticker() })
     }
     
     /// Get 24-hour ticker statistics
@@ -97,12 +230,11 @@ impl BinanceClient {
     /// # Arguments
     /// * `symbol` - Trading pair symbol (e.g., "BTCUSDT")
     pub async fn get_ticker_24h(&self, symbol: &str) -> Result<Ticker24h> {
+        let symbol = &normalize_symbol(symbol);
         let endpoint = Endpoints::ticker_24h();
         let url = format!("{}{}?symbol={}", self.config.get_base_url(), endpoint, symbol);
         
-        let response = self.request_with_retry(|| async {
-            self.rate_limiter.acquire().await;
-            
+        let response = self.request_with_retry(endpoint, Some(1), || async {
             self.http_client
                 .get(&url)
                 .send()
@@ -110,15 +242,175 @@ impl BinanceClient {
         }).await?;
         
         let ticker_response: Binance24hTickerResponse = self.handle_response(response).await?;
+        check_symbol(symbol, &ticker_response.symbol)?;
         ticker_response.to_ticker24h()
     }
     
+    /// Get 24-hour ticker statistics for several symbols in one request
+    /// 
+    /// Request weight scales with the number of symbols: 2 for up to 20,
+    /// 40 for up to 100 and 80 beyond that (the same as fetching all symbols).
+    /// An empty slice returns an empty vec without calling the API.
+    /// 
+    /// # Arguments
+    /// * `symbols` - Trading pair symbols (e.g., `&["BTCUSDT", "ETHUSDT"]`)
+    pub async fn get_ticker_24h_multi(&self, symbols: &[&str]) -> Result<Vec<Ticker24h>> {
+        if symbols.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let symbols: Vec<String> = symbols.iter().map(|s| normalize_symbol(s)).collect();
+        let symbols_json = serde_json::to_string(&symbols)?;
+        let endpoint = Endpoints::ticker_24h();
+        let url = format!(
+            "{}{}?symbols={}",
+            self.config.get_base_url(),
+            endpoint,
+            url::form_urlencoded::byte_serialize(symbols_json.as_bytes()).collect::<String>()
+        );
+        
+        let response = self.request_with_retry(endpoint, Some(symbols.len()), || async {
+            self.http_client
+                .get(&url)
+                .send()
+                .await
+        }).await?;
+        
+        let tickers: Vec<Binance24hTickerResponse> = self.handle_response(response).await?;
+        tickers.into_iter().map(|t| t.to_ticker24h()).collect()
+    }
+    
+    /// Get 24-hour ticker statistics for all symbols
+    /// 
+    /// Costs 80 request weight; prefer
+    /// [`get_ticker_24h_multi`](Self::get_ticker_24h_multi) for a known set.
+    pub async fn get_all_ticker_24h(&self) -> Result<Vec<Ticker24h>> {
+        let endpoint = Endpoints::ticker_24h();
+        let url = format!("{}{}", self.config.get_base_url(), endpoint);
+        
+        let response = self.request_with_retry(endpoint, None, || async {
+            self.http_client
+                .get(&url)
+                .send()
+                .await
+        }).await?;
+        
+        let tickers: Vec<Binance24hTickerResponse> = self.handle_response(response).await?;
+        tickers.into_iter().map(|t| t.to_ticker24h()).collect()
+    }
+    
+    /// Get the `n` symbols with the highest 24h quote volume, highest first
+    /// 
+    /// With `quote_asset` (e.g. `"USDT"`), only symbols ending in it are
+    /// ranked. Volumes in different quote assets are not comparable, so
+    /// filtering is usually what a scanner wants.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig};
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BinanceClient::new(BinanceConfig::new(false))?;
+    ///     
+    ///     for ticker in client.top_symbols_by_volume(20, Some("USDT")).await? {
+    ///         println!("{}: {:.0} USDT", ticker.symbol, ticker.quote_volume);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn top_symbols_by_volume(
+        &self,
+        n: usize,
+        quote_asset: Option<&str>,
+    ) -> Result<Vec<Ticker24h>> {
+        let quote_asset = quote_asset.map(|q| q.to_uppercase());
+        let mut tickers: Vec<Ticker24h> = self
+            .get_all_ticker_24h()
+            .await?
+            .into_iter()
+            .filter(|t| quote_asset.as_ref().is_none_or(|q| t.symbol.ends_with(q.as_str())))
+            .collect();
+        
+        tickers.sort_by(|a, b| b.quote_volume.total_cmp(&a.quote_volume));
+        tickers.truncate(n);
+        Ok(tickers)
+    }
+    
+    /// Get best bid/ask for a symbol
+    pub async fn get_book_ticker(&self, symbol: &str) -> Result<BookTicker> {
+        let symbol = &normalize_symbol(symbol);
+        let endpoint = Endpoints::book_ticker();
+        let url = format!("{}{}?symbol={}", self.config.get_base_url(), endpoint, symbol);
+        
+        let response = self.request_with_retry(endpoint, Some(1), || async {
+            self.http_client
+                .get(&url)
+                .send()
+                .await
+        }).await?;
+        
+        let ticker: BinanceBookTickerResponse = self.handle_response(response).await?;
+        check_symbol(symbol, &ticker.symbol)?;
+        ticker.to_book_ticker()
+    }
+    
+    /// Get best bid/ask for all symbols
+    pub async fn get_all_book_tickers(&self) -> Result<Vec<BookTicker>> {
+        let endpoint = Endpoints::book_ticker();
+        let url = format!("{}{}", self.config.get_base_url(), endpoint);
+        
+        let response = self.request_with_retry(endpoint, None, || async {
+            self.http_client
+                .get(&url)
+                .send()
+                .await
+        }).await?;
+        
+        let tickers: Vec<BinanceBookTickerResponse> = self.handle_response(response).await?;
+        tickers.iter().map(|t| t.to_book_ticker()).collect()
+    }
+    
+    /// Get every symbol's bid/ask spread in basis points, tightest first
+    /// 
+    /// Symbols with an empty bid or ask are skipped.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig};
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BinanceClient::new(BinanceConfig::new(false))?;
+    ///     
+    ///     for (symbol, bps) in client.spreads_ranked().await?.iter().rev().take(10) {
+    ///         println!("{}: {:.1} bps", symbol, bps);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn spreads_ranked(&self) -> Result<Vec<(String, f64)>> {
+        let mut spreads: Vec<(String, f64)> = self
+            .get_all_book_tickers()
+            .await?
+            .into_iter()
+            .filter_map(|t| t.spread_bps().map(|bps| (t.symbol, bps)))
+            .collect();
+        
+        spreads.sort_by(|a, b| a.1.total_cmp(&b.1));
+        Ok(spreads)
+    }
+    
     /// Get klines (candlestick data)
     /// 
+    /// With [`BinanceConfig::retry_empty_klines`] set, an empty response is
+    /// refetched as long as `backoff` allows another attempt.
+    /// 
     /// # Arguments
     /// * `symbol` - Trading pair symbol
     /// * `interval` - Candlestick interval
-    /// * `limit` - Number of candles (max 1000, default 500)
+    /// * `limit` - Number of candles (max `config.max_kline_limit`, `None` uses the
+    ///   server default of 500)
     /// 
     /// # Example
     /// ```no_run
@@ -138,43 +430,64 @@ impl BinanceClient {
         &self,
         symbol: &str,
         interval: Interval,
-        limit: usize,
+        limit: impl Into<Option<usize>>,
     ) -> Result<Vec<Kline>> {
-        if limit > 1000 {
-            return Err(Error::ConfigError(
-                format!("Limit {} exceeds maximum of 1000", limit)
-            ));
-        }
+        let symbol = &normalize_symbol(symbol);
+        let limit = limit.into();
+        check_limit(limit, self.config.max_kline_limit)?;
         
         let endpoint = Endpoints::klines();
         let url = format!(
-            "{}{}?symbol={}&interval={}&limit={}",
+            "{}{}?symbol={}&interval={}{}",
             self.config.get_base_url(),
             endpoint,
             symbol,
             interval,
-            limit
+            limit_param(limit)
         );
         
-        let response = self.request_with_retry(|| async {
-            self.rate_limiter.acquire().await;
-            
-            self.http_client
-                .get(&url)
-                .send()
-                .await
-        }).await?;
+        // The latest candles always exist for a trading symbol
+        self.fetch_klines(endpoint, &url, symbol, interval, true).await
+    }
+    
+    /// Get the most recent `count` fully-closed klines
+    /// 
+    /// [`get_klines`](Self::get_klines) includes the candle that is still
+    /// forming as its last element. This fetches one extra candle and drops
+    /// the in-progress one, judged against local time corrected by the last
+    /// [`sync_time`](Self::sync_time) offset.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Trading pair symbol
+    /// * `interval` - Candlestick interval
+    /// * `count` - Number of closed candles (max `config.max_kline_limit - 1`)
+    pub async fn get_closed_klines(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        count: usize,
+    ) -> Result<Vec<Kline>> {
+        check_limit(Some(count), self.config.max_kline_limit.saturating_sub(1))?;
         
-        let klines_response: Vec<BinanceKlineResponse> = self.handle_response(response).await?;
+        let mut klines = self.get_klines(symbol, interval, count + 1).await?;
         
-        klines_response
-            .into_iter()
-            .map(|k| k.to_kline(symbol.to_string()))
-            .collect()
+        let now = chrono::Utc::now().timestamp_millis() + self.time_offset_ms();
+        if klines.last().is_some_and(|k| k.close_time.timestamp_millis() > now) {
+            klines.pop();
+        }
+        
+        let excess = klines.len().saturating_sub(count);
+        klines.drain(..excess);
+        
+        Ok(klines)
     }
     
     /// Get klines with time range
     /// 
+    /// With `config.retry_empty_klines` set, an empty response is retried if
+    /// the range spans at least one full interval before the current server
+    /// time.
+    /// 
     /// # Arguments
     /// * `symbol` - Trading pair symbol
     /// * `interval` - Candlestick interval
@@ -187,6 +500,7 @@ impl BinanceClient {
         start_time: i64,
         end_time: i64,
     ) -> Result<Vec<Kline>> {
+        let symbol = &normalize_symbol(symbol);
         let endpoint = Endpoints::klines();
         let url = format!(
             "{}{}?symbol={}&interval={}&startTime={}&endTime={}",
@@ -198,9 +512,167 @@ impl BinanceClient {
             end_time
         );
         
-        let response = self.request_with_retry(|| async {
-            self.rate_limiter.acquire().await;
+        let now = chrono::Utc::now().timestamp_millis() + self.time_offset_ms();
+        let expect_rows = end_time.min(now) - start_time >= interval.duration_ms();
+        self.fetch_klines(endpoint, &url, symbol, interval, expect_rows).await
+    }
+    
+    /// Fetch and convert klines from `url`
+    /// 
+    /// If `expect_rows` and `config.retry_empty_klines` are set, an empty
+    /// array is refetched after the backoff delay, up to the
+    /// [`retry_policy`](BinanceConfig::retry_policy)'s `max_attempts`
+    /// fetches in all.
+    async fn fetch_klines(
+        &self,
+        endpoint: &str,
+        url: &str,
+        symbol: &str,
+        interval: Interval,
+        expect_rows: bool,
+    ) -> Result<Vec<Kline>> {
+        let mut fetches = 0;
+        
+        loop {
+            fetches += 1;
+            let response = self.request_with_retry(endpoint, None, || async {
+                self.http_client
+                    .get(url)
+                    .send()
+                    .await
+            }).await?;
+            
+            let klines_response: Vec<BinanceKlineResponse> = self.handle_response(response).await?;
             
+            if klines_response.is_empty()
+                && expect_rows
+                && self.config.retry_empty_klines
+                && self.config.retry_policy().should_retry(fetches)
+            {
+                sleep(self.config.retry_policy().delay(fetches - 1)).await;
+                continue;
+            }
+            
+            return klines_response
+                .into_iter()
+                .map(|k| k.to_kline(symbol.to_string(), interval))
+                .collect();
+        }
+    }
+    
+    /// Stream a symbol's complete kline history, oldest first
+    /// 
+    /// Starts from the symbol's first candle (requesting from the epoch makes
+    /// Binance begin at the listing) and pages forward `max_kline_limit`
+    /// candles at a time until it reaches the present. The candle that is
+    /// still forming is not included. Each page goes through the rate limiter.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig, Interval};
+    /// use futures::TryStreamExt;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BinanceClient::new(BinanceConfig::new(false))?;
+    ///     
+    ///     let history: Vec<_> = client.get_klines_all("BTCUSDT", Interval::Days1).try_collect().await?;
+    ///     println!("{} daily candles since listing", history.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_klines_all(
+        &self,
+        symbol: &str,
+        interval: Interval,
+    ) -> impl Stream<Item = Result<Kline>> + Send + 'static {
+        let client = self.clone();
+        let symbol = normalize_symbol(symbol);
+        
+        stream::try_unfold(Some(0), move |start| {
+            let client = client.clone();
+            let symbol = symbol.clone();
+            async move {
+                let Some(start) = start else {
+                    return Ok::<_, Error>(None);
+                };
+                
+                let mut page = client.klines_page(&symbol, interval, start, None).await?;
+                let full_page = page.len() == client.config.max_kline_limit;
+                
+                let now = chrono::Utc::now().timestamp_millis() + client.time_offset_ms();
+                let fetched = page.len();
+                page.retain(|k| k.close_time.timestamp_millis() <= now);
+                let caught_up = !full_page || page.len() < fetched;
+                
+                let next = match page.last() {
+                    Some(last) if !caught_up => Some(last.close_time.timestamp_millis() + 1),
+                    _ => None,
+                };
+                
+                Ok((!page.is_empty()).then_some((page, next)))
+            }
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+    }
+    
+    /// Preview fetching every `interval` kline opening from `start_time` to
+    /// `end_time` (milliseconds, inclusive)
+    /// 
+    /// Computes the pages, request weight and rate-limited duration of the
+    /// job without sending anything; [`BackfillPlan::execute`] then runs it.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig, Interval};
+    /// use futures::TryStreamExt;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BinanceClient::new(BinanceConfig::new(false))?;
+    ///     
+    ///     let plan = client.plan_klines_backfill("BTCUSDT", Interval::Minutes1, 1_640_995_200_000, 1_672_531_199_999);
+    ///     println!("{} pages, weight {}, at least {:?}", plan.pages, plan.total_weight, plan.estimated_duration);
+    ///     
+    ///     let klines: Vec<_> = plan.execute().try_collect().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn plan_klines_backfill(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        start_time: i64,
+        end_time: i64,
+    ) -> BackfillPlan {
+        BackfillPlan::new(self.clone(), normalize_symbol(symbol), interval, start_time, end_time)
+    }
+    
+    /// One page of up to `max_kline_limit` klines opening at or after
+    /// `start_time` (and at or before `end_time`, if given)
+    pub(crate) async fn klines_page(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        start_time: i64,
+        end_time: Option<i64>,
+    ) -> Result<Vec<Kline>> {
+        let endpoint = Endpoints::klines();
+        let mut url = format!(
+            "{}{}?symbol={}&interval={}&startTime={}&limit={}",
+            self.config.get_base_url(),
+            endpoint,
+            symbol,
+            interval,
+            start_time,
+            self.config.max_kline_limit
+        );
+        if let Some(end_time) = end_time {
+            url.push_str(&format!("&endTime={}", end_time));
+        }
+        
+        let response = self.request_with_retry(endpoint, None, || async {
             self.http_client
                 .get(&url)
                 .send()
@@ -211,7 +683,7 @@ impl BinanceClient {
         
         klines_response
             .into_iter()
-            .map(|k| k.to_kline(symbol.to_string()))
+            .map(|k| k.to_kline(symbol.to_string(), interval))
             .collect()
     }
     
@@ -219,20 +691,28 @@ impl BinanceClient {
     /// 
     /// # Arguments
     /// * `symbol` - Trading pair symbol
-    /// * `limit` - Depth (valid: 5, 10, 20, 50, 100, 500, 1000, 5000)
-    pub async fn get_depth(&self, symbol: &str, limit: usize) -> Result<OrderBook> {
+    /// * `limit` - Depth (valid: 5, 10, 20, 50, 100, 500, 1000, 5000; `None` uses
+    ///   the server default of 100)
+    /// 
+    /// The effective limit is kept in `requested_limit`; pass it to
+    /// [`OrderBook::is_full_depth`] to tell a thin book from a truncated one.
+    pub async fn get_depth(
+        &self,
+        symbol: &str,
+        limit: impl Into<Option<usize>>,
+    ) -> Result<OrderBook> {
+        let symbol = &normalize_symbol(symbol);
+        let limit = limit.into();
         let endpoint = Endpoints::depth();
         let url = format!(
-            "{}{}?symbol={}&limit={}",
+            "{}{}?symbol={}{}",
             self.config.get_base_url(),
             endpoint,
             symbol,
-            limit
+            limit_param(limit)
         );
         
-        let response = self.request_with_retry(|| async {
-            self.rate_limiter.acquire().await;
-            
+        let response = self.request_with_retry(endpoint, limit, || async {
             self.http_client
                 .get(&url)
                 .send()
@@ -240,31 +720,129 @@ impl BinanceClient {
         }).await?;
         
         let depth_response: BinanceDepthResponse = self.handle_response(response).await?;
-        Ok(depth_response.to_order_book(symbol.to_string()))
+        let mut book = depth_response.to_order_book(symbol.to_string())?;
+        // Binance defaults to 100 levels when no limit is sent
+        book.requested_limit = Some(limit.unwrap_or(100));
+        Ok(book)
+    }
+    
+    /// Get the current order book and a stream keeping it up to date
+    /// 
+    /// Returns the REST snapshot right away together with a receiver of the
+    /// full book after every synchronized diff update, so consumers have a
+    /// usable book before the first stream message. Missed updates trigger
+    /// an automatic re-sync (see [`BinanceWebSocket::managed_order_book`]).
+    /// 
+    /// # Arguments
+    /// * `symbol` - Trading pair symbol
+    /// * `depth` - Keep only the best N levels per side (`None` keeps all)
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig};
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BinanceClient::new(BinanceConfig::new(false))?;
+    ///     let (book, mut updates) = client.live_order_book("BTCUSDT", 20).await?;
+    ///     println!("Microprice: {:?}", book.microprice());
+    ///     
+    ///     while let Some(Ok(book)) = updates.recv().await {
+    ///         println!("Microprice: {:?}", book.microprice());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn live_order_book(
+        &self,
+        symbol: &str,
+        depth: impl Into<Option<usize>>,
+    ) -> Result<(OrderBook, mpsc::Receiver<Result<OrderBook>>)> {
+        let ws = BinanceWebSocket::new((*self.config).clone())?;
+        ws.live_order_book(self.clone(), symbol, depth.into()).await
     }
     
     /// Get recent trades
     /// 
+    /// Only the latest trades are available here; use
+    /// [`get_historical_trades`](Self::get_historical_trades) to page back
+    /// from a trade id.
+    /// 
     /// # Arguments
     /// * `symbol` - Trading pair symbol
-    /// * `limit` - Number of trades (max 1000, default 500)
-    pub async fn get_recent_trades(&self, symbol: &str, limit: usize) -> Result<Vec<Trade>> {
-        let endpoint = Endpoints::trades();
+    /// * `limit` - Number of trades (max 1000, `None` uses the server default of 500)
+    pub async fn get_recent_trades(
+        &self,
+        symbol: &str,
+        limit: impl Into<Option<usize>>,
+    ) -> Result<Vec<Trade>> {
+        let symbol = &normalize_symbol(symbol);
+        let endpoint = Endpoints::trades();
         let url = format!(
-            "{}{}?symbol={}&limit={}",
+            "{}{}?symbol={}{}",
             self.config.get_base_url(),
             endpoint,
             symbol,
-            limit
+            limit_param(limit.into())
         );
         
-        let response = self.request_with_retry(|| async {
-            self.rate_limiter.acquire().await;
-            
-            self.http_client
-                .get(&url)
-                .send()
-                .await
+        self.fetch_trades(endpoint, &url, symbol, None).await
+    }
+    
+    /// Get older trades starting from a trade id
+    /// 
+    /// Uses `/api/v3/historicalTrades`, which requires an API key (no
+    /// signature). Without `from_id` the most recent trades are returned.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Trading pair symbol
+    /// * `limit` - Number of trades (max 1000, `None` uses the server default of 500)
+    /// * `from_id` - Return trades with id >= this value
+    pub async fn get_historical_trades(
+        &self,
+        symbol: &str,
+        limit: impl Into<Option<usize>>,
+        from_id: impl Into<Option<i64>>,
+    ) -> Result<Vec<Trade>> {
+        let api_key = self
+            .config
+            .api_key
+            .as_deref()
+            .or_else(|| self.config.api_keys.first().map(|(api_key, _)| api_key.as_str()))
+            .ok_or_else(|| {
+                Error::ConfigError("API key is required for historical trades".to_string())
+            })?;
+        
+        let symbol = &normalize_symbol(symbol);
+        let endpoint = Endpoints::historical_trades();
+        let mut url = format!(
+            "{}{}?symbol={}{}",
+            self.config.get_base_url(),
+            endpoint,
+            symbol,
+            limit_param(limit.into())
+        );
+        if let Some(id) = from_id.into() {
+            url.push_str(&format!("&fromId={}", id));
+        }
+        
+        self.fetch_trades(endpoint, &url, symbol, Some(api_key)).await
+    }
+    
+    /// Fetch and convert a list of raw trades, sending the API key if given
+    async fn fetch_trades(
+        &self,
+        endpoint: &str,
+        url: &str,
+        symbol: &str,
+        api_key: Option<&str>,
+    ) -> Result<Vec<Trade>> {
+        let response = self.request_with_retry(endpoint, None, || async {
+            let mut request = self.http_client.get(url);
+            if let Some(key) = api_key {
+                request = request.header("X-MBX-APIKEY", key);
+            }
+            request.send().await
         }).await?;
         
         #[derive(serde::Deserialize)]
@@ -281,16 +859,136 @@ impl BinanceClient {
         
         let trades_response: Vec<TradeResponse> = self.handle_response(response).await?;
         
-        Ok(trades_response.into_iter().map(|t| Trade {
+        trades_response.into_iter().map(|t| Ok(Trade {
             id: t.id,
             symbol: symbol.to_string(),
-            price: t.price.parse().unwrap_or(0.0),
-            quantity: t.qty.parse().unwrap_or(0.0),
-            quote_quantity: t.quote_qty.parse().unwrap_or(0.0),
-            time: chrono::DateTime::from_timestamp_millis(t.time)
-                .unwrap_or_default(),
+            price: parse_f64("price", &t.price)?,
+            quantity: parse_f64("qty", &t.qty)?,
+            quote_quantity: parse_f64("quoteQty", &t.quote_qty)?,
+            time: chrono::DateTime::from_timestamp_millis(t.time).ok_or_else(|| {
+                Error::DeserializationError("Invalid trade time".to_string())
+            })?,
             is_buyer_maker: t.is_buyer_maker,
-        }).collect())
+        })).collect()
+    }
+    
+    /// Get compressed/aggregate trades
+    /// 
+    /// Without `from_id` or a time range the most recent trades are returned.
+    /// Binance rejects time ranges of an hour or more; use
+    /// [`get_agg_trades_window`](Self::get_agg_trades_window) for longer spans.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Trading pair symbol
+    /// * `from_id` - Return trades with aggregate id >= this value
+    /// * `start_time` - Start time in milliseconds
+    /// * `end_time` - End time in milliseconds
+    /// * `limit` - Number of trades (max 1000, `None` uses the server default of 500)
+    pub async fn get_agg_trades(
+        &self,
+        symbol: &str,
+        from_id: Option<i64>,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: impl Into<Option<usize>>,
+    ) -> Result<Vec<AggTrade>> {
+        let symbol = &normalize_symbol(symbol);
+        let limit = limit.into();
+        check_limit(limit, AGG_TRADES_MAX_LIMIT)?;
+        
+        let mut url = format!(
+            "{}{}?symbol={}{}",
+            self.config.get_base_url(),
+            Endpoints::agg_trades(),
+            symbol,
+            limit_param(limit)
+        );
+        if let Some(from_id) = from_id {
+            url.push_str(&format!("&fromId={}", from_id));
+        }
+        if let Some(start_time) = start_time {
+            url.push_str(&format!("&startTime={}", start_time));
+        }
+        if let Some(end_time) = end_time {
+            url.push_str(&format!("&endTime={}", end_time));
+        }
+        
+        let response = self.request_with_retry(Endpoints::agg_trades(), None, || async {
+            self.http_client
+                .get(&url)
+                .send()
+                .await
+        }).await?;
+        
+        let trades: Vec<BinanceAggTradeResponse> = self.handle_response(response).await?;
+        trades.iter().map(|t| t.to_agg_trade(symbol.to_string())).collect()
+    }
+    
+    /// Get all aggregate trades in a time range of any length
+    /// 
+    /// Splits the range into windows under one hour and pages through busy
+    /// windows by trade id, so nothing is truncated by the per-request limit.
+    /// Requests go through the rate limiter one at a time. Results are in
+    /// chronological order with duplicates removed.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Trading pair symbol
+    /// * `start_time` - Start time in milliseconds (inclusive)
+    /// * `end_time` - End time in milliseconds (inclusive)
+    pub async fn get_agg_trades_window(
+        &self,
+        symbol: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<Vec<AggTrade>> {
+        if start_time > end_time {
+            return Err(Error::InvalidDateRange {
+                start: start_time.to_string(),
+                end: end_time.to_string(),
+            });
+        }
+        
+        let mut trades: Vec<AggTrade> = Vec::new();
+        let mut window_start = start_time;
+        
+        while window_start <= end_time {
+            let window_end = (window_start + AGG_TRADES_MAX_WINDOW_MS - 1).min(end_time);
+            let mut page = self
+                .get_agg_trades(
+                    symbol,
+                    None,
+                    Some(window_start),
+                    Some(window_end),
+                    AGG_TRADES_MAX_LIMIT,
+                )
+                .await?;
+            
+            // A full page may have been cut short; continue by id until past the window
+            while page.len() == AGG_TRADES_MAX_LIMIT {
+                let next_id = page[page.len() - 1].id + 1;
+                trades.append(&mut page);
+                
+                page = self
+                    .get_agg_trades(symbol, Some(next_id), None, None, AGG_TRADES_MAX_LIMIT)
+                    .await?;
+                let in_window = page
+                    .iter()
+                    .take_while(|t| t.time.timestamp_millis() <= window_end)
+                    .count();
+                if in_window < page.len() {
+                    page.truncate(in_window);
+                    break;
+                }
+            }
+            trades.append(&mut page);
+            
+            window_start = window_end + 1;
+        }
+        
+        trades.sort_by_key(|t| t.id);
+        trades.dedup_by_key(|t| t.id);
+        
+        Ok(trades)
     }
     
     /// Get exchange information (all symbols)
@@ -298,9 +996,7 @@ impl BinanceClient {
         let endpoint = Endpoints::exchange_info();
         let url = format!("{}{}", self.config.get_base_url(), endpoint);
         
-        let response = self.request_with_retry(|| async {
-            self.rate_limiter.acquire().await;
-            
+        let response = self.request_with_retry(endpoint, None, || async {
             self.http_client
                 .get(&url)
                 .send()
@@ -309,11 +1005,66 @@ impl BinanceClient {
         
         #[derive(serde::Deserialize)]
         struct ExchangeInfo {
-            symbols: Vec<Symbol>,
+            symbols: Vec<BinanceSymbolResponse>,
         }
         
         let info: ExchangeInfo = self.handle_response(response).await?;
-        Ok(info.symbols)
+        info.symbols.iter().map(|s| s.to_symbol()).collect()
+    }
+    
+    /// Check which of `symbols` exist and are currently trading
+    /// 
+    /// Fetches exchange info once and maps each requested symbol (as given)
+    /// to `true` if it is listed with status `TRADING`. Symbols are matched
+    /// after [`normalize_symbol`].
+    /// Use [`require_symbols`](Self::require_symbols) to fail on the first
+    /// bad symbol instead.
+    pub async fn validate_symbols(&self, symbols: &[&str]) -> Result<HashMap<String, bool>> {
+        let listed: HashMap<String, bool> = self
+            .get_exchange_info()
+            .await?
+            .into_iter()
+            .map(|s| {
+                let trading = s.is_trading();
+                (s.symbol, trading)
+            })
+            .collect();
+        
+        Ok(symbols
+            .iter()
+            .map(|symbol| {
+                let trading = listed.get(&normalize_symbol(symbol)).copied().unwrap_or(false);
+                (symbol.to_string(), trading)
+            })
+            .collect())
+    }
+    
+    /// Trading status of every listed symbol, keyed by symbol
+    /// 
+    /// Keep the previous map around and pass both to
+    /// [`status_changes`](crate::models::status_changes) to detect listings
+    /// and halts between polls.
+    pub async fn symbol_status_map(&self) -> Result<HashMap<String, SymbolStatus>> {
+        Ok(self
+            .get_exchange_info()
+            .await?
+            .into_iter()
+            .map(|s| {
+                let status = s.trading_status();
+                (s.symbol, status)
+            })
+            .collect())
+    }
+    
+    /// Fail with `InvalidSymbol` unless every one of `symbols` is trading
+    /// 
+    /// Reports the first bad symbol in `symbols` order.
+    pub async fn require_symbols(&self, symbols: &[&str]) -> Result<()> {
+        let valid = self.validate_symbols(symbols).await?;
+        match symbols.iter().find(|s| !valid[**s]) {
+            Some(symbol) => Err(Error::InvalidSymbol(symbol.to_string())),
+            None => Ok(()),
+        }
     }
     
     /// Get server time
@@ -321,9 +1072,7 @@ impl BinanceClient {
         let endpoint = Endpoints::time();
         let url = format!("{}{}", self.config.get_base_url(), endpoint);
         
-        let response = self.request_with_retry(|| async {
-            self.rate_limiter.acquire().await;
-            
+        let response = self.request_with_retry(endpoint, None, || async {
             self.http_client
                 .get(&url)
                 .send()
@@ -340,6 +1089,102 @@ impl BinanceClient {
         Ok(time.server_time)
     }
     
+    /// Synchronize with server time
+    ///
+    /// Measures the offset between the server clock and the local clock
+    /// (server minus local, in milliseconds) and stores it for later use.
+    /// Returns the new offset. Within `time_sync_max_age_seconds` of the last
+    /// sync the stored offset is returned without calling the server; use
+    /// [`force_time_sync`](Self::force_time_sync) to measure regardless.
+    pub async fn sync_time(&self) -> Result<i64> {
+        let max_age = Duration::from_secs(self.config.time_sync_max_age_seconds);
+        let fresh = self
+            .lock_last_time_sync()
+            .is_some_and(|synced| synced.elapsed() < max_age);
+        if fresh {
+            return Ok(self.time_offset_ms());
+        }
+        
+        self.force_time_sync().await
+    }
+    
+    /// Measure and store the server time offset now, ignoring the
+    /// staleness window of [`sync_time`](Self::sync_time)
+    pub async fn force_time_sync(&self) -> Result<i64> {
+        let (_, offset) = self.measure_time_offset().await?;
+        self.time_offset_ms.store(offset, Ordering::Relaxed);
+        *self.lock_last_time_sync() = Some(Instant::now());
+        
+        Ok(offset)
+    }
+    
+    fn lock_last_time_sync(&self) -> std::sync::MutexGuard<'_, Option<Instant>> {
+        self.last_time_sync.lock().unwrap_or_else(|e| e.into_inner())
+    }
+    
+    /// Fetch server time and its offset from the local clock
+    async fn measure_time_offset(&self) -> Result<(i64, i64)> {
+        let before = chrono::Utc::now().timestamp_millis();
+        let server_time = self.get_server_time().await?;
+        let after = chrono::Utc::now().timestamp_millis();
+        
+        // Assume the server stamped the response halfway through the round trip
+        Ok((server_time, server_time - (before + after) / 2))
+    }
+    
+    /// Last measured server clock offset in milliseconds (0 until synced)
+    pub fn time_offset_ms(&self) -> i64 {
+        self.time_offset_ms.load(Ordering::Relaxed)
+    }
+    
+    /// Periodically re-sync server time in a background task
+    ///
+    /// Runs [`force_time_sync`](Self::force_time_sync) immediately and then
+    /// every `interval`, logging a warning whenever the offset exceeds one
+    /// second.
+    /// Stop the task with [`StreamHandle::shutdown`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig};
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BinanceClient::new(BinanceConfig::new(false))?;
+    ///     let handle = client.spawn_time_sync(Duration::from_secs(300));
+    ///
+    ///     // ... use the client ...
+    ///
+    ///     handle.shutdown().await;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn spawn_time_sync(&self, interval: Duration) -> StreamHandle {
+        let client = self.clone();
+        
+        StreamHandle::spawn(move |mut shutdown| async move {
+            let mut ticker = tokio::time::interval(interval);
+            
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        match client.force_time_sync().await {
+                            Ok(offset) if offset.abs() > TIME_DRIFT_WARN_MS => {
+                                tracing::warn!(offset_ms = offset, "local clock drifted from Binance server time");
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                tracing::warn!(error = %e, "server time sync failed");
+                            }
+                        }
+                    }
+                    _ = shutdown_signalled(&mut shutdown) => break,
+                }
+            }
+        })
+    }
+    
     /// Ping the server (health check)
     pub async fn ping(&self) -> Result<bool> {
         let endpoint = Endpoints::ping();
@@ -350,6 +1195,7 @@ impl BinanceClient {
             .send()
             .await
             .map_err(Error::HttpError)?;
+        self.record_used_weight(&response);
         
         Ok(response.status() == StatusCode::OK)
     }
@@ -359,50 +1205,647 @@ impl BinanceClient {
         self.ping().await
     }
     
+    /// Detailed health check for monitoring
+    /// 
+    /// Pings the server and measures latency, then fetches server time to
+    /// report the local clock skew. Unlike [`sync_time`](Self::sync_time) this
+    /// does not change the stored offset.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig};
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BinanceClient::new(BinanceConfig::new(false))?;
+    ///     let report = client.health_report().await?;
+    ///     
+    ///     assert!(report.reachable);
+    ///     assert!(report.clock_skew_ms.abs() < 1000, "clock drifted");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn health_report(&self) -> Result<HealthReport> {
+        let start = Instant::now();
+        let reachable = self.ping().await?;
+        let latency = start.elapsed();
+        
+        let (server_time, clock_skew_ms) = self.measure_time_offset().await?;
+        
+        Ok(HealthReport {
+            reachable,
+            latency,
+            server_time: chrono::DateTime::from_timestamp_millis(server_time).ok_or_else(|| {
+                Error::DeserializationError("Invalid server time".to_string())
+            })?,
+            clock_skew_ms,
+            used_weight: self.used_weight(),
+        })
+    }
+    
+    /// Request weight used in the current minute, as last reported by Binance
+    pub fn used_weight(&self) -> Option<u32> {
+        match self.used_weight.load(Ordering::Relaxed) {
+            0 => None,
+            weight => Some(weight),
+        }
+    }
+    
+    /// Request weight last reported on a signed request made with each of
+    /// `api_keys`, in the same order
+    /// 
+    /// `None` for keys that have not been used yet. Empty unless `api_keys`
+    /// is configured.
+    pub fn used_weight_by_key(&self) -> Vec<Option<u32>> {
+        self.key_rotation
+            .used_weights
+            .iter()
+            .map(|weight| match weight.load(Ordering::Relaxed) {
+                0 => None,
+                weight => Some(weight),
+            })
+            .collect()
+    }
+    
+    /// Request weight left in the current minute, as of the last response
+    /// 
+    /// Computed from [`used_weight`](Self::used_weight) and the configured
+    /// `requests_per_minute` limit, so callers can decide whether to send a
+    /// heavy request now or wait. `None` until a response has reported its
+    /// used weight.
+    pub fn weight_remaining_1m(&self) -> Option<u32> {
+        self.used_weight()
+            .map(|used| self.config.requests_per_minute.saturating_sub(used))
+    }
+    
+    // ============================================================
+    // SIGNED (TRADING) ENDPOINTS
+    // ============================================================
+    
+    /// Quote balance needed to place an order, as a pre-flight check
+    /// 
+    /// See [`Symbol::estimate_cost`]. Without a `price` (e.g. for a market
+    /// buy) the order is priced at the current best ask from
+    /// [`get_book_ticker`](Self::get_book_ticker); sells need no quote
+    /// balance and make no request.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Symbol info from [`get_exchange_info`](Self::get_exchange_info)
+    /// * `side` - Order side
+    /// * `quantity` - Base asset quantity
+    /// * `price` - Limit price, or `None` to use the best ask
+    /// * `rates` - The account's commission rates
+    pub async fn estimate_cost(
+        &self,
+        symbol: &Symbol,
+        side: OrderSide,
+        quantity: f64,
+        price: impl Into<Option<f64>>,
+        rates: CommissionRates,
+    ) -> Result<f64> {
+        if side == OrderSide::Sell {
+            return Ok(0.0);
+        }
+        
+        let price = match price.into() {
+            Some(price) => price,
+            None => self.get_book_ticker(&symbol.symbol).await?.ask_price,
+        };
+        Ok(symbol.estimate_cost(side, quantity, price, rates))
+    }
+    
+    /// Place a new order
+    /// 
+    /// `resp_type` trades latency for detail (see [`NewOrderRespType`]).
+    /// Without one, market orders ask for `FULL` so fills are returned and
+    /// other orders get the server default. Quantity and price are sent as
+    /// formatted by [`Symbol::format_qty_str`] and
+    /// [`Symbol::format_price_str`], so they match the symbol's step and
+    /// tick sizes. Requires API credentials.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Symbol info from [`get_exchange_info`](Self::get_exchange_info)
+    /// * `side` - Order side
+    /// * `order_type` - Order type
+    /// * `quantity` - Base asset quantity
+    /// * `price` - Limit price (required for limit orders, ignored for market orders)
+    /// * `resp_type` - Response detail level
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig, NewOrderRespType, OrderSide, OrderType};
+    /// 
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BinanceClient::new(BinanceConfig::from_env()?)?;
+    /// let symbols = client.get_exchange_info().await?;
+    /// let btcusdt = symbols.iter().find(|s| s.symbol == "BTCUSDT").ok_or("BTCUSDT not listed")?;
+    /// let ack = client
+    ///     .place_order(btcusdt, OrderSide::Buy, OrderType::Limit, 0.01, 40000.0, NewOrderRespType::Ack)
+    ///     .await?;
+    /// println!("placed order {}", ack.order_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn place_order(
+        &self,
+        symbol: &Symbol,
+        side: OrderSide,
+        order_type: OrderType,
+        quantity: f64,
+        price: impl Into<Option<f64>>,
+        resp_type: impl Into<Option<NewOrderRespType>>,
+    ) -> Result<NewOrderResponse> {
+        let mut params = format!(
+            "symbol={}&side={}&type={}&quantity={}",
+            symbol.symbol,
+            side,
+            order_type,
+            symbol.format_qty_str(quantity)
+        );
+        
+        match (order_type, price.into()) {
+            (OrderType::Market, _) => {}
+            (OrderType::Limit, Some(price)) => {
+                params.push_str(&format!(
+                    "&timeInForce={}&price={}",
+                    TimeInForce::Gtc,
+                    symbol.format_price_str(price)
+                ));
+            }
+            (OrderType::LimitMaker, Some(price)) => {
+                params.push_str(&format!("&price={}", symbol.format_price_str(price)));
+            }
+            (_, None) => {
+                return Err(Error::InvalidOrder(format!(
+                    "{} order requires a price",
+                    order_type
+                )));
+            }
+        }
+        
+        let resp_type = resp_type
+            .into()
+            .or((order_type == OrderType::Market).then_some(NewOrderRespType::Full));
+        if let Some(resp_type) = resp_type {
+            params.push_str(&format!("&newOrderRespType={}", resp_type));
+        }
+        
+        let response = self.send_signed(Method::POST, Endpoints::order(), &params).await?;
+        let order: BinanceNewOrderResponse = self.handle_response(response).await?;
+        order.to_new_order()
+    }
+    
+    /// Place an OCO (one-cancels-the-other) order
+    /// 
+    /// Combines a limit order at `request.price` with a stop-limit order
+    /// triggered at `request.stop_price`. For a SELL the limit must be above
+    /// and the stop below the current price; for a BUY it is the opposite.
+    /// Quantity and prices are formatted to the symbol's step and tick sizes
+    /// like [`place_order`](Self::place_order). Requires API credentials.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Symbol info from [`get_exchange_info`](Self::get_exchange_info)
+    /// * `request` - Side, quantity and prices of both legs
+    pub async fn place_oco_order(
+        &self,
+        symbol: &Symbol,
+        request: OcoOrderRequest,
+    ) -> Result<OcoOrderResponse> {
+        let OcoOrderRequest {
+            side,
+            quantity,
+            price,
+            stop_price,
+            stop_limit_price,
+            stop_limit_time_in_force,
+            resp_type,
+        } = request;
+        
+        let current = self.get_ticker_price(&symbol.symbol).await?.price;
+        let (low, high) = match side {
+            OrderSide::Sell => (stop_price, price),
+            OrderSide::Buy => (price, stop_price),
+        };
+        if !(low < current && current < high) {
+            return Err(Error::InvalidOrder(format!(
+                "{} OCO requires {} < current price ({}) < {}",
+                side, low, current, high
+            )));
+        }
+        
+        let params = format!(
+            "symbol={}&side={}&quantity={}&price={}&stopPrice={}&stopLimitPrice={}&stopLimitTimeInForce={}&newOrderRespType={}",
+            symbol.symbol,
+            side,
+            symbol.format_qty_str(quantity),
+            symbol.format_price_str(price),
+            symbol.format_price_str(stop_price),
+            symbol.format_price_str(stop_limit_price),
+            stop_limit_time_in_force.unwrap_or(TimeInForce::Gtc),
+            resp_type.unwrap_or(NewOrderRespType::Full)
+        );
+        
+        let response = self.send_signed(Method::POST, Endpoints::order_oco(), &params).await?;
+        let oco: BinanceOcoOrderResponse = self.handle_response(response).await?;
+        oco.to_oco_order()
+    }
+    
+    /// Cancel an order and place a new LIMIT GTC order in one atomic call
+    /// 
+    /// Avoids the window between a separate cancel and re-place, e.g. when a
+    /// market maker moves a quote. Partial failures are not errors: check
+    /// [`CancelReplaceResponse::is_success`] and the per-leg results. With
+    /// [`CancelReplaceMode::StopOnFailure`] a failed cancel leaves the new
+    /// order `NotAttempted`. Quantity and price are formatted to the symbol's
+    /// step and tick sizes like [`place_order`](Self::place_order). Requires
+    /// API credentials.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Symbol info from [`get_exchange_info`](Self::get_exchange_info)
+    /// * `cancel_order_id` - Order id to cancel
+    /// * `side` - Side of the new order
+    /// * `quantity` - Quantity of the new order
+    /// * `price` - Limit price of the new order
+    /// * `mode` - Whether to place the new order if the cancel fails
+    pub async fn cancel_replace_order(
+        &self,
+        symbol: &Symbol,
+        cancel_order_id: i64,
+        side: OrderSide,
+        quantity: f64,
+        price: f64,
+        mode: CancelReplaceMode,
+    ) -> Result<CancelReplaceResponse> {
+        let params = format!(
+            "symbol={}&cancelReplaceMode={}&cancelOrderId={}&side={}&type=LIMIT&timeInForce={}&quantity={}&price={}",
+            symbol.symbol,
+            mode,
+            cancel_order_id,
+            side,
+            TimeInForce::Gtc,
+            symbol.format_qty_str(quantity),
+            symbol.format_price_str(price)
+        );
+        
+        let response = self
+            .send_signed(Method::POST, Endpoints::order_cancel_replace(), &params)
+            .await?;
+        
+        // Failed legs come back as 400/409 with both outcomes under `data`
+        if matches!(response.status(), StatusCode::BAD_REQUEST | StatusCode::CONFLICT) {
+            #[derive(serde::Deserialize)]
+            struct CancelReplaceFailure {
+                code: i32,
+                msg: String,
+                data: Option<BinanceCancelReplaceResponse>,
+            }
+            
+            self.record_used_weight(&response);
+            let status = response.status();
+            let body = self.read_body(response).await?;
+            
+            return match serde_json::from_slice::<CancelReplaceFailure>(&body) {
+                Ok(CancelReplaceFailure { data: Some(data), .. }) => data.to_cancel_replace(),
+                Ok(failure) => Err(Error::ApiError {
+                    code: failure.code,
+                    msg: failure.msg,
+                }),
+                Err(_) => Err(Error::ApiError {
+                    code: status.as_u16() as i32,
+                    msg: String::from_utf8_lossy(&body).into_owned(),
+                }),
+            };
+        }
+        
+        let result: BinanceCancelReplaceResponse = self.handle_response(response).await?;
+        result.to_cancel_replace()
+    }
+    
+    /// Get all orders (open, filled, canceled) for a symbol
+    /// 
+    /// Requires API credentials. Pass the last seen `order_id` to page forward:
+    /// results start at that order id. This endpoint has a request weight of 20.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Trading pair symbol
+    /// * `order_id` - Return orders with id >= this value
+    /// * `start_time` - Start time in milliseconds
+    /// * `end_time` - End time in milliseconds
+    /// * `limit` - Number of orders (max 1000, `None` uses the server default of 500)
+    pub async fn get_all_orders(
+        &self,
+        symbol: &str,
+        order_id: Option<i64>,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: impl Into<Option<usize>>,
+    ) -> Result<Vec<OrderResponse>> {
+        let symbol = &normalize_symbol(symbol);
+        let limit = limit.into();
+        check_limit(limit, 1000)?;
+        
+        let mut params = format!("symbol={}{}", symbol, limit_param(limit));
+        if let Some(order_id) = order_id {
+            params.push_str(&format!("&orderId={}", order_id));
+        }
+        if let Some(start_time) = start_time {
+            params.push_str(&format!("&startTime={}", start_time));
+        }
+        if let Some(end_time) = end_time {
+            params.push_str(&format!("&endTime={}", end_time));
+        }
+        
+        let response = self
+            .send_signed(Method::GET, Endpoints::all_orders(), &params)
+            .await?;
+        let orders: Vec<BinanceOrderResponse> = self.handle_response(response).await?;
+        orders.iter().map(|o| o.to_order()).collect()
+    }
+    
+    /// Get the account's own trades (fills) for a symbol
+    /// 
+    /// Requires API credentials. Pass the last seen trade id + 1 as `from_id`
+    /// to page forward. This endpoint has a request weight of 20.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Trading pair symbol
+    /// * `from_id` - Return trades with id >= this value
+    /// * `start_time` - Start time in milliseconds
+    /// * `end_time` - End time in milliseconds
+    /// * `limit` - Number of trades (max 1000, `None` uses the server default of 500)
+    pub async fn get_my_trades(
+        &self,
+        symbol: &str,
+        from_id: Option<i64>,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: impl Into<Option<usize>>,
+    ) -> Result<Vec<AccountTrade>> {
+        let symbol = &normalize_symbol(symbol);
+        let limit = limit.into();
+        check_limit(limit, 1000)?;
+        
+        let mut params = format!("symbol={}{}", symbol, limit_param(limit));
+        if let Some(from_id) = from_id {
+            params.push_str(&format!("&fromId={}", from_id));
+        }
+        if let Some(start_time) = start_time {
+            params.push_str(&format!("&startTime={}", start_time));
+        }
+        if let Some(end_time) = end_time {
+            params.push_str(&format!("&endTime={}", end_time));
+        }
+        
+        let response = self
+            .send_signed(Method::GET, Endpoints::my_trades(), &params)
+            .await?;
+        let trades: Vec<BinanceAccountTradeResponse> = self.handle_response(response).await?;
+        trades.iter().map(|t| t.to_account_trade()).collect()
+    }
+    
+    /// GET an endpoint this crate does not model
+    /// 
+    /// Goes through the same rate limiting, retries and error mapping as the
+    /// typed methods, charging the weight configured for `path` (1 unless
+    /// set in `config.weights`). `params` are URL-encoded into the query.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceClient, BinanceConfig};
+    /// 
+    /// #[derive(serde::Deserialize)]
+    /// struct TradingDay {
+    ///     #[serde(rename = "lastPrice")]
+    ///     last_price: String,
+    /// }
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BinanceClient::new(BinanceConfig::new(false))?;
+    ///     let day: TradingDay = client
+    ///         .get_json("/api/v3/ticker/tradingDay", &[("symbol", "BTCUSDT")])
+    ///         .await?;
+    ///     println!("Last price: {}", day.last_price);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_json<T>(&self, path: &str, params: &[(&str, &str)]) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mut url = format!("{}{}", self.config.get_base_url(), path);
+        let query = encode_params(params);
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query);
+        }
+        
+        let response = self.request_with_retry(path, None, || async {
+            self.http_client
+                .get(&url)
+                .send()
+                .await
+        }).await?;
+        
+        self.handle_response(response).await
+    }
+    
+    /// POST a signed request to an endpoint this crate does not model
+    /// 
+    /// `params` are signed with a timestamp like the typed trading methods
+    /// and sent as a form body. As with orders, the request is not retried.
+    pub async fn post_signed_json<T>(&self, path: &str, params: &[(&str, &str)]) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let response = self.send_signed(Method::POST, path, &encode_params(params)).await?;
+        self.handle_response(response).await
+    }
+    
     // ============================================================
     // PRIVATE HELPER METHODS
     // ============================================================
     
+    /// Send a signed request (timestamp + HMAC-SHA256 signature)
+    /// 
+    /// GET requests carry the signed parameters in the query string; other
+    /// methods send them as a form-encoded body. The signature covers exactly
+    /// the string that is sent, so the two are never mixed.
+    /// Syncs server time first unless the last sync is recent enough.
+    /// With `api_keys` set, each call signs with the next key pair in turn.
+    /// Order requests are sent once, but their wait for a request slot and
+    /// rate-limit weight counts against `request_deadline_seconds` too.
+    async fn send_signed(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: &str,
+    ) -> Result<Response> {
+        let (key_index, api_key, secret_key) = self.signing_keys()?;
+        let base_url = self.config.get_base_url();
+        
+        // A failed sync leaves the previous offset; the server rejects the
+        // request if it is too far off
+        if let Err(e) = self.sync_time().await {
+            tracing::warn!(error = %e, "server time sync failed");
+        }
+        
+        let attempt = || async {
+            // Re-sign on every attempt so retries carry a fresh timestamp
+            let timestamp = chrono::Utc::now().timestamp_millis() + self.time_offset_ms();
+            let payload = signed_params(secret_key, params, timestamp);
+            let request = if method == Method::GET {
+                self.http_client
+                    .get(format!("{}{}?{}", base_url, endpoint, payload))
+            } else {
+                self.http_client
+                    .request(method.clone(), format!("{}{}", base_url, endpoint))
+                    .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+                    .body(payload)
+            };
+            
+            request
+                .header("X-MBX-APIKEY", api_key)
+                .send()
+                .await
+        };
+        
+        // A failed order request may still have executed, so only reads are retried
+        let response = if method == Method::GET {
+            self.request_with_retry(endpoint, None, attempt).await?
+        } else {
+            self.within_deadline(async {
+                let _permit = self.acquire_request_slot().await;
+                self.rate_limiter.acquire_weight(self.config.weights.weight(endpoint, None)).await;
+                attempt().await.map_err(Error::HttpError)
+            }).await?
+        };
+        
+        if let (Some(index), Some(used)) = (key_index, used_weight_header(&response)) {
+            self.key_rotation.used_weights[index].store(used, Ordering::Relaxed);
+        }
+        Ok(response)
+    }
+    
+    /// Key pair for the next signed request, with its index in `api_keys`
+    /// 
+    /// Rotates round-robin through `api_keys` when set, otherwise uses
+    /// `api_key`/`secret_key`.
+    fn signing_keys(&self) -> Result<(Option<usize>, &str, &str)> {
+        let keys = &self.config.api_keys;
+        if !keys.is_empty() {
+            let index = self.key_rotation.next.fetch_add(1, Ordering::Relaxed) % keys.len();
+            let (api_key, secret_key) = &keys[index];
+            return Ok((Some(index), api_key, secret_key));
+        }
+        
+        match (&self.config.api_key, &self.config.secret_key) {
+            (Some(api_key), Some(secret_key)) => Ok((None, api_key, secret_key)),
+            _ => Err(Error::ConfigError(
+                "API key and secret key are required for signed endpoints".to_string(),
+            )),
+        }
+    }
+    
     /// Make request with automatic retry logic
-    async fn request_with_retry<F, Fut>(&self, mut f: F) -> Result<Response>
+    /// 
+    /// Attempts and the delays between them follow
+    /// [`BinanceConfig::retry_policy`], with full jitter if
+    /// `config.retry_jitter` is set. Every attempt is charged against the
+    /// rate limiter with the weight `config.weights` gives `endpoint` for
+    /// `size` (see [`WeightTable::weight`](crate::WeightTable::weight)). With
+    /// `request_deadline_seconds` set, the attempts and backoff sleeps
+    /// together are cut off with `Error::Timeout` once it passes.
+    async fn request_with_retry<F, Fut>(
+        &self,
+        endpoint: &str,
+        size: Option<usize>,
+        mut f: F,
+    ) -> Result<Response>
     where
         F: FnMut() -> Fut,
         Fut: std::future::Future<Output = reqwest::Result<Response>>,
     {
-        if !self.config.enable_retries {
-            return f().await.map_err(Error::HttpError);
-        }
-        
+        let weight = self.config.weights.weight(endpoint, size);
+        let backoff = self.config.retry_policy();
         let mut attempts = 0;
-        let max_attempts = self.config.max_retries + 1;
         
-        loop {
-            attempts += 1;
-            
-            match f().await {
-                Ok(response) => return Ok(response),
-                Err(e) if attempts >= max_attempts => {
-                    return Err(Error::HttpError(e));
-                }
-                Err(e) if e.is_timeout() => {
-                    let delay = Duration::from_millis(100 * 2u64.pow(attempts - 1));
-                    sleep(delay).await;
-                    continue;
-                }
-                Err(e) if e.is_connect() => {
-                    let delay = Duration::from_millis(500 * 2u64.pow(attempts - 1));
-                    sleep(delay).await;
-                    continue;
-                }
-                Err(e) => {
-                    return Err(Error::HttpError(e));
+        let retries = async {
+            loop {
+                attempts += 1;
+                let permit = self.acquire_request_slot().await;
+                self.rate_limiter.acquire_weight(weight).await;
+                
+                let started = Instant::now();
+                let outcome = f().await.map_err(Error::HttpError);
+                drop(permit);
+                
+                // 5xx responses are retried; the last one is returned for handle_response
+                let retrying = self.config.enable_retries
+                    && backoff.should_retry(attempts)
+                    && match &outcome {
+                        Ok(response) => response.status().is_server_error(),
+                        Err(err) => err.is_transient(),
+                    };
+                telemetry::record_attempt(&outcome, started.elapsed(), retrying);
+                
+                if !retrying {
+                    return outcome;
                 }
+                
+                let delay = if self.config.retry_jitter {
+                    backoff.full_jitter_delay(attempts - 1)
+                } else {
+                    backoff.delay(attempts - 1)
+                };
+                sleep(delay).await;
             }
+        };
+        
+        self.within_deadline(retries).await
+    }
+    
+    /// Run `request`, cutting it off with `Error::Timeout` once
+    /// `request_deadline_seconds` (if set) passes
+    async fn within_deadline<T>(
+        &self,
+        request: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        match self.config.request_deadline_seconds {
+            Some(seconds) => tokio::time::timeout(Duration::from_secs(seconds), request)
+                .await
+                .unwrap_or(Err(Error::Timeout(seconds))),
+            None => request.await,
+        }
+    }
+    
+    /// Wait for an in-flight request slot if `max_concurrent_requests` is set
+    /// 
+    /// The slot is held until the response headers arrive.
+    async fn acquire_request_slot(&self) -> Option<SemaphorePermit<'_>> {
+        match &self.concurrency {
+            Some(semaphore) => semaphore.acquire().await.ok(),
+            None => None,
         }
     }
     
     /// Handle HTTP response and convert to typed result
     async fn handle_response<T>(&self, response: Response) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.record_used_weight(&response);
+        let endpoint = response.url().path().to_string();
+        
+        let result = self.parse_response(response).await;
+        if let Err(e) = &result {
+            telemetry::record_error(&endpoint, e);
+        }
+        result
+    }
+    
+    /// Decode a successful body or map the error status to an `Error`
+    async fn parse_response<T>(&self, response: Response) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
@@ -410,10 +1853,8 @@ impl BinanceClient {
         
         match status {
             StatusCode::OK => {
-                response.json::<T>().await.map_err(|e| Error::ApiError {
-                    code: 0,
-                    msg: format!("Failed to parse response: {}", e),
-                })
+                let body = self.read_body(response).await?;
+                Ok(serde_json::from_slice::<T>(&body)?)
             }
             StatusCode::BAD_REQUEST => {
                 #[derive(serde::Deserialize)]
@@ -422,7 +1863,8 @@ impl BinanceClient {
                     msg: String,
                 }
                 
-                match response.json::<BinanceError>().await {
+                let body = self.read_body(response).await?;
+                match serde_json::from_slice::<BinanceError>(&body) {
                     Ok(err) => Err(Error::ApiError {
                         code: err.code,
                         msg: err.msg,
@@ -446,7 +1888,8 @@ impl BinanceClient {
                 })
             }
             _ => {
-                let error_text = response.text().await.unwrap_or_default();
+                let body = self.read_body(response).await?;
+                let error_text = String::from_utf8_lossy(&body).into_owned();
                 Err(Error::ApiError {
                     code: status.as_u16() as i32,
                     msg: error_text,
@@ -454,6 +1897,271 @@ impl BinanceClient {
             }
         }
     }
+    
+    /// Request `url` and decode the elements of its JSON array body as they arrive
+    /// 
+    /// Error responses are decoded as by `handle_response`.
+    /// `max_response_bytes` bounds the total bytes read.
+    fn json_array_stream<T>(
+        self,
+        endpoint: &'static str,
+        url: String,
+    ) -> impl Stream<Item = Result<T>> + Send + 'static
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        enum State {
+            Pending(BinanceClient, &'static str, String),
+            Reading {
+                response: Response,
+                parser: JsonArrayParser,
+                read: usize,
+                max_bytes: Option<usize>,
+            },
+        }
+        
+        stream::try_unfold(Some(State::Pending(self, endpoint, url)), |state| async move {
+            let (mut response, mut parser, mut read, max_bytes) = match state {
+                None => return Ok(None),
+                Some(State::Pending(client, endpoint, url)) => {
+                    let response = client.request_with_retry(endpoint, None, || async {
+                        client.http_client
+                            .get(&url)
+                            .send()
+                            .await
+                    }).await?;
+                    
+                    if response.status() != StatusCode::OK {
+                        client.handle_response::<serde::de::IgnoredAny>(response).await?;
+                        return Ok(None);
+                    }
+                    client.record_used_weight(&response);
+                    (response, JsonArrayParser::default(), 0, client.config.max_response_bytes)
+                }
+                Some(State::Reading { response, parser, read, max_bytes }) => {
+                    (response, parser, read, max_bytes)
+                }
+            };
+            
+            loop {
+                if let Some(item) = parser.next::<T>()? {
+                    let state = State::Reading { response, parser, read, max_bytes };
+                    return Ok(Some((item, Some(state))));
+                }
+                if parser.is_done() {
+                    return Ok(None);
+                }
+                
+                let Some(chunk) = response.chunk().await.map_err(Error::HttpError)? else {
+                    return Err(Error::DeserializationError(
+                        "Response ended inside a JSON array".to_string(),
+                    ));
+                };
+                read += chunk.len();
+                if let Some(max) = max_bytes.filter(|max| read > *max) {
+                    return Err(Error::DeserializationError(format!(
+                        "Response body exceeds {} bytes", max
+                    )));
+                }
+                parser.push(&chunk);
+            }
+        })
+    }
+    
+    /// Read the response body, enforcing `max_response_bytes` if configured
+    async fn read_body(&self, mut response: Response) -> Result<Vec<u8>> {
+        let Some(max_bytes) = self.config.max_response_bytes else {
+            return Ok(response.bytes().await.map_err(Error::HttpError)?.to_vec());
+        };
+        let too_large = || Error::DeserializationError(format!(
+            "Response body exceeds {} bytes", max_bytes
+        ));
+        
+        if response.content_length().is_some_and(|len| len > max_bytes as u64) {
+            return Err(too_large());
+        }
+        
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(Error::HttpError)? {
+            if body.len() + chunk.len() > max_bytes {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        
+        Ok(body)
+    }
+    
+    /// Remember the `X-MBX-USED-WEIGHT-1M` header, if present
+    fn record_used_weight(&self, response: &Response) {
+        if let Some(used) = used_weight_header(response) {
+            self.used_weight.store(used, Ordering::Relaxed);
+            if self.config.adaptive_rate_limit {
+                self.rate_limiter
+                    .update_from_used_weight(used, self.config.requests_per_minute);
+            }
+        }
+    }
+}
+
+/// Round-robin position and last reported used weight per entry of
+/// `api_keys`
+struct KeyRotation {
+    next: AtomicUsize,
+    used_weights: Vec<AtomicU32>,
+}
+
+impl KeyRotation {
+    fn new(keys: usize) -> Self {
+        Self {
+            next: AtomicUsize::new(0),
+            used_weights: (0..keys).map(|_| AtomicU32::new(0)).collect(),
+        }
+    }
+}
+
+/// Value of the `X-MBX-USED-WEIGHT-1M` header, if present
+fn used_weight_header(response: &Response) -> Option<u32> {
+    response
+        .headers()
+        .get("X-MBX-USED-WEIGHT-1M")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| s.parse().ok())
+}
+
+/// Reject a `limit` above the endpoint maximum
+fn check_limit(limit: Option<usize>, max: usize) -> Result<()> {
+    match limit {
+        Some(limit) if limit > max => Err(Error::ConfigError(
+            format!("Limit {} exceeds maximum of {}", limit, max)
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Reject a response for another symbol than the one requested
+/// 
+/// Catches a misconfigured `base_url` or caching proxy serving the wrong
+/// data instead of passing it off under the requested symbol.
+fn check_symbol(requested: &str, returned: &str) -> Result<()> {
+    if returned == requested {
+        Ok(())
+    } else {
+        Err(Error::DeserializationError(format!(
+            "Requested {} but the response is for {}",
+            requested, returned
+        )))
+    }
+}
+
+/// `key=value&...` with both sides URL-encoded
+fn encode_params(params: &[(&str, &str)]) -> String {
+    url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish()
+}
+
+/// `&limit=N` query fragment, or nothing to use the server default
+fn limit_param(limit: Option<usize>) -> String {
+    limit.map(|l| format!("&limit={}", l)).unwrap_or_default()
+}
+
+/// HTTP client with the configured timeout and User-Agent
+fn build_http_client(config: &BinanceConfig) -> Result<HttpClient> {
+    let mut builder = HttpClient::builder()
+        .timeout(config.timeout())
+        .user_agent(config.get_user_agent())
+        .local_address(config.local_address);
+    if let Some(proxy) = &config.proxy_url {
+        builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(Error::HttpError)?);
+    }
+    builder.build().map_err(Error::HttpError)
+}
+
+/// `params` with `timestamp` and the signature of both appended
+/// 
+/// The result is sent verbatim as the query string or request body.
+fn signed_params(secret_key: &str, params: &str, timestamp: i64) -> String {
+    let payload = if params.is_empty() {
+        format!("timestamp={}", timestamp)
+    } else {
+        format!("{}&timestamp={}", params, timestamp)
+    };
+    let signature = sign(secret_key, &payload);
+    format!("{}&signature={}", payload, signature)
+}
+
+/// HMAC-SHA256 signature of `payload`, hex encoded
+fn sign(secret_key: &str, payload: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret_key.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Incremental parser for a JSON array of objects received in chunks
+/// 
+/// Only the bytes of the element being parsed are buffered.
+#[derive(Default)]
+struct JsonArrayParser {
+    buf: Vec<u8>,
+    started: bool,
+    done: bool,
+}
+
+impl JsonArrayParser {
+    fn push(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+    }
+    
+    /// Whether the closing `]` has been read
+    fn is_done(&self) -> bool {
+        self.done
+    }
+    
+    /// Next complete element, or None if more input is needed or the array ended
+    fn next<T: serde::de::DeserializeOwned>(&mut self) -> Result<Option<T>> {
+        loop {
+            let Some(pos) = self.buf.iter().position(|b| !b.is_ascii_whitespace()) else {
+                self.buf.clear();
+                return Ok(None);
+            };
+            
+            match (self.started, self.buf[pos]) {
+                (false, b'[') => {
+                    self.started = true;
+                    self.buf.drain(..=pos);
+                }
+                (false, _) => {
+                    return Err(Error::DeserializationError(
+                        "Expected a JSON array".to_string(),
+                    ));
+                }
+                (true, b']') => {
+                    self.done = true;
+                    self.buf.clear();
+                    return Ok(None);
+                }
+                (true, b',') => {
+                    self.buf.drain(..=pos);
+                }
+                (true, _) => {
+                    let mut values =
+                        serde_json::Deserializer::from_slice(&self.buf[pos..]).into_iter::<T>();
+                    return match values.next() {
+                        Some(Ok(value)) => {
+                            let end = pos + values.byte_offset();
+                            self.buf.drain(..end);
+                            Ok(Some(value))
+                        }
+                        Some(Err(e)) if e.is_eof() => Ok(None),
+                        Some(Err(e)) => Err(Error::JsonError(e)),
+                        None => Ok(None),
+                    };
+                }
+            }
+        }
+    }
 }
 
 // ============================================================
@@ -489,9 +2197,15 @@ impl BinanceClientBuilder {
         self
     }
     
-    /// Set max retry attempts
+    /// Set max REST retries (`backoff.max_attempts` becomes `max + 1`)
     pub fn max_retries(mut self, max: u32) -> Self {
-        self.config.max_retries = max;
+        self.config.backoff.max_attempts = max.saturating_add(1);
+        self
+    }
+    
+    /// Set backoff policy for retries
+    pub fn backoff(mut self, policy: BackoffPolicy) -> Self {
+        self.config.backoff = policy;
         self
     }
     
@@ -504,6 +2218,7 @@ impl BinanceClientBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::weights::WeightTable;
 
     #[test]
     fn test_client_creation() {
@@ -512,6 +2227,35 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_sign() {
+        // Example from the Binance API documentation
+        let secret = "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j";
+        let query = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559";
+        
+        assert_eq!(
+            sign(secret, query),
+            "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
+
+    #[test]
+    fn test_signed_params() {
+        let secret = "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j";
+        let params = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000";
+        
+        assert_eq!(
+            signed_params(secret, params, 1499827319559),
+            "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000\
+             &timestamp=1499827319559\
+             &signature=c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+        assert_eq!(
+            signed_params(secret, "", 1499827319559),
+            format!("timestamp=1499827319559&signature={}", sign(secret, "timestamp=1499827319559"))
+        );
+    }
+
     #[test]
     fn test_client_builder() {
         let config = BinanceConfig::new(false);
@@ -522,4 +2266,71 @@ mod tests {
         
         assert!(client.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_clone_with_config() {
+        let client = BinanceClient::new(BinanceConfig::new(false)).unwrap();
+        client.time_offset_ms.store(250, Ordering::Relaxed);
+        
+        let slow = client.clone_with_config(|c| c.timeout_seconds = 45).unwrap();
+        assert_eq!(slow.config().timeout(), Duration::from_secs(45));
+        assert_eq!(client.config().timeout_seconds, 10);
+        assert!(Arc::ptr_eq(&client.rate_limiter, &slow.rate_limiter));
+        assert_eq!(slow.time_offset_ms(), 250);
+        
+        // A different request budget needs its own limiter
+        let throttled = client.clone_with_config(|c| c.requests_per_minute = 60).unwrap();
+        assert!(!Arc::ptr_eq(&client.rate_limiter, &throttled.rate_limiter));
+        
+        assert!(client.clone_with_config(|c| c.requests_per_minute = 0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_weight_table_override_charges_limiter() {
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server.mock("GET", "/api/v3/depth")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"lastUpdateId":1,"bids":[],"asks":[]}"#)
+            .create_async()
+            .await;
+        
+        // 120/min allows a burst of 2, then one unit every 500ms
+        let mut config = BinanceConfig::new(false);
+        config.base_url = Some(server.url());
+        config.requests_per_minute = 120;
+        config.weights = WeightTable::new().with_weight(Endpoints::depth(), 1);
+        
+        let light = BinanceClient::new(config.clone()).unwrap();
+        light.get_depth("BTCUSDT", None).await.unwrap();
+        assert!(light.rate_limiter.try_acquire().is_some());
+        
+        config.weights = WeightTable::new().with_weight(Endpoints::depth(), 2);
+        let heavy = BinanceClient::new(config).unwrap();
+        heavy.get_depth("BTCUSDT", None).await.unwrap();
+        assert!(heavy.rate_limiter.try_acquire().is_none());
+    }
+
+    #[test]
+    fn test_json_array_parser_across_chunks() {
+        let body = br#" [ {"symbol":"BTCUSDT","price":"1.5"},
+            {"symbol":"ETHUSDT","price":"2.5"} ] "#;
+        
+        // Feed one byte at a time so every element is split across chunks
+        let mut parser = JsonArrayParser::default();
+        let mut symbols = Vec::new();
+        for byte in body {
+            parser.push(&[*byte]);
+            while let Some(ticker) = parser.next::<BinanceTickerResponse>().unwrap() {
+                symbols.push(ticker.symbol);
+            }
+        }
+        
+        assert!(parser.is_done());
+        assert_eq!(symbols, vec!["BTCUSDT", "ETHUSDT"]);
+        
+        let mut parser = JsonArrayParser::default();
+        parser.push(br#"{"code":-1}"#);
+        assert!(parser.next::<BinanceTickerResponse>().is_err());
+    }
+}
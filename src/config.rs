@@ -1,6 +1,9 @@
 //! Configuration for Binance connector
 
+use crate::retry::BackoffPolicy;
+use crate::weights::WeightTable;
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
 use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +16,15 @@ pub struct BinanceConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secret_key: Option<String>,
 
+    /// Additional API key / secret key pairs rotated across signed requests
+    ///
+    /// When set, signed requests cycle through these pairs round-robin and
+    /// `api_key`/`secret_key` are not used for signing. Binance tracks order
+    /// rate limits per account, so keys from several accounts each get
+    /// their own budget.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<(String, String)>,
+
     /// Use testnet (true) or mainnet (false)
     pub testnet: bool,
 
@@ -37,10 +49,115 @@ pub struct BinanceConfig {
     pub enable_retries: bool,
 
     /// Maximum retry attempts
+    ///
+    /// Any value other than the default 3 overrides `backoff.max_attempts`
+    /// (as `max_retries + 1`) for REST requests; see
+    /// [`retry_policy`](Self::retry_policy).
+    #[deprecated(note = "set `backoff.max_attempts`, which counts the first attempt too")]
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
+
+    /// Slow down as the server-reported used weight approaches
+    /// `requests_per_minute` (see `RateLimiter::update_from_used_weight`)
+    #[serde(default)]
+    pub adaptive_rate_limit: bool,
+
+    /// Backoff between REST retries and WebSocket reconnect attempts
+    ///
+    /// `max_attempts` bounds both the attempts per REST request and the
+    /// WebSocket connection attempts (4 by default).
+    #[serde(default = "default_backoff")]
+    pub backoff: BackoffPolicy,
+
+    /// Randomize each REST retry delay between zero and the backoff delay
+    /// ("full jitter") so concurrent clients don't retry in lockstep
+    ///
+    /// Replaces `backoff.jitter` for REST retries; WebSocket reconnects
+    /// keep using `backoff` as configured.
+    #[serde(default = "default_true")]
+    pub retry_jitter: bool,
+
+    /// Maximum REST response body size in bytes (unlimited if None)
+    ///
+    /// Guards against endpoints returning huge bodies, e.g. an untrusted
+    /// custom `base_url`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_bytes: Option<usize>,
+
+    /// User-Agent sent on REST requests and the WebSocket handshake
+    /// (`binance-connector-rs/<version>` if None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+
+    /// Request weight charged per endpoint (published defaults unless
+    /// overridden)
+    #[serde(default, skip_serializing_if = "WeightTable::is_empty")]
+    pub weights: WeightTable,
+
+    /// How long a measured server time offset is reused, in seconds
+    ///
+    /// [`BinanceClient::sync_time`](crate::BinanceClient::sync_time), which
+    /// signed requests call first, only asks the server again once the last
+    /// sync is older than this. The local clock is assumed not to jump in
+    /// between.
+    #[serde(default = "default_time_sync_max_age")]
+    pub time_sync_max_age_seconds: u64,
+
+    /// Most klines accepted in one klines request
+    ///
+    /// Binance.com caps the spot klines endpoint at 1000; lower or raise it
+    /// for deployments whose region enforces a different cap.
+    #[serde(default = "default_max_kline_limit")]
+    pub max_kline_limit: usize,
+
+    /// Refetch klines, up to the REST retry policy's `max_attempts`
+    /// fetches in all, when a range that should contain candles comes back
+    /// empty
+    ///
+    /// Binance occasionally returns an empty array for a recent range before
+    /// it becomes consistent. Off by default so genuinely empty historical
+    /// ranges are returned immediately.
+    #[serde(default)]
+    pub retry_empty_klines: bool,
+
+    /// HTTP proxy for REST requests and WebSocket connections
+    /// (e.g. `http://proxy.corp:3128`)
+    ///
+    /// WebSocket connections are tunnelled with `CONNECT`; proxy credentials
+    /// in the URL are only sent on REST requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+
+    /// Most REST requests in flight at once (unlimited if None)
+    ///
+    /// Bounds open connections and file descriptors when many requests are
+    /// issued concurrently, independently of the rate limit. Shared by
+    /// clones of a client.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<usize>,
+
+    /// Longest a REST call may take including all retries and backoff, in
+    /// seconds (unbounded if None)
+    ///
+    /// `timeout_seconds` bounds each attempt; this bounds the whole retry
+    /// sequence, which fails with `Error::Timeout` once it passes. Order
+    /// requests are never retried, but their wait for the rate limiter and
+    /// a request slot is bounded the same way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_deadline_seconds: Option<u64>,
+
+    /// Local address REST connections are made from (chosen by the OS if
+    /// None)
+    ///
+    /// An IPv4 or IPv6 address forces that address family, e.g. when only
+    /// one of them reaches Binance, or picks one of several egress IPs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_address: Option<IpAddr>,
 }
 
+/// User-Agent used when none is configured
+pub const DEFAULT_USER_AGENT: &str = concat!("binance-connector-rs/", env!("CARGO_PKG_VERSION"));
+
 fn default_timeout() -> u64 {
     10
 }
@@ -53,13 +170,27 @@ fn default_true() -> bool {
 fn default_max_retries() -> u32 {
     3
 }
+fn default_backoff() -> BackoffPolicy {
+    BackoffPolicy {
+        max_attempts: default_max_retries() + 1,
+        ..BackoffPolicy::default()
+    }
+}
+fn default_time_sync_max_age() -> u64 {
+    300
+}
+fn default_max_kline_limit() -> usize {
+    1000
+}
 
 impl BinanceConfig {
     /// Create new configuration (no auth needed for market data)
+    #[allow(deprecated)]
     pub fn new(testnet: bool) -> Self {
         Self {
             api_key: None,
             secret_key: None,
+            api_keys: Vec::new(),
             testnet,
             base_url: None,
             ws_url: None,
@@ -67,14 +198,29 @@ impl BinanceConfig {
             requests_per_minute: default_rate_limit(),
             enable_retries: default_true(),
             max_retries: default_max_retries(),
+            adaptive_rate_limit: false,
+            backoff: default_backoff(),
+            retry_jitter: default_true(),
+            max_response_bytes: None,
+            user_agent: None,
+            time_sync_max_age_seconds: default_time_sync_max_age(),
+            max_kline_limit: default_max_kline_limit(),
+            retry_empty_klines: false,
+            proxy_url: None,
+            max_concurrent_requests: None,
+            request_deadline_seconds: None,
+            local_address: None,
+            weights: WeightTable::default(),
         }
     }
 
     /// Create config with API credentials (for trading)
+    #[allow(deprecated)]
     pub fn with_auth(api_key: String, secret_key: String, testnet: bool) -> Self {
         Self {
             api_key: Some(api_key),
             secret_key: Some(secret_key),
+            api_keys: Vec::new(),
             testnet,
             base_url: None,
             ws_url: None,
@@ -82,6 +228,19 @@ impl BinanceConfig {
             requests_per_minute: default_rate_limit(),
             enable_retries: default_true(),
             max_retries: default_max_retries(),
+            adaptive_rate_limit: false,
+            backoff: default_backoff(),
+            retry_jitter: default_true(),
+            max_response_bytes: None,
+            user_agent: None,
+            time_sync_max_age_seconds: default_time_sync_max_age(),
+            max_kline_limit: default_max_kline_limit(),
+            retry_empty_klines: false,
+            proxy_url: None,
+            max_concurrent_requests: None,
+            request_deadline_seconds: None,
+            local_address: None,
+            weights: WeightTable::default(),
         }
     }
 
@@ -91,6 +250,7 @@ impl BinanceConfig {
     /// - BINANCE_API_KEY (optional)
     /// - BINANCE_SECRET_KEY (optional)
     /// - BINANCE_TESTNET (optional, default: false)
+    #[allow(deprecated)]
     pub fn from_env() -> crate::Result<Self> {
         let api_key = std::env::var("BINANCE_API_KEY").ok();
         let secret_key = std::env::var("BINANCE_SECRET_KEY").ok();
@@ -113,6 +273,7 @@ impl BinanceConfig {
         Ok(Self {
             api_key,
             secret_key,
+            api_keys: Vec::new(),
             testnet,
             base_url: None,
             ws_url: None,
@@ -120,9 +281,95 @@ impl BinanceConfig {
             requests_per_minute,
             enable_retries: default_true(),
             max_retries: default_max_retries(),
+            adaptive_rate_limit: false,
+            backoff: default_backoff(),
+            retry_jitter: default_true(),
+            max_response_bytes: None,
+            user_agent: None,
+            time_sync_max_age_seconds: default_time_sync_max_age(),
+            max_kline_limit: default_max_kline_limit(),
+            retry_empty_klines: false,
+            proxy_url: None,
+            max_concurrent_requests: None,
+            request_deadline_seconds: None,
+            local_address: None,
+            weights: WeightTable::default(),
         })
     }
 
+    /// Mainnet preset for market-data polling
+    ///
+    /// Retries generously and paces itself by the server-reported weight,
+    /// since a late answer is still useful. Empty kline ranges are retried.
+    pub fn preset_market_data() -> Self {
+        Self {
+            adaptive_rate_limit: true,
+            retry_empty_klines: true,
+            backoff: BackoffPolicy {
+                max_attempts: 6,
+                ..default_backoff()
+            },
+            ..Self::new(false)
+        }
+    }
+
+    /// Mainnet preset for latency-sensitive trading
+    ///
+    /// Fails fast: a short timeout, a single quick retry and short
+    /// reconnect backoff. The rate limit matches Binance's current 6000
+    /// weight per minute, and server time is resynced every minute.
+    pub fn preset_hft() -> Self {
+        Self {
+            timeout_seconds: 2,
+            requests_per_minute: 6000,
+            backoff: BackoffPolicy {
+                initial: Duration::from_millis(50),
+                max_delay: Duration::from_secs(2),
+                max_attempts: 2,
+                ..default_backoff()
+            },
+            time_sync_max_age_seconds: 60,
+            ..Self::new(false)
+        }
+    }
+
+    /// Mainnet preset for background jobs sharing an IP with other clients
+    ///
+    /// Uses half the default rate limit, slows down as used weight rises
+    /// and waits longer between retries.
+    pub fn preset_conservative() -> Self {
+        Self {
+            timeout_seconds: 30,
+            requests_per_minute: 600,
+            adaptive_rate_limit: true,
+            backoff: BackoffPolicy {
+                initial: Duration::from_secs(2),
+                max_delay: Duration::from_secs(120),
+                max_attempts: 6,
+                ..default_backoff()
+            },
+            ..Self::new(false)
+        }
+    }
+
+    /// Start building a configuration (mainnet, no auth, defaults otherwise)
+    ///
+    /// # Example
+    /// ```
+    /// use binance_connector::BinanceConfig;
+    ///
+    /// let config = BinanceConfig::builder()
+    ///     .testnet(true)
+    ///     .auth("key", "secret")
+    ///     .timeout_seconds(30)
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(config.is_authenticated());
+    /// ```
+    pub fn builder() -> BinanceConfigBuilder {
+        BinanceConfigBuilder::default()
+    }
+
     /// Get REST API base URL
     pub fn get_base_url(&self) -> String {
         self.base_url.clone().unwrap_or_else(|| {
@@ -145,14 +392,36 @@ impl BinanceConfig {
         })
     }
 
+    /// Get User-Agent header value
+    pub fn get_user_agent(&self) -> &str {
+        self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT)
+    }
+
     /// Get timeout as Duration
     pub fn timeout(&self) -> Duration {
         Duration::from_secs(self.timeout_seconds)
     }
 
+    /// Backoff applied to REST requests
+    ///
+    /// `backoff`, unless the deprecated `max_retries` was changed from its
+    /// default, in which case it sets `max_attempts` to `max_retries + 1`.
+    pub fn retry_policy(&self) -> BackoffPolicy {
+        #[allow(deprecated)]
+        let max_retries = self.max_retries;
+        if max_retries == default_max_retries() {
+            return self.backoff;
+        }
+
+        BackoffPolicy {
+            max_attempts: max_retries.saturating_add(1),
+            ..self.backoff
+        }
+    }
+
     /// Check if authenticated
     pub fn is_authenticated(&self) -> bool {
-        self.api_key.is_some() && self.secret_key.is_some()
+        (self.api_key.is_some() && self.secret_key.is_some()) || !self.api_keys.is_empty()
     }
 
     /// Validate configuration
@@ -169,6 +438,64 @@ impl BinanceConfig {
             ));
         }
 
+        if self.backoff.max_attempts == 0 {
+            return Err(crate::Error::ConfigError(
+                "Backoff max attempts must be greater than 0".to_string(),
+            ));
+        }
+
+        if !(1.0..f64::INFINITY).contains(&self.backoff.multiplier)
+            || !(0.0..=1.0).contains(&self.backoff.jitter)
+        {
+            return Err(crate::Error::ConfigError(
+                "Backoff multiplier must be at least 1 and jitter between 0 and 1".to_string(),
+            ));
+        }
+
+        if self.max_kline_limit == 0 {
+            return Err(crate::Error::ConfigError(
+                "Max kline limit must be greater than 0".to_string(),
+            ));
+        }
+
+        if reqwest::header::HeaderValue::from_str(self.get_user_agent()).is_err() {
+            return Err(crate::Error::ConfigError(
+                "User agent must be a valid header value".to_string(),
+            ));
+        }
+
+        if self
+            .api_keys
+            .iter()
+            .any(|(api_key, secret_key)| api_key.is_empty() || secret_key.is_empty())
+        {
+            return Err(crate::Error::ConfigError(
+                "API keys and secret keys must not be empty".to_string(),
+            ));
+        }
+
+        if self.request_deadline_seconds == Some(0) {
+            return Err(crate::Error::ConfigError(
+                "Request deadline must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.max_concurrent_requests == Some(0) {
+            return Err(crate::Error::ConfigError(
+                "Max concurrent requests must be greater than 0".to_string(),
+            ));
+        }
+
+        if let Some(proxy) = &self.proxy_url {
+            let valid = url::Url::parse(proxy)
+                .is_ok_and(|url| url.scheme() == "http" && url.host_str().is_some());
+            if !valid {
+                return Err(crate::Error::ConfigError(
+                    "Proxy URL must be an http:// URL with a host".to_string(),
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -179,6 +506,155 @@ impl Default for BinanceConfig {
     }
 }
 
+/// Builder for [`BinanceConfig`]
+///
+/// Every field has a setter; [`build`](Self::build) validates the result.
+#[derive(Debug, Clone, Default)]
+pub struct BinanceConfigBuilder {
+    config: BinanceConfig,
+}
+
+impl BinanceConfigBuilder {
+    /// Set API key and secret key
+    pub fn auth(mut self, api_key: impl Into<String>, secret_key: impl Into<String>) -> Self {
+        self.config.api_key = Some(api_key.into());
+        self.config.secret_key = Some(secret_key.into());
+        self
+    }
+
+    /// Add an API key / secret key pair to rotate across signed requests
+    pub fn add_api_key(mut self, api_key: impl Into<String>, secret_key: impl Into<String>) -> Self {
+        self.config.api_keys.push((api_key.into(), secret_key.into()));
+        self
+    }
+
+    /// Use testnet (true) or mainnet (false)
+    pub fn testnet(mut self, testnet: bool) -> Self {
+        self.config.testnet = testnet;
+        self
+    }
+
+    /// Set REST API base URL
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.config.base_url = Some(url.into());
+        self
+    }
+
+    /// Set WebSocket URL
+    pub fn ws_url(mut self, url: impl Into<String>) -> Self {
+        self.config.ws_url = Some(url.into());
+        self
+    }
+
+    /// Set request timeout in seconds
+    pub fn timeout_seconds(mut self, seconds: u64) -> Self {
+        self.config.timeout_seconds = seconds;
+        self
+    }
+
+    /// Set maximum requests per minute
+    pub fn requests_per_minute(mut self, requests_per_minute: u32) -> Self {
+        self.config.requests_per_minute = requests_per_minute;
+        self
+    }
+
+    /// Enable/disable automatic retries
+    pub fn enable_retries(mut self, enable: bool) -> Self {
+        self.config.enable_retries = enable;
+        self
+    }
+
+    /// Set maximum REST retries (`backoff.max_attempts` becomes `max + 1`)
+    pub fn max_retries(mut self, max: u32) -> Self {
+        self.config.backoff.max_attempts = max.saturating_add(1);
+        self
+    }
+
+    /// Enable/disable slowing down as the used weight approaches the limit
+    pub fn adaptive_rate_limit(mut self, enable: bool) -> Self {
+        self.config.adaptive_rate_limit = enable;
+        self
+    }
+
+    /// Set backoff policy for retries and reconnects
+    pub fn backoff(mut self, policy: BackoffPolicy) -> Self {
+        self.config.backoff = policy;
+        self
+    }
+
+    /// Enable/disable full jitter on REST retry delays
+    pub fn retry_jitter(mut self, enable: bool) -> Self {
+        self.config.retry_jitter = enable;
+        self
+    }
+
+    /// Set maximum REST response body size in bytes
+    pub fn max_response_bytes(mut self, bytes: usize) -> Self {
+        self.config.max_response_bytes = Some(bytes);
+        self
+    }
+
+    /// Set User-Agent header value
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Set per-endpoint request weights
+    pub fn weights(mut self, weights: WeightTable) -> Self {
+        self.config.weights = weights;
+        self
+    }
+
+    /// Set how long a measured server time offset is reused, in seconds
+    pub fn time_sync_max_age_seconds(mut self, seconds: u64) -> Self {
+        self.config.time_sync_max_age_seconds = seconds;
+        self
+    }
+
+    /// Set most klines accepted in one klines request
+    pub fn max_kline_limit(mut self, limit: usize) -> Self {
+        self.config.max_kline_limit = limit;
+        self
+    }
+
+    /// Enable/disable refetching klines that come back unexpectedly empty
+    pub fn retry_empty_klines(mut self, enable: bool) -> Self {
+        self.config.retry_empty_klines = enable;
+        self
+    }
+
+    /// Route REST and WebSocket traffic through an HTTP proxy
+    pub fn proxy_url(mut self, url: impl Into<String>) -> Self {
+        self.config.proxy_url = Some(url.into());
+        self
+    }
+
+    /// Set most REST requests in flight at once
+    pub fn max_concurrent_requests(mut self, requests: usize) -> Self {
+        self.config.max_concurrent_requests = Some(requests);
+        self
+    }
+
+    /// Set longest time a REST call may take including retries
+    pub fn request_deadline_seconds(mut self, seconds: u64) -> Self {
+        self.config.request_deadline_seconds = Some(seconds);
+        self
+    }
+
+    /// Set local address REST connections are made from
+    pub fn local_address(mut self, address: IpAddr) -> Self {
+        self.config.local_address = Some(address);
+        self
+    }
+
+    /// Validate and return the configuration
+    pub fn build(self) -> crate::Result<BinanceConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,5 +687,124 @@ mod tests {
 
         config.timeout_seconds = 0;
         assert!(config.validate().is_err());
+
+        let mut config = BinanceConfig::default();
+        config.backoff.jitter = 1.5;
+        assert!(config.validate().is_err());
+
+        let config = BinanceConfig {
+            user_agent: Some("bad\nagent".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let policy = BackoffPolicy {
+            initial: Duration::from_millis(100),
+            max_attempts: 7,
+            ..Default::default()
+        };
+        let config = BinanceConfig::builder()
+            .auth("key", "secret")
+            .testnet(true)
+            .base_url("http://localhost:8080")
+            .ws_url("ws://localhost:8081")
+            .timeout_seconds(30)
+            .requests_per_minute(600)
+            .enable_retries(false)
+            .adaptive_rate_limit(true)
+            .backoff(policy)
+            .max_retries(4)
+            .retry_jitter(false)
+            .max_response_bytes(1 << 20)
+            .user_agent("my-bot/1.0")
+            .weights(WeightTable::new().with_weight("/api/v3/depth", 10))
+            .time_sync_max_age_seconds(60)
+            .max_kline_limit(1500)
+            .retry_empty_klines(true)
+            .build()
+            .unwrap();
+
+        assert!(config.is_authenticated());
+        assert!(config.testnet);
+        assert_eq!(config.get_base_url(), "http://localhost:8080");
+        assert_eq!(config.get_ws_url(), "ws://localhost:8081");
+        assert_eq!(config.timeout_seconds, 30);
+        assert_eq!(config.requests_per_minute, 600);
+        assert!(!config.enable_retries);
+        assert!(config.adaptive_rate_limit);
+        assert_eq!(config.backoff.initial, Duration::from_millis(100));
+        assert_eq!(config.backoff.max_attempts, 5);
+        assert!(!config.retry_jitter);
+        assert_eq!(config.max_response_bytes, Some(1 << 20));
+        assert_eq!(config.get_user_agent(), "my-bot/1.0");
+        assert_eq!(config.weights.weight("/api/v3/depth", None), 10);
+        assert_eq!(config.time_sync_max_age_seconds, 60);
+        assert_eq!(config.max_kline_limit, 1500);
+        assert!(config.retry_empty_klines);
+    }
+
+    #[test]
+    fn test_config_builder_validates() {
+        assert!(BinanceConfig::builder().build().is_ok());
+        assert!(BinanceConfig::builder().timeout_seconds(0).build().is_err());
+        assert!(BinanceConfig::builder().max_kline_limit(0).build().is_err());
+        assert!(BinanceConfig::builder().user_agent("bad\nagent").build().is_err());
+        assert!(BinanceConfig::builder().max_concurrent_requests(0).build().is_err());
+        assert!(BinanceConfig::builder().request_deadline_seconds(0).build().is_err());
+        assert!(BinanceConfig::builder().add_api_key("key", "").build().is_err());
+        assert!(BinanceConfig::builder().add_api_key("key", "secret").build().unwrap().is_authenticated());
+        assert!(BinanceConfig::builder().proxy_url("http://proxy:3128").build().is_ok());
+        assert!(BinanceConfig::builder().proxy_url("socks5://proxy:1080").build().is_err());
+        assert!(BinanceConfig::builder().proxy_url("proxy:3128").build().is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_retry_policy_honours_max_retries() {
+        let mut config = BinanceConfig::new(false);
+        assert_eq!(config.retry_policy().max_attempts, 4);
+        assert_eq!(config.retry_policy().jitter, 0.0);
+
+        config.backoff.max_attempts = 7;
+        assert_eq!(config.retry_policy().max_attempts, 7);
+
+        // The deprecated field still applies once changed from its default
+        config.max_retries = 1;
+        assert_eq!(config.retry_policy().max_attempts, 2);
+        assert_eq!(config.backoff.max_attempts, 7);
+
+        let config: BinanceConfig =
+            serde_json::from_str(r#"{"testnet": false, "max_retries": 5}"#).unwrap();
+        assert_eq!(config.retry_policy().max_attempts, 6);
+    }
+
+    #[test]
+    fn test_config_presets() {
+        let default = BinanceConfig::new(false);
+        let market_data = BinanceConfig::preset_market_data();
+        let hft = BinanceConfig::preset_hft();
+        let conservative = BinanceConfig::preset_conservative();
+
+        for preset in [&market_data, &hft, &conservative] {
+            assert!(preset.validate().is_ok());
+            assert!(!preset.testnet);
+        }
+
+        assert!(market_data.adaptive_rate_limit);
+        assert!(market_data.retry_empty_klines);
+        assert!(market_data.backoff.max_attempts > default.backoff.max_attempts);
+
+        assert!(hft.timeout_seconds < default.timeout_seconds);
+        assert!(hft.requests_per_minute > default.requests_per_minute);
+        assert_eq!(hft.backoff.max_attempts, 2);
+        assert!(hft.backoff.initial < default.backoff.initial);
+
+        assert!(conservative.requests_per_minute < default.requests_per_minute);
+        assert!(conservative.timeout_seconds > default.timeout_seconds);
+        assert!(conservative.adaptive_rate_limit);
+        assert!(conservative.backoff.max_delay > default.backoff.max_delay);
     }
 }
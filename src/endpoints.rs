@@ -1,5 +1,7 @@
 //! Binance API endpoint definitions
 
+use crate::models::{normalize_symbol, Interval};
+
 /// API endpoint paths
 pub struct Endpoints;
 
@@ -16,6 +18,12 @@ impl Endpoints {
         "/api/v3/ticker/24hr"
     }
 
+    /// Get best bid/ask
+    /// GET /api/v3/ticker/bookTicker
+    pub fn book_ticker() -> &'static str {
+        "/api/v3/ticker/bookTicker"
+    }
+
     /// Get klines (candlestick data)
     /// GET /api/v3/klines
     pub fn klines() -> &'static str {
@@ -34,6 +42,18 @@ impl Endpoints {
         "/api/v3/trades"
     }
 
+    /// Get older trades (API key required)
+    /// GET /api/v3/historicalTrades
+    pub fn historical_trades() -> &'static str {
+        "/api/v3/historicalTrades"
+    }
+
+    /// Get compressed/aggregate trades
+    /// GET /api/v3/aggTrades
+    pub fn agg_trades() -> &'static str {
+        "/api/v3/aggTrades"
+    }
+
     /// Get exchange info
     /// GET /api/v3/exchangeInfo
     pub fn exchange_info() -> &'static str {
@@ -46,6 +66,36 @@ impl Endpoints {
         "/api/v3/time"
     }
 
+    /// Place order (signed)
+    /// POST /api/v3/order
+    pub fn order() -> &'static str {
+        "/api/v3/order"
+    }
+
+    /// Place OCO order (signed)
+    /// POST /api/v3/order/oco
+    pub fn order_oco() -> &'static str {
+        "/api/v3/order/oco"
+    }
+
+    /// Cancel an order and place a new one atomically (signed)
+    /// POST /api/v3/order/cancelReplace
+    pub fn order_cancel_replace() -> &'static str {
+        "/api/v3/order/cancelReplace"
+    }
+
+    /// All orders for a symbol (signed)
+    /// GET /api/v3/allOrders
+    pub fn all_orders() -> &'static str {
+        "/api/v3/allOrders"
+    }
+
+    /// Account trade list (signed)
+    /// GET /api/v3/myTrades
+    pub fn my_trades() -> &'static str {
+        "/api/v3/myTrades"
+    }
+
     /// Ping
     /// GET /api/v3/ping
     pub fn ping() -> &'static str {
@@ -60,31 +110,79 @@ impl WebSocketStreams {
     /// Individual symbol ticker stream
     /// wss://stream.binance.com:9443/ws/<symbol>@ticker
     pub fn ticker(symbol: &str) -> String {
-        format!("{}@ticker", symbol.to_lowercase())
+        format!("{}@ticker", normalize_symbol(symbol).to_lowercase())
     }
 
     /// Individual symbol kline/candlestick stream
-    /// wss://stream.binance.com:9443/ws/<symbol>@kline_<interval>
-    pub fn kline(symbol: &str, interval: &str) -> String {
-        format!("{}@kline_{}", symbol.to_lowercase(), interval)
+    /// wss://stream.binance.com:9443/ws/<symbol>@kline_<interval>[@<timezone>]
+    ///
+    /// `timezone` is a UTC offset such as `+08:00` that shifts where daily and
+    /// longer candles start; intervals are UTC-aligned without it.
+    pub fn kline(symbol: &str, interval: &str, timezone: Option<&str>) -> String {
+        match timezone {
+            Some(tz) => format!("{}@kline_{}@{}", normalize_symbol(symbol).to_lowercase(), interval, tz),
+            None => format!("{}@kline_{}", normalize_symbol(symbol).to_lowercase(), interval),
+        }
+    }
+
+    /// One kline stream per interval, in the order given
+    pub fn klines(symbol: &str, intervals: &[Interval]) -> Vec<String> {
+        intervals
+            .iter()
+            .map(|interval| Self::kline(symbol, &interval.to_string(), None))
+            .collect()
     }
 
     /// Individual symbol trade stream
     /// wss://stream.binance.com:9443/ws/<symbol>@trade
     pub fn trade(symbol: &str) -> String {
-        format!("{}@trade", symbol.to_lowercase())
+        format!("{}@trade", normalize_symbol(symbol).to_lowercase())
+    }
+
+    /// Individual symbol aggregate trade stream
+    /// wss://stream.binance.com:9443/ws/<symbol>@aggTrade
+    pub fn agg_trade(symbol: &str) -> String {
+        format!("{}@aggTrade", normalize_symbol(symbol).to_lowercase())
     }
 
     /// Individual symbol mini ticker stream
     /// wss://stream.binance.com:9443/ws/<symbol>@miniTicker
     pub fn mini_ticker(symbol: &str) -> String {
-        format!("{}@miniTicker", symbol.to_lowercase())
+        format!("{}@miniTicker", normalize_symbol(symbol).to_lowercase())
     }
 
-    /// Partial book depth stream
+    /// Diff book depth stream
     /// wss://stream.binance.com:9443/ws/<symbol>@depth
     pub fn depth(symbol: &str) -> String {
-        format!("{}@depth", symbol.to_lowercase())
+        format!("{}@depth", normalize_symbol(symbol).to_lowercase())
+    }
+
+    /// Partial book depth stream with the top `levels` (5, 10 or 20)
+    /// wss://stream.binance.com:9443/ws/<symbol>@depth<levels>
+    pub fn partial_depth(symbol: &str, levels: usize) -> String {
+        format!("{}@depth{}", normalize_symbol(symbol).to_lowercase(), levels)
+    }
+
+    /// Individual symbol book ticker stream
+    /// wss://stream.binance.com:9443/ws/<symbol>@bookTicker
+    pub fn book_ticker(symbol: &str) -> String {
+        format!("{}@bookTicker", normalize_symbol(symbol).to_lowercase())
+    }
+
+    /// Every stream available for `symbol`, including one kline stream per
+    /// interval
+    pub fn all_for(symbol: &str) -> Vec<String> {
+        let mut streams = vec![
+            Self::ticker(symbol),
+            Self::mini_ticker(symbol),
+            Self::trade(symbol),
+            Self::agg_trade(symbol),
+            Self::book_ticker(symbol),
+            Self::depth(symbol),
+        ];
+        streams.extend([5, 10, 20].map(|levels| Self::partial_depth(symbol, levels)));
+        streams.extend(Self::klines(symbol, &Interval::ALL));
+        streams
     }
 }
 
@@ -101,7 +199,42 @@ mod tests {
     #[test]
     fn test_websocket_streams() {
         assert_eq!(WebSocketStreams::ticker("BTCUSDT"), "btcusdt@ticker");
-        assert_eq!(WebSocketStreams::kline("ETHUSDT", "1m"), "ethusdt@kline_1m");
+        assert_eq!(WebSocketStreams::trade("BTC/USDT"), "btcusdt@trade");
+        assert_eq!(WebSocketStreams::kline("ETHUSDT", "1m", None), "ethusdt@kline_1m");
+        assert_eq!(
+            WebSocketStreams::kline("BTCUSDT", "1d", Some("+08:00")),
+            "btcusdt@kline_1d@+08:00"
+        );
         assert_eq!(WebSocketStreams::trade("BTCUSDT"), "btcusdt@trade");
     }
+
+    #[test]
+    fn test_kline_streams_for_intervals() {
+        let streams = WebSocketStreams::klines(
+            "BTC/USDT",
+            &[Interval::Minutes1, Interval::Hours4, Interval::Months1],
+        );
+        assert_eq!(
+            streams,
+            vec!["btcusdt@kline_1m", "btcusdt@kline_4h", "btcusdt@kline_1M"]
+        );
+    }
+
+    #[test]
+    fn test_all_streams_for_symbol() {
+        let streams = WebSocketStreams::all_for("BTCUSDT");
+
+        let expected: Vec<String> = [
+            "ticker", "miniTicker", "trade", "aggTrade", "bookTicker",
+            "depth", "depth5", "depth10", "depth20",
+            "kline_1s", "kline_1m", "kline_3m", "kline_5m", "kline_15m", "kline_30m",
+            "kline_1h", "kline_2h", "kline_4h", "kline_6h", "kline_8h", "kline_12h",
+            "kline_1d", "kline_3d", "kline_1w", "kline_1M",
+        ]
+        .iter()
+        .map(|suffix| format!("btcusdt@{}", suffix))
+        .collect();
+
+        assert_eq!(streams, expected);
+    }
 }
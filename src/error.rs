@@ -21,35 +21,77 @@ pub enum Error {
     #[error("Invalid interval: {0}")]
     InvalidInterval(String),
 
+    #[error("Invalid order: {0}")]
+    InvalidOrder(String),
+
     #[error("Network timeout after {0}s")]
     Timeout(u64),
 
     #[error("Deserialization error: {0}")]
     DeserializationError(String),
 
+    /// JSON that could not be parsed or did not match the expected shape;
+    /// the source keeps the line, column and category
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
     #[error("WebSocket error: {0}")]
     WebSocketError(String),
 
+    #[error("Unknown stream event type: {0}")]
+    UnknownEvent(String),
+
     #[error("WebSocket connection closed")]
     WebSocketClosed,
 
+    #[error("WebSocket closed by server ({code}): {reason}")]
+    WebSocketClosedWithReason { code: u16, reason: String },
+
     #[error("Invalid date range: start={start}, end={end}")]
     InvalidDateRange { start: String, end: String },
 }
 
+/// Binance error codes for server-side conditions that clear up on their own
+/// (-1000 unknown, -1001 disconnected, -1006 unexpected response,
+/// -1007 backend timeout, -1008 server busy)
+const TRANSIENT_API_CODES: [i32; 5] = [-1000, -1001, -1006, -1007, -1008];
+
 impl Error {
     /// Check if error is retryable
+    ///
+    /// Transient errors plus rate limiting, which succeeds once the
+    /// `retry_after_seconds` window has passed.
     pub fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            Error::HttpError(_)
-                | Error::Timeout(_)
-                | Error::RateLimitExceeded { .. }
-                | Error::WebSocketClosed
-        )
+        self.is_transient() || self.is_rate_limit()
+    }
+
+    /// Check if error is a temporary failure worth retrying immediately
+    ///
+    /// Timeouts, connection failures, dropped WebSocket connections and
+    /// server-side (5xx or Binance internal) errors. Malformed requests,
+    /// rejected parameters and parse failures are permanent.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::HttpError(e) => {
+                e.is_timeout()
+                    || e.is_connect()
+                    || e.status().is_some_and(|status| status.is_server_error())
+            }
+            Error::ApiError { code, .. } => {
+                (500..=599).contains(code) || TRANSIENT_API_CODES.contains(code)
+            }
+            Error::Timeout(_)
+            | Error::WebSocketError(_)
+            | Error::WebSocketClosed
+            | Error::WebSocketClosedWithReason { .. } => true,
+            _ => false,
+        }
     }
 
     /// Check if error is related to rate limiting
@@ -57,3 +99,90 @@ impl Error {
         matches!(self, Error::RateLimitExceeded { .. })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(code: i32) -> Error {
+        Error::ApiError {
+            code,
+            msg: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_api_error_classification() {
+        // Invalid symbol / bad parameters are permanent
+        assert!(!api_error(-1121).is_transient());
+        assert!(!api_error(-1102).is_retryable());
+        assert!(!api_error(400).is_retryable());
+
+        // Binance internal errors and 5xx pass
+        assert!(api_error(-1001).is_transient());
+        assert!(api_error(-1008).is_transient());
+        assert!(api_error(503).is_transient());
+        assert!(api_error(503).is_retryable());
+    }
+
+    #[test]
+    fn test_variant_classification() {
+        let rate_limit = Error::RateLimitExceeded {
+            retry_after_seconds: 60,
+        };
+        assert!(!rate_limit.is_transient());
+        assert!(rate_limit.is_retryable());
+
+        assert!(Error::Timeout(10).is_transient());
+        assert!(Error::WebSocketClosed.is_transient());
+        assert!(Error::WebSocketClosedWithReason {
+            code: 1008,
+            reason: "Too many requests".to_string(),
+        }
+        .is_transient());
+        assert!(Error::WebSocketError("reset".to_string()).is_transient());
+
+        for permanent in [
+            Error::InvalidSymbol("FOO".to_string()),
+            Error::InvalidInterval("7m".to_string()),
+            Error::InvalidOrder("bad".to_string()),
+            Error::DeserializationError("eof".to_string()),
+            Error::JsonError(serde_json::from_str::<u32>("x").unwrap_err()),
+            Error::ConfigError("missing key".to_string()),
+            Error::IoError(std::io::ErrorKind::StorageFull.into()),
+            Error::UnknownEvent("newEvent".to_string()),
+            Error::InvalidDateRange {
+                start: "2".to_string(),
+                end: "1".to_string(),
+            },
+        ] {
+            assert!(!permanent.is_transient(), "{:?}", permanent);
+            assert!(!permanent.is_retryable(), "{:?}", permanent);
+        }
+    }
+
+    #[test]
+    fn test_http_error_classification() {
+        // Malformed URL is a builder error, not worth retrying
+        let builder_error = reqwest::Client::new().get("not a url").build().unwrap_err();
+        assert!(!Error::HttpError(builder_error).is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_connect_error_is_transient() {
+        // Nothing listens on port 1
+        let connect_error = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+        assert!(Error::HttpError(connect_error).is_transient());
+    }
+
+    #[test]
+    fn test_json_error_source() {
+        use std::error::Error as _;
+
+        let err = Error::from(serde_json::from_str::<Vec<u32>>("[1,\n2,").unwrap_err());
+        let source = err.source().expect("JSON errors keep their source");
+        let json = source.downcast_ref::<serde_json::Error>().unwrap();
+        assert_eq!(json.line(), 2);
+        assert!(json.is_eof());
+    }
+}
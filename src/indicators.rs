@@ -0,0 +1,599 @@
+//! Offline computations over fetched market data
+//!
+//! Nothing here calls the API: these helpers derive new series from klines
+//! and trades you already have.
+
+use crate::{
+    error::{Error, Result},
+    models::{AggTrade, Interval, Kline, Trade, TradeSide},
+};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Merge consecutive `from` candles into `to` candles (e.g. 1m into 5m)
+///
+/// Candles are grouped by the `to` period their open time falls in: open is
+/// the first open, close the last close, high/low the extremes, and volumes
+/// and trade counts are summed. A merged candle is only marked closed when
+/// its last input candle is closed and ends the period, so partial periods
+/// at either end of the input are kept but flagged.
+///
+/// Input must be sorted by open time.
+///
+/// # Errors
+/// Returns `InvalidInterval` if `to` is not a whole multiple of `from`, or
+/// either is `Months1` (months have no fixed length).
+///
+/// # Example
+/// ```no_run
+/// use binance_connector::{indicators, BinanceClient, BinanceConfig, Interval};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = BinanceClient::new(BinanceConfig::new(false))?;
+///     let one_minute = client.get_klines("BTCUSDT", Interval::Minutes1, 600).await?;
+///
+///     let five_minute = indicators::resample(&one_minute, Interval::Minutes1, Interval::Minutes5)?;
+///     println!("{} candles", five_minute.len());
+///     Ok(())
+/// }
+/// ```
+pub fn resample(klines: &[Kline], from: Interval, to: Interval) -> Result<Vec<Kline>> {
+    if from == Interval::Months1 || to == Interval::Months1 {
+        return Err(Error::InvalidInterval(
+            "cannot resample monthly candles".to_string(),
+        ));
+    }
+
+    let from_ms = from.duration_ms();
+    let to_ms = to.duration_ms();
+    if to_ms < from_ms || to_ms % from_ms != 0 {
+        return Err(Error::InvalidInterval(format!(
+            "{} is not a multiple of {}",
+            to, from
+        )));
+    }
+
+    let period_start = |kline: &Kline| to.period_start(kline.open_time.timestamp_millis());
+
+    let mut resampled: Vec<Kline> = Vec::new();
+    let mut current_start = None;
+
+    for kline in klines {
+        let start = period_start(kline);
+
+        match resampled.last_mut() {
+            Some(merged) if current_start == Some(start) => {
+                merged.high = merged.high.max(kline.high);
+                merged.low = merged.low.min(kline.low);
+                merged.close = kline.close;
+                merged.volume += kline.volume;
+                merged.quote_volume += kline.quote_volume;
+                merged.trades += kline.trades;
+                merged.taker_buy_base += kline.taker_buy_base;
+                merged.taker_buy_quote += kline.taker_buy_quote;
+                merged.is_closed = kline.is_closed && kline.close_time >= merged.close_time;
+            }
+            _ => {
+                let open_time = timestamp(start)?;
+                let close_time = timestamp(start + to_ms - 1)?;
+                resampled.push(Kline {
+                    open_time,
+                    close_time,
+                    is_closed: kline.is_closed && kline.close_time >= close_time,
+                    interval: Some(to),
+                    ..kline.clone()
+                });
+                current_start = Some(start);
+            }
+        }
+    }
+
+    Ok(resampled)
+}
+
+/// A trade print that can feed [`RollingVwap`]
+pub trait TradePrint {
+    /// Execution price
+    fn price(&self) -> f64;
+    /// Executed base quantity
+    fn quantity(&self) -> f64;
+    /// Execution time
+    fn time(&self) -> DateTime<Utc>;
+}
+
+impl TradePrint for Trade {
+    fn price(&self) -> f64 {
+        self.price
+    }
+    fn quantity(&self) -> f64 {
+        self.quantity
+    }
+    fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
+
+impl TradePrint for AggTrade {
+    fn price(&self) -> f64 {
+        self.price
+    }
+    fn quantity(&self) -> f64 {
+        self.quantity
+    }
+    fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
+
+/// Exponentially time-decayed volume-weighted average price
+///
+/// Each trade contributes `price * quantity` and `quantity`, and both sums
+/// halve for every `half_life` of trade time that passes, so the value
+/// tracks recent fills. Time is taken from the trades themselves; a trade
+/// older than the latest one seen is added without decaying the sums.
+///
+/// # Example
+/// ```no_run
+/// use binance_connector::{indicators::RollingVwap, BinanceConfig, BinanceWebSocket};
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let ws = BinanceWebSocket::new(BinanceConfig::new(false))?;
+///     let mut trades = ws.trade_stream("BTCUSDT").await?;
+///     let mut vwap = RollingVwap::new(Duration::from_secs(30));
+///
+///     while let Some(trade) = trades.recv().await {
+///         vwap.update(&trade?);
+///         println!("fair value: {:?}", vwap.value());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingVwap {
+    half_life_ms: f64,
+    price_volume: f64,
+    volume: f64,
+    last_time: Option<DateTime<Utc>>,
+}
+
+impl RollingVwap {
+    /// Create an empty VWAP decaying over `half_life`
+    pub fn new(half_life: Duration) -> Self {
+        Self {
+            half_life_ms: half_life.as_secs_f64() * 1_000.0,
+            price_volume: 0.0,
+            volume: 0.0,
+            last_time: None,
+        }
+    }
+
+    /// Add a trade, decaying earlier trades by the time elapsed since them
+    pub fn update(&mut self, trade: &impl TradePrint) {
+        let time = trade.time();
+        match self.last_time {
+            Some(last) if time <= last => {}
+            Some(last) => {
+                let elapsed_ms = (time - last).num_milliseconds() as f64;
+                let decay = if self.half_life_ms > 0.0 {
+                    0.5f64.powf(elapsed_ms / self.half_life_ms)
+                } else {
+                    0.0
+                };
+                self.price_volume *= decay;
+                self.volume *= decay;
+                self.last_time = Some(time);
+            }
+            None => self.last_time = Some(time),
+        }
+
+        self.price_volume += trade.price() * trade.quantity();
+        self.volume += trade.quantity();
+    }
+
+    /// Current VWAP, or None before any volume has traded
+    pub fn value(&self) -> Option<f64> {
+        (self.volume > 0.0).then(|| self.price_volume / self.volume)
+    }
+}
+
+/// Reports each candle of a kline stream as final exactly once
+///
+/// Binance may send several `is_closed` frames for the same candle, and a
+/// closing frame can be missed across a reconnect. Feed the tracker every
+/// kline of one symbol and interval in arrival order: a candle is finalized
+/// on its first closed frame, or when a newer candle starts without one
+/// having been seen. Frames for candles older than the latest are ignored.
+///
+/// # Example
+/// ```no_run
+/// use binance_connector::{indicators::KlineTracker, BinanceConfig, BinanceWebSocket, Interval};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let ws = BinanceWebSocket::new(BinanceConfig::new(false))?;
+///     let mut klines = ws.kline_stream("BTCUSDT", Interval::Minutes1, None).await?;
+///     let mut tracker = KlineTracker::new();
+///
+///     while let Some(kline) = klines.recv().await {
+///         for candle in tracker.update(&kline?) {
+///             println!("final close {} at {}", candle.close, candle.close_time);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KlineTracker {
+    /// Latest frame of the newest candle seen
+    current: Option<Kline>,
+    /// Whether `current` has been reported as final
+    finalized: bool,
+}
+
+impl KlineTracker {
+    /// Create a tracker that has seen no candles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next kline, returning candles that just became final
+    ///
+    /// Returns at most two candles, oldest first: the previous candle if it
+    /// never closed, and `kline` itself if it is closed.
+    pub fn update(&mut self, kline: &Kline) -> Vec<Kline> {
+        let mut finalized = Vec::new();
+
+        match &self.current {
+            Some(current) if kline.open_time < current.open_time => return finalized,
+            Some(current) if kline.open_time == current.open_time && self.finalized => {
+                return finalized;
+            }
+            Some(current) if kline.open_time > current.open_time => {
+                if !self.finalized {
+                    finalized.push(Kline {
+                        is_closed: true,
+                        ..current.clone()
+                    });
+                }
+                self.finalized = false;
+            }
+            _ => {}
+        }
+
+        self.current = Some(kline.clone());
+        if kline.is_closed {
+            self.finalized = true;
+            finalized.push(kline.clone());
+        }
+        finalized
+    }
+
+    /// Latest frame of the newest candle seen
+    pub fn current(&self) -> Option<&Kline> {
+        self.current.as_ref()
+    }
+}
+
+/// Builds candles of any bucket size from a trade stream
+///
+/// Binance only offers fixed kline intervals; this aggregates raw trades of
+/// one symbol into candles of an arbitrary `Duration` (e.g. 250ms or 45s).
+/// Buckets are aligned to the Unix epoch. A candle is emitted, marked
+/// closed, when the first trade of a later bucket arrives; buckets without
+/// trades produce no candle. Trades older than the candle being built are
+/// dropped.
+///
+/// # Example
+/// ```no_run
+/// use binance_connector::{indicators::CandleAggregator, BinanceConfig, BinanceWebSocket};
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let ws = BinanceWebSocket::new(BinanceConfig::new(false))?;
+///     let mut trades = ws.trade_stream("BTCUSDT").await?;
+///     let mut candles = CandleAggregator::new(Duration::from_millis(250));
+///
+///     while let Some(trade) = trades.recv().await {
+///         if let Some(candle) = candles.update(&trade?) {
+///             println!("{} O {} C {} V {}", candle.open_time, candle.open, candle.close, candle.volume);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CandleAggregator {
+    bucket_ms: i64,
+    current: Option<Kline>,
+}
+
+impl CandleAggregator {
+    /// Create an aggregator for `bucket`-long candles (at least 1ms)
+    pub fn new(bucket: Duration) -> Self {
+        Self {
+            bucket_ms: (bucket.as_millis() as i64).max(1),
+            current: None,
+        }
+    }
+
+    /// Add a trade, returning the previous candle if the trade closed it
+    pub fn update(&mut self, trade: &Trade) -> Option<Kline> {
+        let time = trade.time.timestamp_millis();
+        let start = time - time.rem_euclid(self.bucket_ms);
+        let taker_buy = trade.aggressor_side() == TradeSide::Buy;
+
+        match self.current.as_mut() {
+            Some(candle) if start < candle.open_time.timestamp_millis() => None,
+            Some(candle) if start == candle.open_time.timestamp_millis() => {
+                candle.high = candle.high.max(trade.price);
+                candle.low = candle.low.min(trade.price);
+                candle.close = trade.price;
+                candle.volume += trade.quantity;
+                candle.quote_volume += trade.quote_quantity;
+                candle.trades += 1;
+                if taker_buy {
+                    candle.taker_buy_base += trade.quantity;
+                    candle.taker_buy_quote += trade.quote_quantity;
+                }
+                None
+            }
+            _ => {
+                let candle = Kline {
+                    symbol: trade.symbol.clone(),
+                    open_time: DateTime::from_timestamp_millis(start)?,
+                    close_time: DateTime::from_timestamp_millis(start + self.bucket_ms - 1)?,
+                    open: trade.price,
+                    high: trade.price,
+                    low: trade.price,
+                    close: trade.price,
+                    volume: trade.quantity,
+                    quote_volume: trade.quote_quantity,
+                    trades: 1,
+                    taker_buy_base: if taker_buy { trade.quantity } else { 0.0 },
+                    taker_buy_quote: if taker_buy { trade.quote_quantity } else { 0.0 },
+                    is_closed: false,
+                    interval: Interval::try_from(self.bucket_ms).ok(),
+                };
+                self.current.replace(candle).map(|previous| Kline {
+                    is_closed: true,
+                    ..previous
+                })
+            }
+        }
+    }
+
+    /// Candle currently being built
+    pub fn current(&self) -> Option<&Kline> {
+        self.current.as_ref()
+    }
+}
+
+fn timestamp(ms: i64) -> Result<DateTime<Utc>> {
+    DateTime::from_timestamp_millis(ms)
+        .ok_or_else(|| Error::DeserializationError(format!("Invalid timestamp {}", ms)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 5m candle `i` of the hour starting at 2021-12-20 12:00 UTC
+    fn five_minute(i: i64) -> Kline {
+        let open = 1_640_001_600_000 + i * 300_000;
+        Kline {
+            symbol: "BTCUSDT".to_string(),
+            open_time: DateTime::from_timestamp_millis(open).unwrap(),
+            close_time: DateTime::from_timestamp_millis(open + 299_999).unwrap(),
+            open: 100.0 + i as f64,
+            high: 105.0 + i as f64,
+            low: 95.0 - i as f64,
+            close: 101.0 + i as f64,
+            volume: 10.0,
+            quote_volume: 1_000.0,
+            trades: 5,
+            taker_buy_base: 4.0,
+            taker_buy_quote: 400.0,
+            is_closed: true,
+            interval: Some(Interval::Minutes5),
+        }
+    }
+
+    #[test]
+    fn test_resample_5m_to_1h() {
+        let klines: Vec<Kline> = (0..12).map(five_minute).collect();
+        let hourly = resample(&klines, Interval::Minutes5, Interval::Hours1).unwrap();
+
+        assert_eq!(hourly.len(), 1);
+        let candle = &hourly[0];
+        assert_eq!(candle.open_time, klines[0].open_time);
+        assert_eq!(candle.close_time, klines[11].close_time);
+        assert_eq!(candle.open, 100.0);
+        assert_eq!(candle.close, 112.0);
+        assert_eq!(candle.high, 116.0);
+        assert_eq!(candle.low, 84.0);
+        assert_eq!(candle.volume, 120.0);
+        assert_eq!(candle.quote_volume, 12_000.0);
+        assert_eq!(candle.trades, 60);
+        assert_eq!(candle.interval, Some(Interval::Hours1));
+        assert_eq!(candle.taker_buy_base, 48.0);
+        assert!(candle.is_closed);
+    }
+
+    #[test]
+    fn test_resample_partial_period_is_open() {
+        // Second hour only has its first two candles
+        let klines: Vec<Kline> = (0..14).map(five_minute).collect();
+        let hourly = resample(&klines, Interval::Minutes5, Interval::Hours1).unwrap();
+
+        assert_eq!(hourly.len(), 2);
+        assert!(hourly[0].is_closed);
+        assert!(!hourly[1].is_closed);
+        assert_eq!(hourly[1].open, 112.0);
+        assert_eq!(hourly[1].volume, 20.0);
+    }
+
+    #[test]
+    fn test_resample_rejects_non_multiple() {
+        assert!(matches!(
+            resample(&[], Interval::Minutes3, Interval::Minutes5),
+            Err(Error::InvalidInterval(_))
+        ));
+        assert!(matches!(
+            resample(&[], Interval::Hours1, Interval::Minutes5),
+            Err(Error::InvalidInterval(_))
+        ));
+        assert!(matches!(
+            resample(&[], Interval::Days1, Interval::Months1),
+            Err(Error::InvalidInterval(_))
+        ));
+    }
+
+    fn trade_at(ms: i64, price: f64, quantity: f64) -> Trade {
+        Trade {
+            id: ms,
+            symbol: "BTCUSDT".to_string(),
+            price,
+            quantity,
+            quote_quantity: price * quantity,
+            time: DateTime::from_timestamp_millis(ms).unwrap(),
+            is_buyer_maker: false,
+        }
+    }
+
+    #[test]
+    fn test_rolling_vwap_burst_matches_volume_weighted_mean() {
+        let mut vwap = RollingVwap::new(Duration::from_secs(60));
+        assert_eq!(vwap.value(), None);
+
+        // Simultaneous burst: no decay between fills
+        let burst = [(100.0, 1.0), (102.0, 3.0), (101.0, 2.0), (99.0, 4.0)];
+        for (price, quantity) in burst {
+            vwap.update(&trade_at(1_000, price, quantity));
+        }
+
+        let expected = (100.0 + 306.0 + 202.0 + 396.0) / 10.0;
+        assert!((vwap.value().unwrap() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rolling_vwap_converges_to_recent_trades() {
+        let mut vwap = RollingVwap::new(Duration::from_secs(1));
+        vwap.update(&trade_at(0, 100.0, 10.0));
+
+        // One half-life later the old fill counts half
+        vwap.update(&trade_at(1_000, 110.0, 5.0));
+        assert!((vwap.value().unwrap() - 105.0).abs() < 1e-9);
+
+        // A long burst at 120 drowns out the earlier fills
+        for i in 0..50 {
+            vwap.update(&trade_at(2_000 + i * 500, 120.0, 1.0));
+        }
+        assert!((vwap.value().unwrap() - 120.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_rolling_vwap_accepts_agg_trades() {
+        let mut vwap = RollingVwap::new(Duration::from_secs(10));
+        vwap.update(&trade_at(0, 100.0, 1.0));
+        vwap.update(&AggTrade {
+            id: 1,
+            symbol: "BTCUSDT".to_string(),
+            price: 104.0,
+            quantity: 3.0,
+            first_trade_id: 1,
+            last_trade_id: 3,
+            time: DateTime::from_timestamp_millis(0).unwrap(),
+            is_buyer_maker: true,
+        });
+        assert!((vwap.value().unwrap() - 103.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_kline_tracker_dedups_closed_frames() {
+        let mut tracker = KlineTracker::new();
+        let open = Kline {
+            is_closed: false,
+            ..five_minute(0)
+        };
+
+        assert!(tracker.update(&open).is_empty());
+        assert_eq!(tracker.update(&five_minute(0)).len(), 1);
+        assert!(tracker.update(&five_minute(0)).is_empty());
+        assert!(tracker.update(&five_minute(0)).is_empty());
+
+        // Next candle starting does not finalize the closed one again
+        let next = Kline {
+            is_closed: false,
+            ..five_minute(1)
+        };
+        assert!(tracker.update(&next).is_empty());
+        assert_eq!(tracker.current().unwrap().open_time, next.open_time);
+    }
+
+    #[test]
+    fn test_kline_tracker_finalizes_on_open_time_advance() {
+        let mut tracker = KlineTracker::new();
+        let forming = |i| Kline {
+            is_closed: false,
+            ..five_minute(i)
+        };
+
+        assert!(tracker.update(&forming(0)).is_empty());
+        let finalized = tracker.update(&forming(1));
+        assert_eq!(finalized.len(), 1);
+        assert_eq!(finalized[0].open_time, forming(0).open_time);
+        assert!(finalized[0].is_closed);
+
+        // A late frame for the finalized candle is ignored
+        assert!(tracker.update(&five_minute(0)).is_empty());
+
+        // Missed close followed by a closed frame of the next candle
+        let finalized = tracker.update(&five_minute(2));
+        assert_eq!(
+            finalized.iter().map(|k| k.open_time).collect::<Vec<_>>(),
+            vec![forming(1).open_time, five_minute(2).open_time]
+        );
+    }
+
+    #[test]
+    fn test_candle_aggregator_two_buckets() {
+        let mut candles = CandleAggregator::new(Duration::from_millis(250));
+        let sell = |ms, price, quantity| Trade {
+            is_buyer_maker: true,
+            ..trade_at(ms, price, quantity)
+        };
+
+        assert!(candles.update(&trade_at(1_000, 100.0, 1.0)).is_none());
+        assert!(candles.update(&sell(1_100, 103.0, 2.0)).is_none());
+        assert!(candles.update(&trade_at(1_249, 99.0, 1.0)).is_none());
+
+        let first = candles.update(&sell(1_260, 101.0, 4.0)).unwrap();
+        assert_eq!(first.open_time.timestamp_millis(), 1_000);
+        assert_eq!(first.close_time.timestamp_millis(), 1_249);
+        assert_eq!((first.open, first.high, first.low, first.close), (100.0, 103.0, 99.0, 99.0));
+        assert_eq!(first.volume, 4.0);
+        assert_eq!(first.quote_volume, 100.0 + 206.0 + 99.0);
+        assert_eq!(first.trades, 3);
+        assert_eq!(first.taker_buy_base, 2.0);
+        assert!(first.is_closed);
+
+        // A late trade for the closed bucket is dropped
+        assert!(candles.update(&trade_at(1_200, 500.0, 1.0)).is_none());
+
+        let second = candles.update(&trade_at(1_500, 102.0, 1.0)).unwrap();
+        assert_eq!(second.open_time.timestamp_millis(), 1_250);
+        assert_eq!((second.open, second.high, second.low, second.close), (101.0, 101.0, 101.0, 101.0));
+        assert_eq!(second.volume, 4.0);
+        assert_eq!(second.trades, 1);
+        assert_eq!(second.taker_buy_base, 0.0);
+
+        let current = candles.current().unwrap();
+        assert_eq!(current.open_time.timestamp_millis(), 1_500);
+        assert_eq!(current.close, 102.0);
+        assert!(!current.is_closed);
+    }
+}
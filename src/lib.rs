@@ -3,20 +3,41 @@
 //! High-performance Rust client for Binance cryptocurrency exchange.
 //! Supports REST API and WebSocket streaming for real-time data.
 
+pub mod backfill;
+pub mod cache;
 pub mod client;
 pub mod config;
 pub mod endpoints;
 pub mod error;
+pub mod indicators;
 pub mod models;
 pub mod rate_limiter;
+pub mod recorder;
+pub mod retry;
+mod telemetry;
 pub mod websocket;
+pub mod weights;
 
 // Re-export main types
+pub use backfill::BackfillPlan;
+pub use cache::CachingClient;
 pub use client::BinanceClient;
-pub use config::BinanceConfig;
+pub use config::{BinanceConfig, BinanceConfigBuilder};
 pub use error::{Error, Result};
-pub use models::{Interval, Kline, OrderBook, Symbol, Ticker, Trade};
-pub use websocket::BinanceWebSocket;
+pub use models::{
+    AccountTrade, AggTrade, BookTicker, CancelReplaceMode, CancelReplaceResponse, CancelReplaceStatus,
+    CommissionRates, HealthReport, Interval, Kline, MarketStats, MiniTicker, NewOrderRespType,
+    NewOrderResponse, OcoOrderRequest, OcoOrderResponse, OrderBook, OrderBookDelta, OrderFill, OrderResponse, OrderSide, OrderStatus,
+    OrderType, PriceImpact, PriceKey, PriceLevel, SideDelta, StatusChanges, Symbol, SymbolFilter, SymbolStatus, Ticker, TimeInForce,
+    Trade, TradeSide, assert_continuous, find_gaps, normalize_symbol, status_changes,
+};
+pub use recorder::{Recorder, RecorderHandle};
+pub use retry::BackoffPolicy;
+pub use weights::WeightTable;
+pub use websocket::{
+    BinanceWebSocket, CombinedMessage, MarketEvent, MultiplexHandle, StreamConfig, StreamEvent,
+    StreamHandle,
+};
 
 #[cfg(test)]
 mod tests {
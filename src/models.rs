@@ -1,7 +1,8 @@
 //! Data models for Binance API
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// OHLCV candlestick data (called "Kline" in Binance)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -19,6 +20,133 @@ pub struct Kline {
     pub taker_buy_base: f64,  // Taker buy volume (base)
     pub taker_buy_quote: f64, // Taker buy volume (quote)
     pub is_closed: bool,      // Is this candle finalized?
+    /// Candle interval, `None` for candles built at a non-standard interval
+    #[serde(default)]
+    pub interval: Option<Interval>,
+}
+
+impl Kline {
+    /// Column names matching [`to_csv_row`](Self::to_csv_row)
+    pub const CSV_HEADER: &'static str = "symbol,interval,open_time,close_time,open,high,low,close,\
+        volume,quote_volume,trades,taker_buy_base,taker_buy_quote,is_closed";
+
+    /// One CSV line (without newline) in [`CSV_HEADER`](Self::CSV_HEADER) order
+    ///
+    /// Times are RFC 3339 in UTC with milliseconds; a missing interval is an
+    /// empty field. Prices keep full precision, so
+    /// [`from_csv_row`](Self::from_csv_row) restores the same kline.
+    pub fn to_csv_row(&self) -> String {
+        let time = |t: &DateTime<Utc>| t.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.symbol,
+            self.interval.map(|i| i.to_string()).unwrap_or_default(),
+            time(&self.open_time),
+            time(&self.close_time),
+            self.open,
+            self.high,
+            self.low,
+            self.close,
+            self.volume,
+            self.quote_volume,
+            self.trades,
+            self.taker_buy_base,
+            self.taker_buy_quote,
+            self.is_closed
+        )
+    }
+
+    /// Parse a line written by [`to_csv_row`](Self::to_csv_row)
+    pub fn from_csv_row(line: &str) -> crate::Result<Kline> {
+        fn parse<T: std::str::FromStr>(field: &str, name: &str) -> crate::Result<T> {
+            field.trim().parse().map_err(|_| {
+                crate::Error::DeserializationError(format!("Invalid kline {}: {:?}", name, field))
+            })
+        }
+        fn time(field: &str, name: &str) -> crate::Result<DateTime<Utc>> {
+            DateTime::parse_from_rfc3339(field.trim())
+                .map(|t| t.with_timezone(&Utc))
+                .map_err(|_| {
+                    crate::Error::DeserializationError(format!("Invalid kline {}: {:?}", name, field))
+                })
+        }
+
+        let fields: Vec<&str> = line.trim_end_matches(['\r', '\n']).split(',').collect();
+        let [
+            symbol, interval, open_time, close_time,
+            open, high, low, close,
+            volume, quote_volume, trades, taker_buy_base, taker_buy_quote,
+            is_closed,
+        ] = fields[..] else {
+            return Err(crate::Error::DeserializationError(format!(
+                "Expected 14 kline fields, got {}",
+                fields.len()
+            )));
+        };
+
+        Ok(Kline {
+            symbol: symbol.trim().to_string(),
+            interval: match interval.trim() {
+                "" => None,
+                interval => Some(parse(interval, "interval")?),
+            },
+            open_time: time(open_time, "open_time")?,
+            close_time: time(close_time, "close_time")?,
+            open: parse(open, "open")?,
+            high: parse(high, "high")?,
+            low: parse(low, "low")?,
+            close: parse(close, "close")?,
+            volume: parse(volume, "volume")?,
+            quote_volume: parse(quote_volume, "quote_volume")?,
+            trades: parse(trades, "trades")?,
+            taker_buy_base: parse(taker_buy_base, "taker_buy_base")?,
+            taker_buy_quote: parse(taker_buy_quote, "taker_buy_quote")?,
+            is_closed: parse(is_closed, "is_closed")?,
+        })
+    }
+}
+
+/// Find stretches of missing candles in a series sorted by open time
+///
+/// Returns `(start, end)` for each gap: `start` is the open time of the
+/// first missing candle and `end` the open time of the next candle present,
+/// so `start..end` is the window to re-request. Monthly candles follow the
+/// calendar; other intervals step by [`Interval::duration_ms`].
+pub fn find_gaps(klines: &[Kline], interval: Interval) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    klines
+        .windows(2)
+        .filter_map(|pair| {
+            let expected = next_open_time(pair[0].open_time, interval)?;
+            (pair[1].open_time > expected).then_some((expected, pair[1].open_time))
+        })
+        .collect()
+}
+
+/// Check that each candle opens within `tolerance` of the previous close
+///
+/// Consecutive closes and opens rarely match exactly, but a jump larger
+/// than `tolerance` (in quote currency) usually means bad or misordered
+/// data. Complements [`find_gaps`], which checks open times. Returns
+/// `Error::DeserializationError` describing the first discontinuity.
+pub fn assert_continuous(klines: &[Kline], tolerance: f64) -> crate::Result<()> {
+    match klines
+        .windows(2)
+        .find(|pair| (pair[1].open - pair[0].close).abs() > tolerance)
+    {
+        Some(pair) => Err(crate::Error::DeserializationError(format!(
+            "{} candle at {} opens at {} but previous close was {}",
+            pair[1].symbol, pair[1].open_time, pair[1].open, pair[0].close
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Open time of the candle after one opening at `open_time`
+fn next_open_time(open_time: DateTime<Utc>, interval: Interval) -> Option<DateTime<Utc>> {
+    match interval {
+        Interval::Months1 => open_time.checked_add_months(chrono::Months::new(1)),
+        _ => open_time.checked_add_signed(chrono::Duration::milliseconds(interval.duration_ms())),
+    }
 }
 
 /// Real-time ticker (price info)
@@ -26,7 +154,99 @@ pub struct Kline {
 pub struct Ticker {
     pub symbol: String,
     pub price: f64,
-    pub timestamp: DateTime<Utc>,
+    /// Server time the price is valid at; `None` for `/api/v3/ticker/price`,
+    /// which reports no time
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Local time the data was received
+    #[serde(default)]
+    pub received_at: DateTime<Utc>,
+}
+
+/// Rolling 24-hour mini ticker (OHLCV without bid/ask or trade ids)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MiniTicker {
+    pub symbol: String,
+    pub close_price: f64,
+    pub open_price: f64,
+    pub high_price: f64,
+    pub low_price: f64,
+    pub volume: f64,       // Total traded base asset volume
+    pub quote_volume: f64, // Total traded quote asset volume
+    pub event_time: DateTime<Utc>,
+}
+
+impl MiniTicker {
+    /// Latest price (the close of the rolling window)
+    pub fn price(&self) -> f64 {
+        self.close_price
+    }
+
+    /// Window statistics in the shared [`MarketStats`] shape
+    pub fn stats(&self) -> MarketStats {
+        MarketStats::from(self)
+    }
+}
+
+/// Window statistics common to every ticker flavour
+///
+/// Both [`Ticker24h`] (REST) and [`MiniTicker`] (the rolling-window stream)
+/// convert into this, so display and logging code can take either.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarketStats {
+    pub symbol: String,
+    pub last_price: f64,
+    pub open_price: f64,
+    pub high_price: f64,
+    pub low_price: f64,
+    pub volume: f64,
+    pub quote_volume: f64,
+    pub price_change: f64,
+    pub price_change_percent: f64,
+    /// End of the window (close time or stream event time)
+    pub as_of: DateTime<Utc>,
+}
+
+impl From<&Ticker24h> for MarketStats {
+    fn from(ticker: &Ticker24h) -> Self {
+        MarketStats {
+            symbol: ticker.symbol.clone(),
+            last_price: ticker.last_price,
+            open_price: ticker.open_price,
+            high_price: ticker.high_price,
+            low_price: ticker.low_price,
+            volume: ticker.volume,
+            quote_volume: ticker.quote_volume,
+            price_change: ticker.price_change,
+            price_change_percent: ticker.price_change_percent,
+            as_of: ticker.close_time,
+        }
+    }
+}
+
+impl From<&MiniTicker> for MarketStats {
+    /// The mini ticker carries no change fields, so they are derived from
+    /// the open and close prices (0.0 percent if open is zero)
+    fn from(ticker: &MiniTicker) -> Self {
+        let price_change = ticker.close_price - ticker.open_price;
+        let price_change_percent = if ticker.open_price > 0.0 {
+            price_change / ticker.open_price * 100.0
+        } else {
+            0.0
+        };
+        MarketStats {
+            symbol: ticker.symbol.clone(),
+            last_price: ticker.close_price,
+            open_price: ticker.open_price,
+            high_price: ticker.high_price,
+            low_price: ticker.low_price,
+            volume: ticker.volume,
+            quote_volume: ticker.quote_volume,
+            price_change,
+            price_change_percent,
+            as_of: ticker.event_time,
+        }
+    }
 }
 
 /// 24-hour ticker statistics
@@ -53,13 +273,86 @@ pub struct Ticker24h {
 }
 
 impl Ticker24h {
+    /// Window statistics in the shared [`MarketStats`] shape
+    pub fn stats(&self) -> MarketStats {
+        MarketStats::from(self)
+    }
+
+    /// Ask minus bid
+    #[deprecated(note = "returns 0.0-based values when a side has no quote; use `try_spread`")]
     pub fn spread(&self) -> f64 {
         self.ask_price - self.bid_price
     }
 
+    /// Midpoint of bid and ask
+    #[deprecated(note = "returns 0.0-based values when a side has no quote; use `try_mid`")]
     pub fn mid(&self) -> f64 {
         (self.bid_price + self.ask_price) / 2.0
     }
+
+    /// Whether both a bid and an ask are present
+    ///
+    /// Illiquid symbols can report empty or zero quotes.
+    pub fn has_quotes(&self) -> bool {
+        self.bid_price > 0.0 && self.ask_price > 0.0
+    }
+
+    /// Ask minus bid, or `None` without quotes
+    pub fn try_spread(&self) -> Option<f64> {
+        self.has_quotes().then_some(self.ask_price - self.bid_price)
+    }
+
+    /// Midpoint of bid and ask, or `None` without quotes
+    pub fn try_mid(&self) -> Option<f64> {
+        self.has_quotes().then_some((self.bid_price + self.ask_price) / 2.0)
+    }
+
+    /// 24h high minus 24h low
+    pub fn range(&self) -> f64 {
+        self.high_price - self.low_price
+    }
+
+    /// 24h range as a percentage of the open price (0.0 if open is zero)
+    pub fn range_percent(&self) -> f64 {
+        if self.open_price > 0.0 {
+            self.range() / self.open_price * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Bid/ask spread in basis points of the mid price, or `None` without
+    /// quotes
+    pub fn spread_bps(&self) -> Option<f64> {
+        Some(self.try_spread()? / self.try_mid()? * 10_000.0)
+    }
+
+    /// Whether the price rose over the window
+    pub fn is_up(&self) -> bool {
+        self.price_change > 0.0
+    }
+}
+
+/// Best bid/ask for a symbol
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BookTicker {
+    pub symbol: String,
+    pub bid_price: f64,
+    pub bid_qty: f64,
+    pub ask_price: f64,
+    pub ask_qty: f64,
+}
+
+impl BookTicker {
+    /// Bid/ask spread in basis points of the mid price, or `None` if either
+    /// side is empty
+    pub fn spread_bps(&self) -> Option<f64> {
+        if self.bid_price <= 0.0 || self.ask_price <= 0.0 {
+            return None;
+        }
+        let mid = (self.bid_price + self.ask_price) / 2.0;
+        Some((self.ask_price - self.bid_price) / mid * 10_000.0)
+    }
 }
 
 /// Order book (market depth)
@@ -69,15 +362,344 @@ pub struct OrderBook {
     pub last_update_id: i64,
     pub bids: Vec<PriceLevel>,
     pub asks: Vec<PriceLevel>,
+    /// Time the book is valid at: the event time for WebSocket updates.
+    /// The REST depth endpoint reports no server time, so REST snapshots use
+    /// the local receive time.
     pub timestamp: DateTime<Utc>,
+    /// Local time the data was received
+    #[serde(default)]
+    pub received_at: DateTime<Utc>,
+    /// Depth `limit` the REST snapshot was requested with (`None` for
+    /// WebSocket books)
+    #[serde(default)]
+    pub requested_limit: Option<usize>,
+}
+
+impl OrderBook {
+    /// Whether the book holds every level the exchange has
+    ///
+    /// Binance returns up to `requested_limit` levels per side, so a side
+    /// with exactly that many may have been cut off; fewer means the whole
+    /// side fit.
+    pub fn is_full_depth(&self, requested_limit: usize) -> bool {
+        self.bids.len() < requested_limit && self.asks.len() < requested_limit
+    }
+
+    /// Bids and asks as maps from price to quantity, for level lookups and
+    /// range scans in O(log n)
+    ///
+    /// Both maps iterate in ascending price order, so the best bid is
+    /// `bids.last_key_value()` and the best ask `asks.first_key_value()`.
+    /// See [`PriceKey`] for how float prices compare as keys.
+    ///
+    /// # Example
+    /// ```
+    /// use binance_connector::{OrderBook, PriceKey, PriceLevel};
+    ///
+    /// let book = OrderBook {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     last_update_id: 1,
+    ///     bids: vec![
+    ///         PriceLevel { price: 43000.0, quantity: 1.5 },
+    ///         PriceLevel { price: 42999.0, quantity: 2.0 },
+    ///     ],
+    ///     asks: vec![],
+    ///     timestamp: chrono::Utc::now(),
+    ///     received_at: chrono::Utc::now(),
+    ///     requested_limit: None,
+    /// };
+    /// let (bids, _asks) = book.as_maps();
+    /// assert_eq!(bids.get(&PriceKey(43000.0)), Some(&1.5));
+    /// let near_top: f64 = bids.range(PriceKey(42999.5)..).map(|(_, qty)| qty).sum();
+    /// assert_eq!(near_top, 1.5);
+    /// ```
+    pub fn as_maps(&self) -> (BTreeMap<PriceKey, f64>, BTreeMap<PriceKey, f64>) {
+        let to_map = |levels: &[PriceLevel]| {
+            levels
+                .iter()
+                .map(|level| (PriceKey(level.price), level.quantity))
+                .collect()
+        };
+        (to_map(&self.bids), to_map(&self.asks))
+    }
+
+    /// Volume imbalance over the top `levels` of each side
+    ///
+    /// `(bid_vol - ask_vol) / (bid_vol + ask_vol)`, in `[-1, 1]`. Positive
+    /// values mean more resting bid volume. Returns 0.0 for an empty book.
+    pub fn imbalance(&self, levels: usize) -> f64 {
+        let bid_vol: f64 = self.bids.iter().take(levels).map(|l| l.quantity).sum();
+        let ask_vol: f64 = self.asks.iter().take(levels).map(|l| l.quantity).sum();
+        let total = bid_vol + ask_vol;
+
+        if total > 0.0 {
+            (bid_vol - ask_vol) / total
+        } else {
+            0.0
+        }
+    }
+
+    /// Size-weighted mid price of the best bid and ask
+    ///
+    /// `(best_bid * ask_qty + best_ask * bid_qty) / (bid_qty + ask_qty)`.
+    /// Returns None if either side is empty.
+    pub fn microprice(&self) -> Option<f64> {
+        let bid = self.bids.first()?;
+        let ask = self.asks.first()?;
+        let total = bid.quantity + ask.quantity;
+
+        if total > 0.0 {
+            Some((bid.price * ask.quantity + ask.price * bid.quantity) / total)
+        } else {
+            None
+        }
+    }
+
+    /// Simulate a market order of `base_qty` against the book
+    ///
+    /// Buys walk the asks and sells walk the bids, best level first. If the
+    /// visible depth is too thin, the result reports the partial fill and
+    /// the unfilled `remaining_qty`. Returns None for a non-positive
+    /// quantity or an empty side.
+    pub fn price_impact(&self, side: OrderSide, base_qty: f64) -> Option<PriceImpact> {
+        let levels = match side {
+            OrderSide::Buy => &self.asks,
+            OrderSide::Sell => &self.bids,
+        };
+        if base_qty.is_nan() || base_qty <= 0.0 || levels.is_empty() {
+            return None;
+        }
+
+        let mut remaining_qty = base_qty;
+        let mut notional = 0.0;
+        let mut worst_price = levels[0].price;
+
+        for level in levels {
+            if remaining_qty <= 0.0 {
+                break;
+            }
+            let take = level.quantity.min(remaining_qty);
+            notional += take * level.price;
+            remaining_qty -= take;
+            worst_price = level.price;
+        }
+
+        let filled_qty = base_qty - remaining_qty;
+        Some(PriceImpact {
+            avg_price: if filled_qty > 0.0 { notional / filled_qty } else { worst_price },
+            worst_price,
+            filled_qty,
+            remaining_qty,
+        })
+    }
+
+    /// Apply a depth diff to this book
+    ///
+    /// Upserts each level by price, removes levels whose new quantity is zero
+    /// (how Binance signals a level was cleared), re-sorts both sides (bids
+    /// descending, asks ascending) and sets `last_update_id`. Use it to keep a
+    /// REST snapshot current with diff depth stream events; checking update
+    /// id continuity is left to the caller. `timestamp` and `received_at` are
+    /// set to now; overwrite `timestamp` with the event time if known.
+    pub fn apply_diff(
+        &mut self,
+        bids: &[PriceLevel],
+        asks: &[PriceLevel],
+        new_update_id: i64,
+    ) {
+        apply_levels(&mut self.bids, bids);
+        apply_levels(&mut self.asks, asks);
+        sort_bids(&mut self.bids);
+        sort_asks(&mut self.asks);
+        self.last_update_id = new_update_id;
+        self.timestamp = Utc::now();
+        self.received_at = self.timestamp;
+    }
+
+    /// Levels that changed since `previous`, per side
+    ///
+    /// Levels are matched by price: a new price is added, a vanished price
+    /// removed and a price whose quantity differs changed. If
+    /// `last_update_id` did not advance past `previous`, the books are taken
+    /// to be the same state and the delta is empty.
+    pub fn diff(&self, previous: &OrderBook) -> OrderBookDelta {
+        if self.last_update_id <= previous.last_update_id {
+            return OrderBookDelta::default();
+        }
+
+        OrderBookDelta {
+            bids: diff_levels(&self.bids, &previous.bids),
+            asks: diff_levels(&self.asks, &previous.asks),
+        }
+    }
+
+    /// Keep only the best `max_levels` on each side (assumes sorted sides)
+    pub(crate) fn truncate_levels(&mut self, max_levels: usize) {
+        self.bids.truncate(max_levels);
+        self.asks.truncate(max_levels);
+    }
+}
+
+/// Result of walking the book with [`OrderBook::price_impact`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PriceImpact {
+    /// Volume-weighted average fill price
+    pub avg_price: f64,
+    /// Price of the last level touched
+    pub worst_price: f64,
+    /// Quantity the visible book could fill
+    pub filled_qty: f64,
+    /// Quantity left unfilled (0.0 on a full fill)
+    pub remaining_qty: f64,
+}
+
+/// Level changes between two order books (see [`OrderBook::diff`])
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderBookDelta {
+    pub bids: SideDelta,
+    pub asks: SideDelta,
+}
+
+impl OrderBookDelta {
+    /// Whether no level changed
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+}
+
+/// Level changes on one side of the book
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SideDelta {
+    /// Levels at prices the previous book did not have
+    pub added: Vec<PriceLevel>,
+    /// Levels of the previous book whose price is gone
+    pub removed: Vec<PriceLevel>,
+    /// Levels whose quantity changed, with the new quantity
+    pub changed: Vec<PriceLevel>,
+}
+
+impl SideDelta {
+    /// Whether no level changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn diff_levels(current: &[PriceLevel], previous: &[PriceLevel]) -> SideDelta {
+    let mut delta = SideDelta::default();
+
+    for level in current {
+        match previous.iter().find(|l| l.price == level.price) {
+            None => delta.added.push(level.clone()),
+            Some(old) if old.quantity != level.quantity => delta.changed.push(level.clone()),
+            Some(_) => {}
+        }
+    }
+    delta.removed = previous
+        .iter()
+        .filter(|old| !current.iter().any(|l| l.price == old.price))
+        .cloned()
+        .collect();
+
+    delta
+}
+
+fn apply_levels(levels: &mut Vec<PriceLevel>, updates: &[PriceLevel]) {
+    for update in updates {
+        let existing = levels.iter().position(|l| l.price == update.price);
+        match existing {
+            // Binance signals level removal with a zero quantity
+            Some(i) if update.quantity == 0.0 => {
+                levels.remove(i);
+            }
+            Some(i) => levels[i].quantity = update.quantity,
+            None if update.quantity == 0.0 => {}
+            None => levels.push(update.clone()),
+        }
+    }
 }
 
+/// One price level of an order book
+///
+/// Levels order by price, then quantity, using IEEE 754 total ordering
+/// (`f64::total_cmp`), so `Ord` is lawful even with NaN: a positive NaN
+/// sorts above every price. Use [`sort_bids`] / [`sort_asks`] to order a
+/// book side; they keep NaN-priced levels at the back of either side.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceLevel {
     pub price: f64,
     pub quantity: f64,
 }
 
+impl PartialEq for PriceLevel {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for PriceLevel {}
+
+impl PartialOrd for PriceLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PriceLevel {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.price
+            .total_cmp(&other.price)
+            .then_with(|| self.quantity.total_cmp(&other.quantity))
+    }
+}
+
+/// Price usable as an ordered map key, see [`OrderBook::as_maps`]
+///
+/// Compares with IEEE 754 total ordering (`f64::total_cmp`) like
+/// [`PriceLevel`], so `Ord` is lawful even with NaN. Keys match by exact
+/// bit pattern: a price parsed from the same exchange string always finds
+/// its level, but a computed one (`0.1 + 0.2`) may miss by one ulp, and
+/// `-0.0` and `0.0` are different keys. Use `range` for approximate
+/// lookups.
+#[derive(Debug, Clone, Copy)]
+pub struct PriceKey(pub f64);
+
+impl PartialEq for PriceKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for PriceKey {}
+
+impl PartialOrd for PriceKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PriceKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl From<f64> for PriceKey {
+    fn from(price: f64) -> Self {
+        Self(price)
+    }
+}
+
+/// Sort bids best first (highest price first); NaN prices go last
+pub fn sort_bids(levels: &mut [PriceLevel]) {
+    levels.sort_by(|a, b| a.price.is_nan().cmp(&b.price.is_nan()).then_with(|| b.cmp(a)));
+}
+
+/// Sort asks best first (lowest price first); NaN prices go last
+pub fn sort_asks(levels: &mut [PriceLevel]) {
+    levels.sort_by(|a, b| a.price.is_nan().cmp(&b.price.is_nan()).then_with(|| a.cmp(b)));
+}
+
 /// Recent trade
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trade {
@@ -90,6 +712,121 @@ pub struct Trade {
     pub is_buyer_maker: bool,
 }
 
+impl Trade {
+    /// Side of the taker (aggressor) order
+    ///
+    /// When the buyer is the maker, the seller crossed the spread, so the
+    /// trade was a market sell.
+    pub fn aggressor_side(&self) -> TradeSide {
+        if self.is_buyer_maker {
+            TradeSide::Sell
+        } else {
+            TradeSide::Buy
+        }
+    }
+}
+
+/// Trade direction from the aggressor's perspective
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+impl std::fmt::Display for TradeSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            TradeSide::Buy => "BUY",
+            TradeSide::Sell => "SELL",
+        };
+        f.pad(s)
+    }
+}
+
+/// Aggregate trade (fills at the same price and time from one taker order)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggTrade {
+    pub id: i64, // Aggregate trade id
+    pub symbol: String,
+    pub price: f64,
+    pub quantity: f64,
+    pub first_trade_id: i64,
+    pub last_trade_id: i64,
+    pub time: DateTime<Utc>,
+    pub is_buyer_maker: bool,
+}
+
+impl AggTrade {
+    /// Convert to a raw trade, losing the aggregation
+    ///
+    /// The trade takes `first_trade_id` as its id; the aggregate id and the
+    /// rest of the trade id range are dropped. The fills are merged into one
+    /// trade of the total quantity, and `quote_quantity` is recomputed as
+    /// `price * quantity`.
+    pub fn to_trade(&self) -> Trade {
+        Trade {
+            id: self.first_trade_id,
+            symbol: self.symbol.clone(),
+            price: self.price,
+            quantity: self.quantity,
+            quote_quantity: self.price * self.quantity,
+            time: self.time,
+            is_buyer_maker: self.is_buyer_maker,
+        }
+    }
+}
+
+/// A raw trade is an aggregate of itself
+///
+/// `first_trade_id` and `last_trade_id` are the trade id. Binance numbers
+/// aggregate trades separately from raw trades, so `id` (also the trade id)
+/// does not match any aggregate id the server would report.
+impl From<Trade> for AggTrade {
+    fn from(trade: Trade) -> Self {
+        AggTrade {
+            id: trade.id,
+            symbol: trade.symbol,
+            price: trade.price,
+            quantity: trade.quantity,
+            first_trade_id: trade.id,
+            last_trade_id: trade.id,
+            time: trade.time,
+            is_buyer_maker: trade.is_buyer_maker,
+        }
+    }
+}
+
+/// Own trade (fill) from the account trade list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountTrade {
+    pub id: i64,
+    pub symbol: String,
+    pub order_id: i64,
+    pub price: f64,
+    pub quantity: f64,
+    pub quote_quantity: f64,
+    pub commission: f64,
+    pub commission_asset: String,
+    pub time: DateTime<Utc>,
+    pub is_buyer: bool,
+    pub is_maker: bool,
+}
+
+impl AccountTrade {
+    /// Base quantity actually received or given up, net of commission
+    ///
+    /// Commission is only subtracted when it was charged in `base_asset`
+    /// (e.g. "BTC" for BTCUSDT); otherwise the gross quantity is returned.
+    pub fn net_quantity(&self, base_asset: &str) -> f64 {
+        if self.commission_asset == base_asset {
+            self.quantity - self.commission
+        } else {
+            self.quantity
+        }
+    }
+}
+
 /// Symbol information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Symbol {
@@ -100,55 +837,678 @@ pub struct Symbol {
     pub base_asset_precision: i32,
     pub quote_asset_precision: i32,
     pub order_types: Vec<String>,
+    #[serde(default)]
+    pub filters: Vec<SymbolFilter>,
+    /// Markets the symbol trades on (`SPOT`, `MARGIN`, `LEVERAGED`, ...)
+    #[serde(default)]
+    pub permissions: Vec<String>,
 }
 
-/// Candlestick interval
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-pub enum Interval {
-    #[serde(rename = "1s")]
-    Seconds1,
-    #[serde(rename = "1m")]
-    Minutes1,
-    #[serde(rename = "3m")]
-    Minutes3,
-    #[serde(rename = "5m")]
-    Minutes5,
-    #[serde(rename = "15m")]
-    Minutes15,
-    #[serde(rename = "30m")]
-    Minutes30,
-    #[serde(rename = "1h")]
-    Hours1,
-    #[serde(rename = "2h")]
-    Hours2,
-    #[serde(rename = "4h")]
-    Hours4,
-    #[serde(rename = "6h")]
-    Hours6,
-    #[serde(rename = "8h")]
-    Hours8,
-    #[serde(rename = "12h")]
-    Hours12,
-    #[serde(rename = "1d")]
-    Days1,
-    #[serde(rename = "3d")]
-    Days3,
-    #[serde(rename = "1w")]
-    Weeks1,
-    #[serde(rename = "1M")]
-    Months1,
-}
+impl Symbol {
+    /// Whether the symbol is currently open for trading
+    pub fn is_trading(&self) -> bool {
+        self.status == "TRADING"
+    }
 
-impl Interval {
-    /// Get duration in milliseconds
-    pub fn duration_ms(&self) -> i64 {
-        match self {
-            Interval::Seconds1 => 1_000,
-            Interval::Minutes1 => 60_000,
-            Interval::Minutes3 => 180_000,
-            Interval::Minutes5 => 300_000,
-            Interval::Minutes15 => 900_000,
-            Interval::Minutes30 => 1_800_000,
+    /// Parsed trading status
+    pub fn trading_status(&self) -> SymbolStatus {
+        SymbolStatus::from(self.status.as_str())
+    }
+
+    /// Whether the symbol can be traded on the spot market
+    pub fn allows_spot(&self) -> bool {
+        self.permissions.iter().any(|p| p == "SPOT")
+    }
+
+    /// Maximum number of open orders allowed on this symbol (`MAX_NUM_ORDERS`)
+    pub fn max_open_orders(&self) -> Option<u32> {
+        self.filters.iter().find_map(|f| match f {
+            SymbolFilter::MaxNumOrders { max_num_orders } => Some(*max_num_orders),
+            _ => None,
+        })
+    }
+
+    /// Maximum number of open algo (stop/take-profit) orders allowed on this
+    /// symbol (`MAX_NUM_ALGO_ORDERS`)
+    pub fn max_open_algo_orders(&self) -> Option<u32> {
+        self.filters.iter().find_map(|f| match f {
+            SymbolFilter::MaxNumAlgoOrders { max_num_algo_orders } => Some(*max_num_algo_orders),
+            _ => None,
+        })
+    }
+
+    /// Quote balance needed to place an order, as a pre-flight check
+    ///
+    /// The quantity is rounded down to the `LOT_SIZE` step and the price up
+    /// to the `PRICE_FILTER` tick, as the exchange would require. A buy
+    /// needs the notional plus commission at the larger of the maker and
+    /// taker rates, so the estimate is an upper bound whichever side of the
+    /// book the order ends up on. A sell spends the base asset and needs no
+    /// quote balance.
+    ///
+    /// # Example
+    /// ```
+    /// use binance_connector::models::{CommissionRates, OrderSide};
+    /// # use binance_connector::Symbol;
+    /// # fn check(btcusdt: &Symbol, usdt_free: f64) {
+    /// let needed = btcusdt.estimate_cost(OrderSide::Buy, 0.01, 43000.0, CommissionRates::default());
+    /// if needed > usdt_free {
+    ///     println!("short by {:.2} USDT", needed - usdt_free);
+    /// }
+    /// # }
+    /// ```
+    pub fn estimate_cost(
+        &self,
+        side: OrderSide,
+        quantity: f64,
+        price: f64,
+        rates: CommissionRates,
+    ) -> f64 {
+        if side == OrderSide::Sell {
+            return 0.0;
+        }
+
+        let quantity = self
+            .step_size()
+            .map_or(quantity, |step| round_to_step(quantity, step, f64::floor));
+        let price = self
+            .tick_size()
+            .map_or(price, |tick| round_to_step(price, tick, f64::ceil));
+
+        quantity * price * (1.0 + rates.maker.max(rates.taker))
+    }
+
+    /// Price as an order parameter: rounded to the nearest `PRICE_FILTER`
+    /// tick and printed with no more decimals than the tick has
+    ///
+    /// Trailing zeros are trimmed, so float noise such as `1.10000000000001`
+    /// never reaches the exchange (error -1111). Without a price filter the
+    /// quote asset precision is used.
+    pub fn format_price_str(&self, price: f64) -> String {
+        match self.tick_size().filter(|tick| *tick > 0.0) {
+            Some(tick) => format_trimmed(round_to_step(price, tick, f64::round), step_decimals(tick)),
+            None => format_trimmed(price, self.quote_asset_precision.max(0) as usize),
+        }
+    }
+
+    /// Quantity as an order parameter: rounded down to the `LOT_SIZE` step
+    /// and printed with no more decimals than the step has
+    ///
+    /// Rounds down so the order never exceeds the intended size. Trailing
+    /// zeros are trimmed. Without a lot size filter the base asset precision
+    /// is used.
+    pub fn format_qty_str(&self, quantity: f64) -> String {
+        match self.step_size().filter(|step| *step > 0.0) {
+            Some(step) => {
+                format_trimmed(round_to_step(quantity, step, f64::floor), step_decimals(step))
+            }
+            None => format_trimmed(quantity, self.base_asset_precision.max(0) as usize),
+        }
+    }
+
+    /// `PRICE_FILTER` tick size
+    fn tick_size(&self) -> Option<f64> {
+        self.filters.iter().find_map(|f| match f {
+            SymbolFilter::PriceFilter { tick_size, .. } => Some(*tick_size),
+            _ => None,
+        })
+    }
+
+    /// `LOT_SIZE` step size
+    fn step_size(&self) -> Option<f64> {
+        self.filters.iter().find_map(|f| match f {
+            SymbolFilter::LotSize { step_size, .. } => Some(*step_size),
+            _ => None,
+        })
+    }
+}
+
+/// Decimal places of a tick or step size (e.g. 2 for 0.01, 0 for 10)
+fn step_decimals(step: f64) -> usize {
+    (0..16)
+        .find(|&decimals| {
+            let scaled = step * 10f64.powi(decimals as i32);
+            (scaled - scaled.round()).abs() < 1e-9 * scaled.max(1.0)
+        })
+        .unwrap_or(16)
+}
+
+/// Format with at most `decimals` places, trimming trailing zeros
+fn format_trimmed(value: f64, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, value);
+    let trimmed = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &formatted
+    };
+    match trimmed {
+        "-0" => "0".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Round `value` to a multiple of `step` with `round` (unchanged if `step` is 0)
+fn round_to_step(value: f64, step: f64, round: fn(f64) -> f64) -> f64 {
+    if step <= 0.0 {
+        return value;
+    }
+    // Absorb float noise so exact multiples are kept, e.g. 0.3 / 0.1
+    let steps = value / step;
+    let nearest = steps.round();
+    if (steps - nearest).abs() < 1e-9 {
+        nearest * step
+    } else {
+        round(steps) * step
+    }
+}
+
+/// Commission rates as fractions of the notional (0.001 = 0.1%)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CommissionRates {
+    pub maker: f64,
+    pub taker: f64,
+}
+
+impl Default for CommissionRates {
+    /// Binance's standard spot rate of 0.1% for both makers and takers
+    fn default() -> Self {
+        Self {
+            maker: 0.001,
+            taker: 0.001,
+        }
+    }
+}
+
+/// Trading status of a symbol in exchange info
+///
+/// Statuses this crate does not know about are kept verbatim in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SymbolStatus {
+    PreTrading,
+    Trading,
+    PostTrading,
+    EndOfDay,
+    Halt,
+    AuctionMatch,
+    Break,
+    Other(String),
+}
+
+impl SymbolStatus {
+    /// Status as sent by Binance (e.g. `TRADING`)
+    pub fn as_str(&self) -> &str {
+        match self {
+            SymbolStatus::PreTrading => "PRE_TRADING",
+            SymbolStatus::Trading => "TRADING",
+            SymbolStatus::PostTrading => "POST_TRADING",
+            SymbolStatus::EndOfDay => "END_OF_DAY",
+            SymbolStatus::Halt => "HALT",
+            SymbolStatus::AuctionMatch => "AUCTION_MATCH",
+            SymbolStatus::Break => "BREAK",
+            SymbolStatus::Other(s) => s,
+        }
+    }
+
+    /// Whether orders can currently be placed
+    pub fn is_trading(&self) -> bool {
+        *self == SymbolStatus::Trading
+    }
+}
+
+impl From<&str> for SymbolStatus {
+    fn from(s: &str) -> Self {
+        match s {
+            "PRE_TRADING" => SymbolStatus::PreTrading,
+            "TRADING" => SymbolStatus::Trading,
+            "POST_TRADING" => SymbolStatus::PostTrading,
+            "END_OF_DAY" => SymbolStatus::EndOfDay,
+            "HALT" => SymbolStatus::Halt,
+            "AUCTION_MATCH" => SymbolStatus::AuctionMatch,
+            "BREAK" => SymbolStatus::Break,
+            other => SymbolStatus::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for SymbolStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl Serialize for SymbolStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for SymbolStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(SymbolStatus::from(s.as_str()))
+    }
+}
+
+/// Symbols whose trading status changed between two status maps
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChanges {
+    /// Symbols now trading that were missing or not trading before
+    pub listed: Vec<String>,
+    /// Symbols that were trading and now are not, or were removed
+    pub halted: Vec<String>,
+}
+
+impl StatusChanges {
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.listed.is_empty() && self.halted.is_empty()
+    }
+}
+
+/// Diff two symbol status maps (see `BinanceClient::symbol_status_map`)
+///
+/// Only transitions into and out of [`SymbolStatus::Trading`] are reported;
+/// e.g. `BREAK` -> `HALT` is not a change. Both lists are sorted.
+pub fn status_changes(
+    old: &HashMap<String, SymbolStatus>,
+    new: &HashMap<String, SymbolStatus>,
+) -> StatusChanges {
+    let was_trading = |symbol: &str| old.get(symbol).is_some_and(SymbolStatus::is_trading);
+    let is_trading = |symbol: &str| new.get(symbol).is_some_and(SymbolStatus::is_trading);
+
+    let mut listed: Vec<String> = new
+        .keys()
+        .filter(|s| is_trading(s) && !was_trading(s))
+        .cloned()
+        .collect();
+    let mut halted: Vec<String> = old
+        .keys()
+        .filter(|s| was_trading(s) && !is_trading(s))
+        .cloned()
+        .collect();
+    listed.sort();
+    halted.sort();
+
+    StatusChanges { listed, halted }
+}
+
+/// Trading rule attached to a symbol in exchange info
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SymbolFilter {
+    /// `PRICE_FILTER`: price bounds and tick size
+    PriceFilter { min_price: f64, max_price: f64, tick_size: f64 },
+    /// `LOT_SIZE`: quantity bounds and step size
+    LotSize { min_qty: f64, max_qty: f64, step_size: f64 },
+    /// `MARKET_LOT_SIZE`: quantity bounds and step size for market orders
+    MarketLotSize { min_qty: f64, max_qty: f64, step_size: f64 },
+    /// `MIN_NOTIONAL`: minimum price × quantity
+    MinNotional { min_notional: f64 },
+    /// `NOTIONAL`: price × quantity bounds
+    Notional { min_notional: f64, max_notional: f64 },
+    /// `MAX_NUM_ORDERS`: open order cap
+    MaxNumOrders { max_num_orders: u32 },
+    /// `MAX_NUM_ALGO_ORDERS`: open algo order cap
+    MaxNumAlgoOrders { max_num_algo_orders: u32 },
+    /// Any other filter, by its `filterType`
+    Other(String),
+}
+
+/// Format a price for `symbol`; same as [`Symbol::format_price_str`]
+pub fn format_price(price: f64, symbol: &Symbol) -> String {
+    symbol.format_price_str(price)
+}
+
+/// Format a quantity for `symbol`; same as [`Symbol::format_qty_str`]
+pub fn format_qty(quantity: f64, symbol: &Symbol) -> String {
+    symbol.format_qty_str(quantity)
+}
+
+/// Order side
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+impl std::fmt::Display for OrderSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            OrderSide::Buy => "BUY",
+            OrderSide::Sell => "SELL",
+        };
+        f.pad(s)
+    }
+}
+
+/// Order status
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    PendingCancel,
+    Rejected,
+    Expired,
+    ExpiredInMatch,
+}
+
+/// Order details returned by order placement and query endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderResponse {
+    pub symbol: String,
+    pub order_id: i64,
+    pub order_list_id: i64, // -1 unless the order belongs to an order list (e.g. OCO)
+    pub client_order_id: String,
+    pub price: f64,
+    pub orig_qty: f64,
+    pub executed_qty: f64,
+    pub cumulative_quote_qty: f64,
+    pub status: OrderStatus,
+    pub time_in_force: String,
+    pub order_type: String,
+    pub side: OrderSide,
+    pub stop_price: Option<f64>,
+    pub time: Option<DateTime<Utc>>, // Creation time (queries) or transaction time (placement)
+    pub update_time: Option<DateTime<Utc>>,
+}
+
+/// Parameters of an OCO order for [`BinanceClient::place_oco_order`](crate::BinanceClient::place_oco_order)
+///
+/// Combines a limit leg at `price` with a stop-limit leg triggered at
+/// `stop_price`. The stop leg defaults to GTC and the response to `Full`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OcoOrderRequest {
+    /// Order side of both legs
+    pub side: OrderSide,
+    /// Base asset quantity for both legs
+    pub quantity: f64,
+    /// Limit leg price
+    pub price: f64,
+    /// Stop leg trigger price
+    pub stop_price: f64,
+    /// Stop leg limit price
+    pub stop_limit_price: f64,
+    /// Stop leg time in force (`None` for GTC)
+    pub stop_limit_time_in_force: Option<TimeInForce>,
+    /// Response detail level (`None` for FULL)
+    pub resp_type: Option<NewOrderRespType>,
+}
+
+impl OcoOrderRequest {
+    /// OCO order with a GTC stop leg and a `Full` response
+    pub fn new(
+        side: OrderSide,
+        quantity: f64,
+        price: f64,
+        stop_price: f64,
+        stop_limit_price: f64,
+    ) -> Self {
+        Self {
+            side,
+            quantity,
+            price,
+            stop_price,
+            stop_limit_price,
+            stop_limit_time_in_force: None,
+            resp_type: None,
+        }
+    }
+
+    /// Set the stop leg time in force
+    pub fn stop_limit_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.stop_limit_time_in_force = Some(time_in_force);
+        self
+    }
+
+    /// Set the response detail level
+    pub fn resp_type(mut self, resp_type: NewOrderRespType) -> Self {
+        self.resp_type = Some(resp_type);
+        self
+    }
+}
+
+/// OCO (one-cancels-the-other) order list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcoOrderResponse {
+    pub order_list_id: i64,
+    pub contingency_type: String,
+    pub list_status_type: String,
+    pub list_order_status: String,
+    pub list_client_order_id: String,
+    pub transaction_time: DateTime<Utc>,
+    pub symbol: String,
+    pub orders: Vec<OrderResponse>,
+}
+
+/// How cancel-replace behaves when the cancel fails
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CancelReplaceMode {
+    /// Don't place the new order if the cancel fails
+    StopOnFailure,
+    /// Place the new order even if the cancel fails
+    AllowFailure,
+}
+
+impl std::fmt::Display for CancelReplaceMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            CancelReplaceMode::StopOnFailure => "STOP_ON_FAILURE",
+            CancelReplaceMode::AllowFailure => "ALLOW_FAILURE",
+        };
+        f.pad(s)
+    }
+}
+
+/// Outcome of one half of a cancel-replace
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CancelReplaceStatus {
+    Success,
+    Failure,
+    NotAttempted,
+}
+
+/// Error Binance reported for a single order operation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrderRejection {
+    pub code: i32,
+    pub msg: String,
+}
+
+/// Result of a cancel-replace, including partial failures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelReplaceResponse {
+    pub cancel_result: CancelReplaceStatus,
+    pub new_order_result: CancelReplaceStatus,
+    pub cancel_order: Option<OrderResponse>,     // Canceled order, on success
+    pub cancel_error: Option<OrderRejection>,    // Why the cancel failed
+    pub new_order: Option<OrderResponse>,        // Placed order, on success
+    pub new_order_error: Option<OrderRejection>, // Why the new order failed
+}
+
+impl CancelReplaceResponse {
+    /// Check if both the cancel and the new order succeeded
+    pub fn is_success(&self) -> bool {
+        self.cancel_result == CancelReplaceStatus::Success
+            && self.new_order_result == CancelReplaceStatus::Success
+    }
+}
+
+/// Order type accepted by [`BinanceClient::place_order`](crate::BinanceClient::place_order)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderType {
+    /// Limit order resting until filled or canceled (GTC)
+    Limit,
+    /// Fill immediately at the best available prices
+    Market,
+    /// Limit order rejected if it would immediately match (post-only)
+    LimitMaker,
+}
+
+impl std::fmt::Display for OrderType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            OrderType::Limit => "LIMIT",
+            OrderType::Market => "MARKET",
+            OrderType::LimitMaker => "LIMIT_MAKER",
+        };
+        f.pad(s)
+    }
+}
+
+/// Detail level of an order placement response
+///
+/// `Ack` returns only the order ids and is the fastest; `Result` adds the
+/// order state; `Full` also lists the fills.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NewOrderRespType {
+    Ack,
+    Result,
+    Full,
+}
+
+impl std::fmt::Display for NewOrderRespType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            NewOrderRespType::Ack => "ACK",
+            NewOrderRespType::Result => "RESULT",
+            NewOrderRespType::Full => "FULL",
+        };
+        f.pad(s)
+    }
+}
+
+/// How long an order stays on the book
+///
+/// `Gtc` rests until cancelled; `Ioc` fills what it can immediately and
+/// cancels the rest; `Fok` fills entirely or not at all.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TimeInForce {
+    Gtc,
+    Ioc,
+    Fok,
+}
+
+impl std::fmt::Display for TimeInForce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            TimeInForce::Gtc => "GTC",
+            TimeInForce::Ioc => "IOC",
+            TimeInForce::Fok => "FOK",
+        };
+        f.pad(s)
+    }
+}
+
+/// One fill of a newly placed order (`Full` responses only)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrderFill {
+    pub trade_id: i64,
+    pub price: f64,
+    pub quantity: f64,
+    pub commission: f64,
+    pub commission_asset: String,
+}
+
+/// Response to placing an order, at the requested [`NewOrderRespType`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewOrderResponse {
+    pub symbol: String,
+    pub order_id: i64,
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub transact_time: DateTime<Utc>,
+    pub order: Option<OrderResponse>, // Order state; None for `Ack` responses
+    pub fills: Vec<OrderFill>,        // Empty unless `Full`
+}
+
+/// Result of [`BinanceClient::health_report`](crate::BinanceClient::health_report)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub reachable: bool,
+    pub latency: std::time::Duration, // Ping round trip
+    pub server_time: DateTime<Utc>,
+    pub clock_skew_ms: i64,           // Server clock minus local clock
+    pub used_weight: Option<u32>,     // Last X-MBX-USED-WEIGHT-1M seen, if any
+}
+
+/// Candlestick interval
+///
+/// Serializes to the Binance interval code (e.g. `"1m"`). Deserialization
+/// accepts either the code or the interval duration in milliseconds.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub enum Interval {
+    #[serde(rename = "1s")]
+    Seconds1,
+    #[serde(rename = "1m")]
+    Minutes1,
+    #[serde(rename = "3m")]
+    Minutes3,
+    #[serde(rename = "5m")]
+    Minutes5,
+    #[serde(rename = "15m")]
+    Minutes15,
+    #[serde(rename = "30m")]
+    Minutes30,
+    #[serde(rename = "1h")]
+    Hours1,
+    #[serde(rename = "2h")]
+    Hours2,
+    #[serde(rename = "4h")]
+    Hours4,
+    #[serde(rename = "6h")]
+    Hours6,
+    #[serde(rename = "8h")]
+    Hours8,
+    #[serde(rename = "12h")]
+    Hours12,
+    #[serde(rename = "1d")]
+    Days1,
+    #[serde(rename = "3d")]
+    Days3,
+    #[serde(rename = "1w")]
+    Weeks1,
+    #[serde(rename = "1M")]
+    Months1,
+}
+
+impl Interval {
+    /// Every interval, shortest first
+    pub const ALL: [Interval; 16] = [
+        Interval::Seconds1,
+        Interval::Minutes1,
+        Interval::Minutes3,
+        Interval::Minutes5,
+        Interval::Minutes15,
+        Interval::Minutes30,
+        Interval::Hours1,
+        Interval::Hours2,
+        Interval::Hours4,
+        Interval::Hours6,
+        Interval::Hours8,
+        Interval::Hours12,
+        Interval::Days1,
+        Interval::Days3,
+        Interval::Weeks1,
+        Interval::Months1,
+    ];
+
+    /// Get duration in milliseconds
+    pub fn duration_ms(&self) -> i64 {
+        match self {
+            Interval::Seconds1 => 1_000,
+            Interval::Minutes1 => 60_000,
+            Interval::Minutes3 => 180_000,
+            Interval::Minutes5 => 300_000,
+            Interval::Minutes15 => 900_000,
+            Interval::Minutes30 => 1_800_000,
             Interval::Hours1 => 3_600_000,
             Interval::Hours2 => 7_200_000,
             Interval::Hours4 => 14_400_000,
@@ -161,6 +1521,142 @@ impl Interval {
             Interval::Months1 => 2_592_000_000,
         }
     }
+
+    /// Wall time covered by `count` candles, in milliseconds
+    ///
+    /// Saturates at `i64::MAX` instead of overflowing. `Months1` counts as
+    /// 30 days.
+    pub fn span_ms(&self, count: usize) -> i64 {
+        i64::try_from(count)
+            .unwrap_or(i64::MAX)
+            .saturating_mul(self.duration_ms())
+    }
+
+    /// Number of candles needed to cover `start..end` (milliseconds)
+    ///
+    /// A partial candle at the end counts as one. Returns 0 if `end` is not
+    /// after `start`. This is a span estimate that ignores candle
+    /// boundaries; use [`candles_opening_within`](Self::candles_opening_within)
+    /// to count the candles Binance actually returns for a range.
+    pub fn candles_in_range(&self, start: i64, end: i64) -> usize {
+        if end <= start {
+            return 0;
+        }
+        let span = (end as i128) - (start as i128);
+        let count = (span + self.duration_ms() as i128 - 1) / self.duration_ms() as i128;
+        usize::try_from(count).unwrap_or(usize::MAX)
+    }
+
+    /// Number of candles whose open time falls within `start..=end`
+    /// (milliseconds)
+    ///
+    /// Counts the candles a klines request with `startTime=start` and
+    /// `endTime=end` covers: both ends are inclusive and candles open on
+    /// [`period_start`](Self::period_start) boundaries, so a `start` inside
+    /// a candle skips that candle. Returns 0 if `end` is before `start`.
+    pub fn candles_opening_within(&self, start: i64, end: i64) -> u64 {
+        if end < start {
+            return 0;
+        }
+        let (Some(first), Some(last)) = (self.period_index(start), self.period_index(end)) else {
+            return 0;
+        };
+        let first = if self.period_start(start) == start { first } else { first + 1 };
+        u64::try_from(last - first + 1).unwrap_or(0)
+    }
+
+    /// Open time of the candle containing `ms` (milliseconds)
+    ///
+    /// Weekly candles open on Monday 00:00 UTC and monthly candles on the
+    /// 1st of the calendar month; other intervals are aligned to the Unix
+    /// epoch.
+    pub fn period_start(&self, ms: i64) -> i64 {
+        match self {
+            Interval::Months1 => DateTime::from_timestamp_millis(ms)
+                .and_then(|t| t.date_naive().with_day(1))
+                .map_or(ms, |first| {
+                    first.and_time(chrono::NaiveTime::MIN).and_utc().timestamp_millis()
+                }),
+            _ => ms - (ms - self.alignment_ms()).rem_euclid(self.duration_ms()),
+        }
+    }
+
+    /// Sequence number of the candle containing `ms`, counted from the epoch
+    fn period_index(&self, ms: i64) -> Option<i64> {
+        match self {
+            Interval::Months1 => DateTime::from_timestamp_millis(ms)
+                .map(|t| i64::from(t.year()) * 12 + i64::from(t.month0())),
+            _ => Some((ms - self.alignment_ms()).div_euclid(self.duration_ms())),
+        }
+    }
+
+    /// Epoch-relative time of a candle boundary; Monday 1970-01-05 for weeks,
+    /// since the epoch fell on a Thursday
+    fn alignment_ms(&self) -> i64 {
+        match self {
+            Interval::Weeks1 => 4 * 86_400_000,
+            _ => 0,
+        }
+    }
+}
+
+impl TryFrom<i64> for Interval {
+    type Error = crate::error::Error;
+
+    /// Reconstruct an interval from its duration in milliseconds
+    fn try_from(ms: i64) -> Result<Self, Self::Error> {
+        match ms {
+            1_000 => Ok(Interval::Seconds1),
+            60_000 => Ok(Interval::Minutes1),
+            180_000 => Ok(Interval::Minutes3),
+            300_000 => Ok(Interval::Minutes5),
+            900_000 => Ok(Interval::Minutes15),
+            1_800_000 => Ok(Interval::Minutes30),
+            3_600_000 => Ok(Interval::Hours1),
+            7_200_000 => Ok(Interval::Hours2),
+            14_400_000 => Ok(Interval::Hours4),
+            21_600_000 => Ok(Interval::Hours6),
+            28_800_000 => Ok(Interval::Hours8),
+            43_200_000 => Ok(Interval::Hours12),
+            86_400_000 => Ok(Interval::Days1),
+            259_200_000 => Ok(Interval::Days3),
+            604_800_000 => Ok(Interval::Weeks1),
+            2_592_000_000 => Ok(Interval::Months1),
+            _ => Err(crate::Error::InvalidInterval(format!("{}ms", ms))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Interval {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct IntervalVisitor;
+
+        impl serde::de::Visitor<'_> for IntervalVisitor {
+            type Value = Interval;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a Binance interval code (e.g. \"1m\") or a duration in milliseconds")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Interval, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Interval, E> {
+                Interval::try_from(v).map_err(E::custom)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Interval, E> {
+                let ms = i64::try_from(v).map_err(E::custom)?;
+                Interval::try_from(ms).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(IntervalVisitor)
+    }
 }
 
 impl std::fmt::Display for Interval {
@@ -183,7 +1679,7 @@ impl std::fmt::Display for Interval {
             Interval::Weeks1 => "1w",
             Interval::Months1 => "1M",
         };
-        write!(f, "{}", s)
+        f.pad(s)
     }
 }
 
@@ -213,8 +1709,48 @@ impl std::str::FromStr for Interval {
     }
 }
 
-// Internal Binance API response structures
-#[derive(Debug, Deserialize)]
+/// Normalize a user-supplied symbol to Binance's REST form
+///
+/// Drops separators (`/`, `-`, `_`, whitespace) and uppercases, so
+/// `"btc/usdt"`, `"BTC-USDT"` and `"BTCUSDT"` all become `"BTCUSDT"`. Stream
+/// names use the lowercase of the result.
+pub fn normalize_symbol(input: &str) -> String {
+    input
+        .chars()
+        .filter(|c| !matches!(c, '/' | '-' | '_') && !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Parse a decimal string from the API, reporting `field` on failure
+///
+/// Surrounding whitespace is ignored and scientific notation is accepted.
+/// Empty strings, `"null"` and non-finite values are errors.
+pub(crate) fn parse_f64(field: &str, value: &str) -> crate::Result<f64> {
+    let value = value.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("null") {
+        return Err(crate::Error::DeserializationError(format!(
+            "{}: missing numeric value",
+            field
+        )));
+    }
+
+    match value.parse::<f64>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(crate::Error::DeserializationError(format!(
+            "{}: invalid number {:?}",
+            field, value
+        ))),
+    }
+}
+
+/// Parse a decimal string from the API, treating anything unparseable as 0
+pub(crate) fn parse_f64_or_zero(value: &str) -> f64 {
+    parse_f64("", value).unwrap_or(0.0)
+}
+
+// Internal Binance API response structures
+#[derive(Debug, Deserialize)]
 pub(crate) struct BinanceKlineResponse(
     pub i64,    // Open time
     pub String, // Open
@@ -227,11 +1763,12 @@ pub(crate) struct BinanceKlineResponse(
     pub i64,    // Number of trades
     pub String, // Taker buy base asset volume
     pub String, // Taker buy quote asset volume
+    #[allow(dead_code)]
     pub String, // Ignore
 );
 
 impl BinanceKlineResponse {
-    pub(crate) fn to_kline(&self, symbol: String) -> crate::Result<Kline> {
+    pub(crate) fn to_kline(&self, symbol: String, interval: Interval) -> crate::Result<Kline> {
         Ok(Kline {
             symbol,
             open_time: DateTime::from_timestamp_millis(self.0).ok_or_else(|| {
@@ -240,16 +1777,39 @@ impl BinanceKlineResponse {
             close_time: DateTime::from_timestamp_millis(self.6).ok_or_else(|| {
                 crate::Error::DeserializationError("Invalid close time".to_string())
             })?,
-            open: self.1.parse().unwrap_or(0.0),
-            high: self.2.parse().unwrap_or(0.0),
-            low: self.3.parse().unwrap_or(0.0),
-            close: self.4.parse().unwrap_or(0.0),
-            volume: self.5.parse().unwrap_or(0.0),
-            quote_volume: self.7.parse().unwrap_or(0.0),
+            open: parse_f64("open", &self.1)?,
+            high: parse_f64("high", &self.2)?,
+            low: parse_f64("low", &self.3)?,
+            close: parse_f64("close", &self.4)?,
+            volume: parse_f64("volume", &self.5)?,
+            quote_volume: parse_f64("quoteVolume", &self.7)?,
             trades: self.8,
-            taker_buy_base: self.9.parse().unwrap_or(0.0),
-            taker_buy_quote: self.10.parse().unwrap_or(0.0),
+            taker_buy_base: parse_f64("takerBuyBase", &self.9)?,
+            taker_buy_quote: parse_f64("takerBuyQuote", &self.10)?,
             is_closed: true,
+            interval: Some(interval),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BinanceBookTickerResponse {
+    pub symbol: String,
+    pub bid_price: String,
+    pub bid_qty: String,
+    pub ask_price: String,
+    pub ask_qty: String,
+}
+
+impl BinanceBookTickerResponse {
+    pub(crate) fn to_book_ticker(&self) -> crate::Result<BookTicker> {
+        Ok(BookTicker {
+            symbol: self.symbol.clone(),
+            bid_price: parse_f64("bidPrice", &self.bid_price)?,
+            bid_qty: parse_f64("bidQty", &self.bid_qty)?,
+            ask_price: parse_f64("askPrice", &self.ask_price)?,
+            ask_qty: parse_f64("askQty", &self.ask_qty)?,
         })
     }
 }
@@ -261,12 +1821,13 @@ pub(crate) struct BinanceTickerResponse {
 }
 
 impl BinanceTickerResponse {
-    pub(crate) fn to_ticker(&self) -> Ticker {
-        Ticker {
+    pub(crate) fn to_ticker(&self) -> crate::Result<Ticker> {
+        Ok(Ticker {
             symbol: self.symbol.clone(),
-            price: self.price.parse().unwrap_or(0.0),
-            timestamp: Utc::now(),
-        }
+            price: parse_f64("price", &self.price)?,
+            timestamp: None,
+            received_at: Utc::now(),
+        })
     }
 }
 
@@ -279,8 +1840,12 @@ pub(crate) struct Binance24hTickerResponse {
     pub weighted_avg_price: String,
     pub prev_close_price: String,
     pub last_price: String,
-    pub bid_price: String,
-    pub ask_price: String,
+    /// Null or empty when the book has no bids
+    #[serde(default)]
+    pub bid_price: Option<String>,
+    /// Null or empty when the book has no asks
+    #[serde(default)]
+    pub ask_price: Option<String>,
     pub open_price: String,
     pub high_price: String,
     pub low_price: String,
@@ -297,18 +1862,18 @@ impl Binance24hTickerResponse {
     pub(crate) fn to_ticker24h(&self) -> crate::Result<Ticker24h> {
         Ok(Ticker24h {
             symbol: self.symbol.clone(),
-            price_change: self.price_change.parse().unwrap_or(0.0),
-            price_change_percent: self.price_change_percent.parse().unwrap_or(0.0),
-            weighted_avg_price: self.weighted_avg_price.parse().unwrap_or(0.0),
-            prev_close_price: self.prev_close_price.parse().unwrap_or(0.0),
-            last_price: self.last_price.parse().unwrap_or(0.0),
-            bid_price: self.bid_price.parse().unwrap_or(0.0),
-            ask_price: self.ask_price.parse().unwrap_or(0.0),
-            open_price: self.open_price.parse().unwrap_or(0.0),
-            high_price: self.high_price.parse().unwrap_or(0.0),
-            low_price: self.low_price.parse().unwrap_or(0.0),
-            volume: self.volume.parse().unwrap_or(0.0),
-            quote_volume: self.quote_volume.parse().unwrap_or(0.0),
+            price_change: parse_f64("priceChange", &self.price_change)?,
+            price_change_percent: parse_f64("priceChangePercent", &self.price_change_percent)?,
+            weighted_avg_price: parse_f64("weightedAvgPrice", &self.weighted_avg_price)?,
+            prev_close_price: parse_f64("prevClosePrice", &self.prev_close_price)?,
+            last_price: parse_f64("lastPrice", &self.last_price)?,
+            bid_price: self.bid_price.as_deref().map_or(0.0, parse_f64_or_zero),
+            ask_price: self.ask_price.as_deref().map_or(0.0, parse_f64_or_zero),
+            open_price: parse_f64("openPrice", &self.open_price)?,
+            high_price: parse_f64("highPrice", &self.high_price)?,
+            low_price: parse_f64("lowPrice", &self.low_price)?,
+            volume: parse_f64("volume", &self.volume)?,
+            quote_volume: parse_f64("quoteVolume", &self.quote_volume)?,
             open_time: DateTime::from_timestamp_millis(self.open_time).ok_or_else(|| {
                 crate::Error::DeserializationError("Invalid open time".to_string())
             })?,
@@ -322,6 +1887,296 @@ impl Binance24hTickerResponse {
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub(crate) struct BinanceAggTradeResponse {
+    #[serde(rename = "a")]
+    pub id: i64,
+    #[serde(rename = "p")]
+    pub price: String,
+    #[serde(rename = "q")]
+    pub quantity: String,
+    #[serde(rename = "f")]
+    pub first_trade_id: i64,
+    #[serde(rename = "l")]
+    pub last_trade_id: i64,
+    #[serde(rename = "T")]
+    pub time: i64,
+    #[serde(rename = "m")]
+    pub is_buyer_maker: bool,
+}
+
+impl BinanceAggTradeResponse {
+    pub(crate) fn to_agg_trade(&self, symbol: String) -> crate::Result<AggTrade> {
+        Ok(AggTrade {
+            id: self.id,
+            symbol,
+            price: parse_f64("p", &self.price)?,
+            quantity: parse_f64("q", &self.quantity)?,
+            first_trade_id: self.first_trade_id,
+            last_trade_id: self.last_trade_id,
+            time: DateTime::from_timestamp_millis(self.time).ok_or_else(|| {
+                crate::Error::DeserializationError("Invalid trade time".to_string())
+            })?,
+            is_buyer_maker: self.is_buyer_maker,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BinanceAccountTradeResponse {
+    pub symbol: String,
+    pub id: i64,
+    pub order_id: i64,
+    pub price: String,
+    pub qty: String,
+    pub quote_qty: String,
+    pub commission: String,
+    pub commission_asset: String,
+    pub time: i64,
+    pub is_buyer: bool,
+    pub is_maker: bool,
+}
+
+impl BinanceAccountTradeResponse {
+    pub(crate) fn to_account_trade(&self) -> crate::Result<AccountTrade> {
+        Ok(AccountTrade {
+            id: self.id,
+            symbol: self.symbol.clone(),
+            order_id: self.order_id,
+            price: parse_f64("price", &self.price)?,
+            quantity: parse_f64("qty", &self.qty)?,
+            quote_quantity: parse_f64("quoteQty", &self.quote_qty)?,
+            commission: parse_f64("commission", &self.commission)?,
+            commission_asset: self.commission_asset.clone(),
+            time: DateTime::from_timestamp_millis(self.time).ok_or_else(|| {
+                crate::Error::DeserializationError("Invalid trade time".to_string())
+            })?,
+            is_buyer: self.is_buyer,
+            is_maker: self.is_maker,
+        })
+    }
+}
+
+fn default_order_list_id() -> i64 {
+    -1
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BinanceOrderResponse {
+    pub symbol: String,
+    pub order_id: i64,
+    #[serde(default = "default_order_list_id")]
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub price: String,
+    pub orig_qty: String,
+    pub executed_qty: String,
+    #[serde(rename = "cummulativeQuoteQty")]
+    pub cumulative_quote_qty: String,
+    pub status: OrderStatus,
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub order_type: String,
+    pub side: OrderSide,
+    pub stop_price: Option<String>,
+    pub time: Option<i64>,
+    pub transact_time: Option<i64>,
+    pub update_time: Option<i64>,
+}
+
+impl BinanceOrderResponse {
+    pub(crate) fn to_order(&self) -> crate::Result<OrderResponse> {
+        Ok(OrderResponse {
+            symbol: self.symbol.clone(),
+            order_id: self.order_id,
+            order_list_id: self.order_list_id,
+            client_order_id: self.client_order_id.clone(),
+            price: parse_f64("price", &self.price)?,
+            orig_qty: parse_f64("origQty", &self.orig_qty)?,
+            executed_qty: parse_f64("executedQty", &self.executed_qty)?,
+            cumulative_quote_qty: parse_f64("cummulativeQuoteQty", &self.cumulative_quote_qty)?,
+            status: self.status,
+            time_in_force: self.time_in_force.clone(),
+            order_type: self.order_type.clone(),
+            side: self.side,
+            stop_price: self
+                .stop_price
+                .as_deref()
+                .map(|p| parse_f64("stopPrice", p))
+                .transpose()?,
+            time: self
+                .time
+                .or(self.transact_time)
+                .and_then(DateTime::from_timestamp_millis),
+            update_time: self.update_time.and_then(DateTime::from_timestamp_millis),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BinanceOcoOrderResponse {
+    pub order_list_id: i64,
+    pub contingency_type: String,
+    pub list_status_type: String,
+    pub list_order_status: String,
+    pub list_client_order_id: String,
+    pub transaction_time: i64,
+    pub symbol: String,
+    pub order_reports: Vec<BinanceOrderResponse>,
+}
+
+impl BinanceOcoOrderResponse {
+    pub(crate) fn to_oco_order(&self) -> crate::Result<OcoOrderResponse> {
+        Ok(OcoOrderResponse {
+            order_list_id: self.order_list_id,
+            contingency_type: self.contingency_type.clone(),
+            list_status_type: self.list_status_type.clone(),
+            list_order_status: self.list_order_status.clone(),
+            list_client_order_id: self.list_client_order_id.clone(),
+            transaction_time: DateTime::from_timestamp_millis(self.transaction_time).ok_or_else(
+                || crate::Error::DeserializationError("Invalid transaction time".to_string()),
+            )?,
+            symbol: self.symbol.clone(),
+            orders: self
+                .order_reports
+                .iter()
+                .map(|o| o.to_order())
+                .collect::<crate::Result<_>>()?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BinanceOrderFill {
+    pub trade_id: i64,
+    pub price: String,
+    pub qty: String,
+    pub commission: String,
+    pub commission_asset: String,
+}
+
+/// Fields present in every order placement response, `ACK` included
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BinanceNewOrderAck {
+    pub symbol: String,
+    pub order_id: i64,
+    #[serde(default = "default_order_list_id")]
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub transact_time: i64,
+    #[serde(default)]
+    pub fills: Vec<BinanceOrderFill>,
+}
+
+/// Order placement response; the order report is only present for
+/// `RESULT` and `FULL`
+#[derive(Debug)]
+pub(crate) struct BinanceNewOrderResponse {
+    pub ack: BinanceNewOrderAck,
+    pub order: Option<BinanceOrderResponse>,
+}
+
+impl<'de> Deserialize<'de> for BinanceNewOrderResponse {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let ack = BinanceNewOrderAck::deserialize(&value).map_err(D::Error::custom)?;
+        let order = match value.get("status") {
+            Some(_) => Some(BinanceOrderResponse::deserialize(&value).map_err(D::Error::custom)?),
+            None => None,
+        };
+        Ok(Self { ack, order })
+    }
+}
+
+impl BinanceNewOrderResponse {
+    pub(crate) fn to_new_order(&self) -> crate::Result<NewOrderResponse> {
+        Ok(NewOrderResponse {
+            symbol: self.ack.symbol.clone(),
+            order_id: self.ack.order_id,
+            order_list_id: self.ack.order_list_id,
+            client_order_id: self.ack.client_order_id.clone(),
+            transact_time: DateTime::from_timestamp_millis(self.ack.transact_time).ok_or_else(
+                || crate::Error::DeserializationError("Invalid transaction time".to_string()),
+            )?,
+            order: self.order.as_ref().map(|o| o.to_order()).transpose()?,
+            fills: self
+                .ack
+                .fills
+                .iter()
+                .map(|f| {
+                    Ok(OrderFill {
+                        trade_id: f.trade_id,
+                        price: parse_f64("price", &f.price)?,
+                        quantity: parse_f64("qty", &f.qty)?,
+                        commission: parse_f64("commission", &f.commission)?,
+                        commission_asset: f.commission_asset.clone(),
+                    })
+                })
+                .collect::<crate::Result<_>>()?,
+        })
+    }
+}
+
+/// Order report or per-order error inside a cancel-replace response
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum BinanceOrderOutcome {
+    Order(Box<BinanceOrderResponse>),
+    Rejected { code: i32, msg: String },
+}
+
+impl BinanceOrderOutcome {
+    fn split(
+        outcome: Option<&Self>,
+    ) -> crate::Result<(Option<OrderResponse>, Option<OrderRejection>)> {
+        Ok(match outcome {
+            Some(BinanceOrderOutcome::Order(order)) => (Some(order.to_order()?), None),
+            Some(BinanceOrderOutcome::Rejected { code, msg }) => (
+                None,
+                Some(OrderRejection {
+                    code: *code,
+                    msg: msg.clone(),
+                }),
+            ),
+            None => (None, None),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BinanceCancelReplaceResponse {
+    pub cancel_result: CancelReplaceStatus,
+    pub new_order_result: CancelReplaceStatus,
+    pub cancel_response: Option<BinanceOrderOutcome>,
+    pub new_order_response: Option<BinanceOrderOutcome>,
+}
+
+impl BinanceCancelReplaceResponse {
+    pub(crate) fn to_cancel_replace(&self) -> crate::Result<CancelReplaceResponse> {
+        let (cancel_order, cancel_error) =
+            BinanceOrderOutcome::split(self.cancel_response.as_ref())?;
+        let (new_order, new_order_error) =
+            BinanceOrderOutcome::split(self.new_order_response.as_ref())?;
+
+        Ok(CancelReplaceResponse {
+            cancel_result: self.cancel_result,
+            new_order_result: self.new_order_result,
+            cancel_order,
+            cancel_error,
+            new_order,
+            new_order_error,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct BinanceDepthResponse {
     #[serde(rename = "lastUpdateId")]
@@ -331,28 +2186,143 @@ pub(crate) struct BinanceDepthResponse {
 }
 
 impl BinanceDepthResponse {
-    pub(crate) fn to_order_book(&self, symbol: String) -> OrderBook {
-        OrderBook {
+    /// Convert to an `OrderBook`, sorting both sides best first
+    pub(crate) fn to_order_book(&self, symbol: String) -> crate::Result<OrderBook> {
+        let levels = |raw: &[(String, String)]| -> crate::Result<Vec<PriceLevel>> {
+            raw.iter()
+                .map(|(p, q)| {
+                    Ok(PriceLevel {
+                        price: parse_f64("price", p)?,
+                        quantity: parse_f64("qty", q)?,
+                    })
+                })
+                .collect()
+        };
+        let mut bids = levels(&self.bids)?;
+        let mut asks = levels(&self.asks)?;
+        sort_bids(&mut bids);
+        sort_asks(&mut asks);
+
+        let received_at = Utc::now();
+        Ok(OrderBook {
             symbol,
             last_update_id: self.last_update_id,
-            bids: self
-                .bids
-                .iter()
-                .map(|(p, q)| PriceLevel {
-                    price: p.parse().unwrap_or(0.0),
-                    quantity: q.parse().unwrap_or(0.0),
-                })
-                .collect(),
-            asks: self
-                .asks
+            bids,
+            asks,
+            timestamp: received_at,
+            received_at,
+            requested_limit: None,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BinanceSymbolResponse {
+    pub symbol: String,
+    pub status: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub base_asset_precision: i32,
+    pub quote_asset_precision: i32,
+    #[serde(default)]
+    pub order_types: Vec<String>,
+    #[serde(default)]
+    pub filters: Vec<BinanceSymbolFilter>,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    /// Newer responses list permissions here and leave `permissions` empty
+    #[serde(default)]
+    pub permission_sets: Vec<Vec<String>>,
+}
+
+impl BinanceSymbolResponse {
+    pub fn to_symbol(&self) -> crate::Result<Symbol> {
+        Ok(Symbol {
+            symbol: self.symbol.clone(),
+            status: self.status.clone(),
+            base_asset: self.base_asset.clone(),
+            quote_asset: self.quote_asset.clone(),
+            base_asset_precision: self.base_asset_precision,
+            quote_asset_precision: self.quote_asset_precision,
+            order_types: self.order_types.clone(),
+            filters: self
+                .filters
                 .iter()
-                .map(|(p, q)| PriceLevel {
-                    price: p.parse().unwrap_or(0.0),
-                    quantity: q.parse().unwrap_or(0.0),
-                })
-                .collect(),
-            timestamp: Utc::now(),
+                .map(|f| f.to_filter())
+                .collect::<crate::Result<_>>()?,
+            permissions: self.permissions(),
+        })
+    }
+
+    /// `permissions` merged with every entry of `permissionSets`, deduplicated
+    fn permissions(&self) -> Vec<String> {
+        let mut permissions = self.permissions.clone();
+        for permission in self.permission_sets.iter().flatten() {
+            if !permissions.contains(permission) {
+                permissions.push(permission.clone());
+            }
         }
+        permissions
+    }
+}
+
+/// Union of the filter fields we understand; the rest are ignored
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BinanceSymbolFilter {
+    pub filter_type: String,
+    pub min_price: Option<String>,
+    pub max_price: Option<String>,
+    pub tick_size: Option<String>,
+    pub min_qty: Option<String>,
+    pub max_qty: Option<String>,
+    pub step_size: Option<String>,
+    pub min_notional: Option<String>,
+    pub max_notional: Option<String>,
+    pub max_num_orders: Option<u32>,
+    pub max_num_algo_orders: Option<u32>,
+}
+
+impl BinanceSymbolFilter {
+    /// Convert to a `SymbolFilter`; a missing or malformed number in a known
+    /// filter is an error, since tick and step sizes drive order rounding
+    pub fn to_filter(&self) -> crate::Result<SymbolFilter> {
+        let num = |field: &str, value: &Option<String>| {
+            parse_f64(field, value.as_deref().unwrap_or(""))
+        };
+
+        Ok(match (self.filter_type.as_str(), self.max_num_orders, self.max_num_algo_orders) {
+            ("PRICE_FILTER", _, _) => SymbolFilter::PriceFilter {
+                min_price: num("minPrice", &self.min_price)?,
+                max_price: num("maxPrice", &self.max_price)?,
+                tick_size: num("tickSize", &self.tick_size)?,
+            },
+            ("LOT_SIZE", _, _) => SymbolFilter::LotSize {
+                min_qty: num("minQty", &self.min_qty)?,
+                max_qty: num("maxQty", &self.max_qty)?,
+                step_size: num("stepSize", &self.step_size)?,
+            },
+            ("MARKET_LOT_SIZE", _, _) => SymbolFilter::MarketLotSize {
+                min_qty: num("minQty", &self.min_qty)?,
+                max_qty: num("maxQty", &self.max_qty)?,
+                step_size: num("stepSize", &self.step_size)?,
+            },
+            ("MIN_NOTIONAL", _, _) => SymbolFilter::MinNotional {
+                min_notional: num("minNotional", &self.min_notional)?,
+            },
+            ("NOTIONAL", _, _) => SymbolFilter::Notional {
+                min_notional: num("minNotional", &self.min_notional)?,
+                max_notional: num("maxNotional", &self.max_notional)?,
+            },
+            ("MAX_NUM_ORDERS", Some(max_num_orders), _) => {
+                SymbolFilter::MaxNumOrders { max_num_orders }
+            }
+            ("MAX_NUM_ALGO_ORDERS", _, Some(max_num_algo_orders)) => {
+                SymbolFilter::MaxNumAlgoOrders { max_num_algo_orders }
+            }
+            (other, _, _) => SymbolFilter::Other(other.to_string()),
+        })
     }
 }
 
@@ -373,6 +2343,691 @@ mod tests {
         assert_eq!(Interval::Hours1.duration_ms(), 3_600_000);
     }
 
+    #[test]
+    fn test_interval_span_and_candle_count() {
+        // 300 one-minute candles cover five hours
+        assert_eq!(Interval::Minutes1.span_ms(300), 5 * 3_600_000);
+        assert_eq!(Interval::Months1.span_ms(usize::MAX), i64::MAX);
+
+        let start = 1_640_000_000_000;
+        let week = 7 * 86_400_000;
+        assert_eq!(Interval::Hours1.candles_in_range(start, start + week), 168);
+        assert_eq!(Interval::Hours1.candles_in_range(start, start + week + 1), 169);
+        assert_eq!(Interval::Hours1.candles_in_range(start, start), 0);
+        // Full i64 range does not overflow
+        assert!(Interval::Seconds1.candles_in_range(i64::MIN, i64::MAX) > 0);
+    }
+
+    #[test]
+    fn test_kline_csv_round_trip() {
+        let kline = Kline {
+            open: 43_250.12345678,
+            close: 0.1 + 0.2,
+            is_closed: false,
+            ..kline_at(1_640_000_000_000, Interval::Minutes15)
+        };
+
+        let row = kline.to_csv_row();
+        assert!(row.starts_with("BTCUSDT,15m,2021-12-20T11:33:20.000Z,"));
+        assert_eq!(row.split(',').count(), Kline::CSV_HEADER.split(',').count());
+        assert_eq!(Kline::from_csv_row(&format!("{}\n", row)).unwrap(), kline);
+
+        let untagged = Kline { interval: None, ..kline };
+        assert_eq!(Kline::from_csv_row(&untagged.to_csv_row()).unwrap(), untagged);
+    }
+
+    #[test]
+    fn test_kline_csv_malformed() {
+        let row = kline_at(1_640_000_000_000, Interval::Hours1).to_csv_row();
+
+        let malformed = [
+            row.replace(",1h,", ",7x,"),
+            row.replace("2021-12-20T11:33:20.000Z", "1640000000000"),
+            row.replace(",true", ",yes"),
+            row.rsplit_once(',').unwrap().0.to_string(),
+            String::new(),
+        ];
+        for line in &malformed {
+            assert!(matches!(
+                Kline::from_csv_row(line),
+                Err(crate::Error::DeserializationError(_))
+            ));
+        }
+    }
+
+    fn kline_at(open_ms: i64, interval: Interval) -> Kline {
+        Kline {
+            symbol: "BTCUSDT".to_string(),
+            open_time: DateTime::from_timestamp_millis(open_ms).unwrap(),
+            close_time: DateTime::from_timestamp_millis(open_ms + interval.duration_ms() - 1)
+                .unwrap(),
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 1.0,
+            quote_volume: 1.0,
+            trades: 1,
+            taker_buy_base: 0.5,
+            taker_buy_quote: 0.5,
+            is_closed: true,
+            interval: Some(interval),
+        }
+    }
+
+    #[test]
+    fn test_find_gaps() {
+        let start = 1_640_995_200_000; // 2022-01-01
+        let minute = Interval::Minutes1.duration_ms();
+
+        // Candles 3 and 4 are missing
+        let klines: Vec<Kline> = [0, 1, 2, 5, 6]
+            .iter()
+            .map(|i| kline_at(start + i * minute, Interval::Minutes1))
+            .collect();
+        let gaps = find_gaps(&klines, Interval::Minutes1);
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].0.timestamp_millis(), start + 3 * minute);
+        assert_eq!(gaps[0].1.timestamp_millis(), start + 5 * minute);
+
+        assert!(find_gaps(&klines[..3], Interval::Minutes1).is_empty());
+        assert!(find_gaps(&[], Interval::Minutes1).is_empty());
+
+        // Months follow the calendar: Jan 1 -> Feb 1 is 31 days, no gap
+        let feb = 1_643_673_600_000;
+        let months = [kline_at(start, Interval::Months1), kline_at(feb, Interval::Months1)];
+        assert!(find_gaps(&months, Interval::Months1).is_empty());
+    }
+
+    #[test]
+    fn test_assert_continuous() {
+        let start = 1_640_995_200_000; // 2022-01-01
+        let minute = Interval::Minutes1.duration_ms();
+        let mut klines: Vec<Kline> = (0..4)
+            .map(|i| {
+                let mut kline = kline_at(start + i * minute, Interval::Minutes1);
+                kline.open = 43000.0 + i as f64;
+                kline.close = 43000.5 + i as f64;
+                kline
+            })
+            .collect();
+        assert!(assert_continuous(&klines, 1.0).is_ok());
+        assert!(assert_continuous(&klines, 0.1).is_err());
+        assert!(assert_continuous(&[], 0.0).is_ok());
+
+        klines[2].open = 45000.0;
+        match assert_continuous(&klines, 1.0) {
+            Err(crate::Error::DeserializationError(msg)) => {
+                assert!(msg.contains("45000"), "{}", msg);
+                assert!(msg.contains("43001.5"), "{}", msg);
+            }
+            other => panic!("expected a discontinuity, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_interval_candles_opening_within() {
+        let jan_1_2022 = 1_640_995_200_000; // a Saturday
+        let day = 86_400_000;
+        let hour = Interval::Hours1;
+
+        // A start mid-candle skips that candle; both ends are inclusive
+        assert_eq!(hour.candles_opening_within(jan_1_2022 + 1, jan_1_2022 + 3_600_000), 1);
+        assert_eq!(hour.candles_opening_within(jan_1_2022, jan_1_2022 + 3_599_999), 1);
+        assert_eq!(hour.candles_opening_within(jan_1_2022, jan_1_2022 + 3_600_000), 2);
+        assert_eq!(hour.candles_opening_within(jan_1_2022 + 1, jan_1_2022 + 2), 0);
+        assert_eq!(hour.candles_opening_within(jan_1_2022, jan_1_2022 - 1), 0);
+
+        // Weeks open on Mondays: 2022-01-03 and 2022-01-10 fall in Jan 1..=14
+        let weeks = Interval::Weeks1;
+        assert_eq!(weeks.period_start(jan_1_2022), jan_1_2022 - 5 * day);
+        assert_eq!(weeks.candles_opening_within(jan_1_2022, jan_1_2022 + 14 * day - 1), 2);
+
+        // Calendar months: Jan..=Dec 2022, then Feb..=Dec from a mid-January start
+        let months = Interval::Months1;
+        let dec_31 = jan_1_2022 + 364 * day;
+        assert_eq!(months.period_start(jan_1_2022 + 15 * day), jan_1_2022);
+        assert_eq!(months.candles_opening_within(jan_1_2022, dec_31), 12);
+        assert_eq!(months.candles_opening_within(jan_1_2022 + day, dec_31), 11);
+    }
+
+    #[test]
+    fn test_interval_try_from_duration() {
+        assert_eq!(Interval::try_from(60_000).unwrap(), Interval::Minutes1);
+        assert_eq!(Interval::try_from(86_400_000).unwrap(), Interval::Days1);
+        assert!(Interval::try_from(42_000).is_err());
+    }
+
+    #[test]
+    fn test_interval_serde() {
+        assert_eq!(serde_json::to_string(&Interval::Minutes5).unwrap(), "\"5m\"");
+        assert_eq!(serde_json::from_str::<Interval>("\"5m\"").unwrap(), Interval::Minutes5);
+        assert_eq!(serde_json::from_str::<Interval>("3600000").unwrap(), Interval::Hours1);
+        assert!(serde_json::from_str::<Interval>("\"7m\"").is_err());
+        assert!(serde_json::from_str::<Interval>("123").is_err());
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Setting {
+            Interval(Interval),
+        }
+        let Setting::Interval(interval) = serde_json::from_str("\"1h\"").unwrap();
+        assert_eq!(interval, Interval::Hours1);
+    }
+
+    fn mock_order_book() -> OrderBook {
+        let depth: BinanceDepthResponse = serde_json::from_str(
+            r#"{
+                "lastUpdateId": 12345,
+                "bids": [["43000.00", "1.5"], ["42999.00", "2.0"]],
+                "asks": [["43001.00", "1.2"], ["43002.00", "1.8"]]
+            }"#,
+        )
+        .unwrap();
+        depth.to_order_book("BTCUSDT".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_order_book_imbalance() {
+        let book = mock_order_book();
+
+        assert!((book.imbalance(1) - (1.5 - 1.2) / 2.7).abs() < 1e-12);
+        assert!((book.imbalance(10) - (3.5 - 3.0) / 6.5).abs() < 1e-12);
+
+        let empty = OrderBook {
+            bids: vec![],
+            asks: vec![],
+            ..book
+        };
+        assert_eq!(empty.imbalance(5), 0.0);
+    }
+
+    #[test]
+    fn test_order_book_full_depth() {
+        // Two levels per side
+        let book = mock_order_book();
+        assert!(book.is_full_depth(5));
+        assert!(!book.is_full_depth(2));
+
+        let one_sided = OrderBook { asks: vec![], ..book };
+        assert!(!one_sided.is_full_depth(2));
+        assert!(one_sided.is_full_depth(3));
+    }
+
+    #[test]
+    fn test_order_book_microprice() {
+        let book = mock_order_book();
+        let expected = (43000.0 * 1.2 + 43001.0 * 1.5) / 2.7;
+        assert!((book.microprice().unwrap() - expected).abs() < 1e-9);
+
+        let one_sided = OrderBook { asks: vec![], ..book };
+        assert!(one_sided.microprice().is_none());
+    }
+
+    #[test]
+    fn test_price_level_ordering_and_sorting() {
+        assert!(level(100.0, 1.0) < level(101.0, 0.1));
+        assert!(level(100.0, 1.0) < level(100.0, 2.0));
+        assert_eq!(level(100.0, 1.0), level(100.0, 1.0));
+
+        let mut bids = vec![level(99.0, 1.0), level(f64::NAN, 1.0), level(101.0, 1.0), level(100.0, 1.0)];
+        sort_bids(&mut bids);
+        let prices: Vec<f64> = bids.iter().map(|l| l.price).collect();
+        assert_eq!(&prices[..3], &[101.0, 100.0, 99.0]);
+        assert!(prices[3].is_nan());
+
+        let mut asks = vec![level(102.0, 1.0), level(-f64::NAN, 1.0), level(100.5, 1.0)];
+        sort_asks(&mut asks);
+        let prices: Vec<f64> = asks.iter().map(|l| l.price).collect();
+        assert_eq!(&prices[..2], &[100.5, 102.0]);
+        assert!(prices[2].is_nan());
+    }
+
+    #[test]
+    fn test_rest_depth_is_sorted() {
+        let depth: BinanceDepthResponse = serde_json::from_str(
+            r#"{
+                "lastUpdateId": 1,
+                "bids": [["42999.00", "2.0"], ["43000.00", "1.5"]],
+                "asks": [["43002.00", "1.8"], ["43001.00", "1.2"]]
+            }"#,
+        )
+        .unwrap();
+        let book = depth.to_order_book("BTCUSDT".to_string()).unwrap();
+
+        assert_eq!(book.bids[0].price, 43000.0);
+        assert_eq!(book.asks[0].price, 43001.0);
+    }
+
+    #[test]
+    fn test_order_book_price_impact() {
+        let book = mock_order_book();
+
+        // Full fill across both ask levels
+        let buy = book.price_impact(OrderSide::Buy, 2.0).unwrap();
+        assert!((buy.avg_price - (1.2 * 43001.0 + 0.8 * 43002.0) / 2.0).abs() < 1e-9);
+        assert_eq!(buy.worst_price, 43002.0);
+        assert_eq!(buy.filled_qty, 2.0);
+        assert_eq!(buy.remaining_qty, 0.0);
+
+        // Inside the best level
+        let small = book.price_impact(OrderSide::Sell, 1.0).unwrap();
+        assert_eq!(small.avg_price, 43000.0);
+        assert_eq!(small.worst_price, 43000.0);
+
+        // Bids only hold 3.5
+        let sell = book.price_impact(OrderSide::Sell, 5.0).unwrap();
+        assert!((sell.avg_price - (1.5 * 43000.0 + 2.0 * 42999.0) / 3.5).abs() < 1e-9);
+        assert_eq!(sell.worst_price, 42999.0);
+        assert_eq!(sell.filled_qty, 3.5);
+        assert_eq!(sell.remaining_qty, 1.5);
+
+        assert!(book.price_impact(OrderSide::Buy, 0.0).is_none());
+        assert!(book.price_impact(OrderSide::Buy, f64::NAN).is_none());
+        let empty = OrderBook { asks: vec![], ..book };
+        assert!(empty.price_impact(OrderSide::Buy, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_account_trade_net_quantity() {
+        let trade = AccountTrade {
+            id: 1,
+            symbol: "BTCUSDT".to_string(),
+            order_id: 10,
+            price: 43000.0,
+            quantity: 1.0,
+            quote_quantity: 43000.0,
+            commission: 0.001,
+            commission_asset: "BTC".to_string(),
+            time: Utc::now(),
+            is_buyer: true,
+            is_maker: false,
+        };
+        assert!((trade.net_quantity("BTC") - 0.999).abs() < 1e-12);
+
+        let bnb_fee = AccountTrade {
+            commission_asset: "BNB".to_string(),
+            ..trade
+        };
+        assert_eq!(bnb_fee.net_quantity("BTC"), 1.0);
+    }
+
+    fn symbol_with_precision(base: i32, quote: i32) -> Symbol {
+        Symbol {
+            symbol: "TESTUSDT".to_string(),
+            status: "TRADING".to_string(),
+            base_asset: "TEST".to_string(),
+            quote_asset: "USDT".to_string(),
+            base_asset_precision: base,
+            quote_asset_precision: quote,
+            order_types: vec![],
+            filters: vec![],
+            permissions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_format_price_and_qty() {
+        let shib = symbol_with_precision(0, 8);
+        assert_eq!(format_price(0.00000891, &shib), "0.00000891");
+        assert_eq!(format_qty(1_250_000.0, &shib), "1250000");
+
+        let btc = symbol_with_precision(5, 2);
+        assert_eq!(format_price(43250.5, &btc), "43250.5");
+        assert_eq!(format_qty(0.123456, &btc), "0.12346");
+
+        // Tick and step sizes take precedence over asset precision
+        let filtered = Symbol {
+            filters: vec![
+                SymbolFilter::PriceFilter {
+                    min_price: 0.1,
+                    max_price: 1_000_000.0,
+                    tick_size: 0.1,
+                },
+                SymbolFilter::LotSize {
+                    min_qty: 0.001,
+                    max_qty: 9000.0,
+                    step_size: 0.001,
+                },
+            ],
+            ..symbol_with_precision(8, 8)
+        };
+        assert_eq!(format_price(43250.56, &filtered), "43250.6");
+        assert_eq!(format_price(43250.56, &filtered), filtered.format_price_str(43250.56));
+        assert_eq!(format_qty(0.1 + 0.2, &filtered), "0.3");
+        assert_eq!(format_qty(0.123456, &filtered), filtered.format_qty_str(0.123456));
+    }
+
+    #[test]
+    fn test_symbol_estimate_cost() {
+        let symbol = Symbol {
+            filters: vec![
+                SymbolFilter::PriceFilter {
+                    min_price: 0.01,
+                    max_price: 1_000_000.0,
+                    tick_size: 0.01,
+                },
+                SymbolFilter::LotSize {
+                    min_qty: 0.00001,
+                    max_qty: 9000.0,
+                    step_size: 0.00001,
+                },
+            ],
+            ..symbol_with_precision(5, 2)
+        };
+        let rates = CommissionRates {
+            maker: 0.00075,
+            taker: 0.001,
+        };
+
+        // 0.123456 rounds down to 0.12345, 43000.004 up to 43000.01
+        let cost = symbol.estimate_cost(OrderSide::Buy, 0.123456, 43000.004, rates);
+        assert!((cost - 0.12345 * 43000.01 * 1.001).abs() < 1e-6);
+
+        let exact = symbol.estimate_cost(OrderSide::Buy, 0.3, 100.0, rates);
+        assert!((exact - 30.03).abs() < 1e-9);
+
+        assert_eq!(symbol.estimate_cost(OrderSide::Sell, 1.0, 43000.0, rates), 0.0);
+    }
+
+    #[test]
+    fn test_symbol_format_price_and_qty_str() {
+        let symbol = Symbol {
+            filters: vec![
+                SymbolFilter::PriceFilter {
+                    min_price: 0.01,
+                    max_price: 1_000_000.0,
+                    tick_size: 0.01,
+                },
+                SymbolFilter::LotSize {
+                    min_qty: 0.00001,
+                    max_qty: 9000.0,
+                    step_size: 0.00001,
+                },
+            ],
+            ..symbol_with_precision(8, 8)
+        };
+
+        assert_eq!(symbol.format_price_str(1.10000000000001), "1.1");
+        assert_eq!(symbol.format_price_str(0.1 + 0.2), "0.3");
+        assert_eq!(symbol.format_price_str(43250.504), "43250.5");
+        assert_eq!(symbol.format_price_str(43250.0), "43250");
+        assert_eq!(symbol.format_price_str(0.015000001), "0.02");
+
+        assert_eq!(symbol.format_qty_str(0.1 + 0.2), "0.3");
+        assert_eq!(symbol.format_qty_str(0.123456), "0.12345");
+        assert_eq!(symbol.format_qty_str(2.00001), "2.00001");
+        assert_eq!(symbol.format_qty_str(0.000001), "0");
+
+        // Whole-unit steps and no filters
+        let shib = Symbol {
+            filters: vec![SymbolFilter::LotSize {
+                min_qty: 1.0,
+                max_qty: 1e12,
+                step_size: 1.0,
+            }],
+            ..symbol_with_precision(2, 8)
+        };
+        assert_eq!(shib.format_qty_str(1_250_000.7), "1250000");
+        assert_eq!(shib.format_price_str(0.000008910000001), "0.00000891");
+    }
+
+    fn level(price: f64, quantity: f64) -> PriceLevel {
+        PriceLevel { price, quantity }
+    }
+
+    #[test]
+    fn test_apply_diff_insert() {
+        let mut book = mock_order_book();
+        book.apply_diff(
+            &[level(43000.5, 0.7), level(42990.0, 3.0)],
+            &[level(43001.5, 0.4)],
+            12346,
+        );
+
+        let prices = |levels: &[PriceLevel]| levels.iter().map(|l| l.price).collect::<Vec<_>>();
+        assert_eq!(prices(&book.bids), vec![43000.5, 43000.0, 42999.0, 42990.0]);
+        assert_eq!(prices(&book.asks), vec![43001.0, 43001.5, 43002.0]);
+        assert_eq!(book.last_update_id, 12346);
+    }
+
+    #[test]
+    fn test_apply_diff_update() {
+        let mut book = mock_order_book();
+        book.apply_diff(&[level(42999.0, 5.0)], &[level(43001.0, 0.1)], 12346);
+
+        assert_eq!(book.bids.len(), 2);
+        assert_eq!(book.bids[1].quantity, 5.0);
+        assert_eq!(book.asks.len(), 2);
+        assert_eq!(book.asks[0].quantity, 0.1);
+    }
+
+    #[test]
+    fn test_apply_diff_zero_quantity_removes_level() {
+        let mut book = mock_order_book();
+        book.apply_diff(
+            &[level(43000.0, 0.0), level(42000.0, 0.0)],
+            &[level(43002.0, 0.0)],
+            12346,
+        );
+
+        // Removing an unknown level is a no-op
+        assert_eq!(book.bids.len(), 1);
+        assert_eq!(book.bids[0].price, 42999.0);
+        assert_eq!(book.asks.len(), 1);
+        assert_eq!(book.asks[0].price, 43001.0);
+    }
+
+    #[test]
+    fn test_order_book_diff() {
+        let previous = mock_order_book();
+        let current = OrderBook {
+            last_update_id: previous.last_update_id + 5,
+            bids: vec![level(43000.5, 0.7), level(43000.0, 1.0), level(42999.0, 2.0)],
+            asks: vec![level(43002.0, 1.0)],
+            ..previous.clone()
+        };
+
+        let delta = current.diff(&previous);
+        assert_eq!(delta.bids.added, vec![level(43000.5, 0.7)]);
+        assert_eq!(delta.bids.changed, vec![level(43000.0, 1.0)]);
+        assert!(delta.bids.removed.is_empty());
+        assert!(delta.asks.added.is_empty());
+        assert_eq!(delta.asks.removed, vec![previous.asks[0].clone()]);
+        assert_eq!(delta.asks.changed, vec![level(43002.0, 1.0)]);
+
+        // Same update id: nothing to redraw
+        let stale = OrderBook {
+            last_update_id: previous.last_update_id,
+            ..current.clone()
+        };
+        assert!(stale.diff(&previous).is_empty());
+        assert!(current.diff(&current).is_empty());
+    }
+
+    #[test]
+    fn test_order_book_as_maps() {
+        let book = OrderBook {
+            bids: vec![level(43000.0, 1.5), level(42999.5, 0.5), level(42999.0, 2.0)],
+            asks: vec![level(43001.0, 0.5), level(43002.0, 1.0), level(43010.0, 3.0)],
+            ..mock_order_book()
+        };
+        let (bids, asks) = book.as_maps();
+
+        // Level lookup
+        assert_eq!(bids.get(&PriceKey(42999.5)), Some(&0.5));
+        assert_eq!(asks.get(&43002.0.into()), Some(&1.0));
+        assert_eq!(asks.get(&PriceKey(43003.0)), None);
+
+        // Best levels sit at opposite ends of the ascending maps
+        assert_eq!(bids.last_key_value(), Some((&PriceKey(43000.0), &1.5)));
+        assert_eq!(asks.first_key_value(), Some((&PriceKey(43001.0), &0.5)));
+
+        // Range scans: ask depth up to 43005, bids from 42999.5 down
+        let ask_qty: f64 = asks.range(..=PriceKey(43005.0)).map(|(_, qty)| qty).sum();
+        assert_eq!(ask_qty, 1.5);
+        let bid_prices: Vec<f64> = bids
+            .range(..=PriceKey(42999.5))
+            .rev()
+            .map(|(price, _)| price.0)
+            .collect();
+        assert_eq!(bid_prices, vec![42999.5, 42999.0]);
+    }
+
+    fn trade(is_buyer_maker: bool) -> Trade {
+        Trade {
+            id: 1,
+            symbol: "BTCUSDT".to_string(),
+            price: 43000.0,
+            quantity: 0.5,
+            quote_quantity: 21500.0,
+            time: Utc::now(),
+            is_buyer_maker,
+        }
+    }
+
+    #[test]
+    fn test_trade_aggressor_side() {
+        // Buyer was the resting order, so a seller hit the bid
+        assert_eq!(trade(true).aggressor_side(), TradeSide::Sell);
+        // Seller was the resting order, so a buyer lifted the ask
+        assert_eq!(trade(false).aggressor_side(), TradeSide::Buy);
+
+        assert_eq!(format!("{:<4}|", TradeSide::Buy), "BUY |");
+    }
+
+    #[test]
+    fn test_trade_agg_trade_round_trip() {
+        let raw = trade(true);
+        let agg = AggTrade::from(raw.clone());
+        assert_eq!(agg.first_trade_id, raw.id);
+        assert_eq!(agg.last_trade_id, raw.id);
+
+        let back = agg.to_trade();
+        assert_eq!(back.id, raw.id);
+        assert_eq!(back.symbol, raw.symbol);
+        assert_eq!(back.price, raw.price);
+        assert_eq!(back.quantity, raw.quantity);
+        assert_eq!(back.quote_quantity, raw.quote_quantity);
+        assert_eq!(back.time, raw.time);
+        assert_eq!(back.is_buyer_maker, raw.is_buyer_maker);
+    }
+
+    #[test]
+    fn test_agg_trade_to_trade_is_lossy() {
+        let agg = AggTrade {
+            id: 900,
+            symbol: "ETHUSDT".to_string(),
+            price: 2000.0,
+            quantity: 3.0,
+            first_trade_id: 10,
+            last_trade_id: 12,
+            time: Utc::now(),
+            is_buyer_maker: false,
+        };
+        let raw = agg.to_trade();
+        assert_eq!(raw.id, 10);
+        assert_eq!(raw.quote_quantity, 6000.0);
+        assert_eq!(raw.aggressor_side(), TradeSide::Buy);
+
+        // The id range collapses to the first trade
+        let again = AggTrade::from(raw);
+        assert_eq!(again.last_trade_id, 10);
+    }
+
+    #[test]
+    fn test_status_changes() {
+        let map = |entries: &[(&str, &str)]| -> HashMap<String, SymbolStatus> {
+            entries
+                .iter()
+                .map(|(symbol, status)| (symbol.to_string(), SymbolStatus::from(*status)))
+                .collect()
+        };
+        let old = map(&[
+            ("BTCUSDT", "TRADING"),
+            ("ETHUSDT", "TRADING"),
+            ("LUNAUSDT", "TRADING"),
+            ("NEWUSDT", "PRE_TRADING"),
+            ("OLDUSDT", "BREAK"),
+        ]);
+        let new = map(&[
+            ("BTCUSDT", "TRADING"),
+            ("ETHUSDT", "HALT"),
+            ("NEWUSDT", "TRADING"),
+            ("OLDUSDT", "HALT"),
+            ("SOLUSDT", "TRADING"),
+        ]);
+
+        let changes = status_changes(&old, &new);
+        assert_eq!(changes.listed, vec!["NEWUSDT", "SOLUSDT"]);
+        assert_eq!(changes.halted, vec!["ETHUSDT", "LUNAUSDT"]);
+        assert!(status_changes(&new, &new).is_empty());
+
+        // Unknown statuses survive a round trip
+        let status: SymbolStatus = serde_json::from_str(r#""DELISTING""#).unwrap();
+        assert_eq!(status, SymbolStatus::Other("DELISTING".to_string()));
+        assert_eq!(serde_json::to_string(&status).unwrap(), r#""DELISTING""#);
+    }
+
+    #[test]
+    fn test_normalize_symbol() {
+        assert_eq!(normalize_symbol("BTC/USDT"), "BTCUSDT");
+        assert_eq!(normalize_symbol("btc-usdt"), "BTCUSDT");
+        assert_eq!(normalize_symbol("BTCUSDT"), "BTCUSDT");
+        assert_eq!(normalize_symbol(" eth_btc "), "ETHBTC");
+    }
+
+    #[test]
+    fn test_parse_f64() {
+        assert_eq!(parse_f64("price", "43000.50").unwrap(), 43000.5);
+        assert_eq!(parse_f64("price", " 1.5 ").unwrap(), 1.5);
+        assert_eq!(parse_f64("qty", "1e-8").unwrap(), 0.00000001);
+        assert_eq!(parse_f64("qty", "2.5E3").unwrap(), 2500.0);
+
+        for bad in ["", "   ", "null", "NULL", "abc", "NaN", "inf"] {
+            match parse_f64("bidPrice", bad) {
+                Err(crate::Error::DeserializationError(msg)) => assert!(msg.starts_with("bidPrice")),
+                other => panic!("{:?} parsed as {:?}", bad, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_f64_or_zero() {
+        assert_eq!(parse_f64_or_zero("0.00100000"), 0.001);
+        assert_eq!(parse_f64_or_zero("1.2e2"), 120.0);
+        assert_eq!(parse_f64_or_zero(""), 0.0);
+        assert_eq!(parse_f64_or_zero("null"), 0.0);
+    }
+
+    #[test]
+    fn test_malformed_filter_and_stop_price_are_errors() {
+        let filter: BinanceSymbolFilter = serde_json::from_str(
+            r#"{"filterType": "PRICE_FILTER", "minPrice": "0.01", "maxPrice": "1000", "tickSize": "0,01"}"#,
+        )
+        .unwrap();
+        match filter.to_filter() {
+            Err(crate::Error::DeserializationError(msg)) => assert!(msg.starts_with("tickSize")),
+            other => panic!("unexpected filter: {:?}", other),
+        }
+
+        let order = |stop_price: &str| -> BinanceOrderResponse {
+            serde_json::from_str(&format!(
+                r#"{{"symbol": "BTCUSDT", "orderId": 1, "clientOrderId": "a", "price": "0.0",
+                    "origQty": "1.0", "executedQty": "0.0", "cummulativeQuoteQty": "0.0",
+                    "status": "NEW", "timeInForce": "GTC", "type": "STOP_LOSS", "side": "SELL",
+                    "stopPrice": "{}"}}"#,
+                stop_price
+            ))
+            .unwrap()
+        };
+        assert_eq!(order("42000.00").to_order().unwrap().stop_price, Some(42000.0));
+        match order("n/a").to_order() {
+            Err(crate::Error::DeserializationError(msg)) => assert!(msg.starts_with("stopPrice")),
+            other => panic!("unexpected order: {:?}", other),
+        }
+    }
+
     #[test]
     fn test_ticker24h_calculations() {
         let ticker = Ticker24h {
@@ -396,7 +3051,83 @@ mod tests {
             count: 1000,
         };
 
-        assert_eq!(ticker.spread(), 2.0);
-        assert_eq!(ticker.mid(), 43000.0);
+        assert!(ticker.has_quotes());
+        assert_eq!(ticker.try_spread(), Some(2.0));
+        assert_eq!(ticker.try_mid(), Some(43000.0));
+        assert_eq!(ticker.range(), 2000.0);
+        assert!((ticker.range_percent() - 4.761904).abs() < 1e-5);
+        assert!((ticker.spread_bps().unwrap() - 0.465116).abs() < 1e-5);
+        assert!(ticker.is_up());
+
+        let flat = Ticker24h {
+            price_change: 0.0,
+            open_price: 0.0,
+            bid_price: 0.0,
+            ask_price: 0.0,
+            ..ticker
+        };
+        assert!(!flat.is_up());
+        assert_eq!(flat.range_percent(), 0.0);
+    }
+
+    #[test]
+    fn test_market_stats_agree() {
+        let close_time = Utc::now();
+        let ticker = Ticker24h {
+            symbol: "BTCUSDT".to_string(),
+            price_change: 1000.0,
+            price_change_percent: 2.5,
+            weighted_avg_price: 40500.0,
+            prev_close_price: 40000.0,
+            last_price: 41000.0,
+            bid_price: 40999.0,
+            ask_price: 41001.0,
+            open_price: 40000.0,
+            high_price: 41500.0,
+            low_price: 39500.0,
+            volume: 1000.0,
+            quote_volume: 40_500_000.0,
+            open_time: close_time - chrono::Duration::hours(24),
+            close_time,
+            first_id: 1,
+            last_id: 1000,
+            count: 1000,
+        };
+        let mini = MiniTicker {
+            symbol: "BTCUSDT".to_string(),
+            close_price: 41000.0,
+            open_price: 40000.0,
+            high_price: 41500.0,
+            low_price: 39500.0,
+            volume: 1000.0,
+            quote_volume: 40_500_000.0,
+            event_time: close_time,
+        };
+
+        assert_eq!(ticker.stats(), mini.stats());
+        assert_eq!(mini.stats().price_change, 1000.0);
+
+        let no_open = MiniTicker { open_price: 0.0, ..mini };
+        assert_eq!(no_open.stats().price_change_percent, 0.0);
+    }
+
+    #[test]
+    fn test_ticker24h_without_quotes() {
+        // Illiquid symbol: null bid, empty ask and a zero last price
+        let response: Binance24hTickerResponse = serde_json::from_str(r#"{
+            "symbol": "XYZUSDT", "priceChange": "0.00000000", "priceChangePercent": "0.000",
+            "weightedAvgPrice": "0.00000000", "prevClosePrice": "0.00000000",
+            "lastPrice": "0.00000000", "bidPrice": null, "askPrice": "",
+            "openPrice": "0.00000000", "highPrice": "0.00000000", "lowPrice": "0.00000000",
+            "volume": "0.00000000", "quoteVolume": "0.00000000",
+            "openTime": 1640000000000, "closeTime": 1640086400000,
+            "firstId": -1, "lastId": -1, "count": 0
+        }"#).unwrap();
+        let ticker = response.to_ticker24h().unwrap();
+
+        assert!(!ticker.has_quotes());
+        assert_eq!(ticker.try_spread(), None);
+        assert_eq!(ticker.try_mid(), None);
+        assert_eq!(ticker.spread_bps(), None);
     }
 }
@@ -1,20 +1,48 @@
 //! Rate limiter implementation for Binance API using Governor
 //! 
-//! Binance uses weight-based rate limiting, but this implementation provides
-//! simple request-per-minute rate limiting. Weight-based limiting can be added later.
+//! Binance uses weight-based rate limiting. This implementation provides
+//! request-per-minute rate limiting where heavier endpoints are charged
+//! several units via `acquire_weight`.
+//!
+//! The limiter can also adapt to the weight Binance reports as used: once
+//! `X-MBX-USED-WEIGHT-1M` passes 80% of the limit, every acquire is delayed
+//! a little more, up to one second at the limit.
 
 use governor::{
-    clock::DefaultClock,
+    clock::{Clock, DefaultClock, ReasonablyRealtime},
+    middleware::NoOpMiddleware,
     state::{InMemoryState, NotKeyed},
     Quota, RateLimiter as GovernorRateLimiter,
 };
 use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Fraction of the weight limit above which acquires start slowing down
+const ADAPTIVE_THRESHOLD: f64 = 0.8;
+
+/// Extra delay per acquire once the reported weight reaches the limit
+const ADAPTIVE_MAX_DELAY: Duration = Duration::from_secs(1);
 
 /// Token bucket rate limiter using Governor's GCRA algorithm
+///
+/// Generic over Governor's [`Clock`] so tests can drive time manually;
+/// the default constructors use the real system clock.
 #[derive(Clone)]
-pub struct RateLimiter {
-    governor: Arc<GovernorRateLimiter<NotKeyed, InMemoryState, DefaultClock>>,
+pub struct RateLimiter<C: Clock = DefaultClock> {
+    governor: Arc<GovernorRateLimiter<NotKeyed, InMemoryState, C, NoOpMiddleware<C::Instant>>>,
+    /// Extra delay per acquire from server-reported weight, in microseconds
+    throttle_micros: Arc<AtomicU64>,
+}
+
+/// Per-minute quota with a burst of one second's worth of requests
+fn per_minute_quota(requests_per_minute: u32) -> Quota {
+    let burst: u32 = requests_per_minute.div_ceil(60).max(1);
+    Quota::per_minute(
+        NonZeroU32::new(requests_per_minute)
+            .expect("requests_per_minute must be greater than 0")
+    ).allow_burst(NonZeroU32::new(burst).expect("Burst must be greater than 0."))
 }
 
 impl RateLimiter {
@@ -34,14 +62,9 @@ impl RateLimiter {
     /// let limiter = RateLimiter::new(1200);
     /// ```
     pub fn new(requests_per_minute: u32) -> Self {
-        let burst: u32 = ((requests_per_minute + 59) / 60).max(1);
-        let quota = Quota::per_minute(
-            NonZeroU32::new(requests_per_minute)
-                .expect("requests_per_minute must be greater than 0")
-        ).allow_burst(NonZeroU32::new(burst).expect("Burst must be greater than 0."));
-        
         Self {
-            governor: Arc::new(GovernorRateLimiter::direct(quota)),
+            governor: Arc::new(GovernorRateLimiter::direct(per_minute_quota(requests_per_minute))),
+            throttle_micros: Arc::new(AtomicU64::new(0)),
         }
     }
     
@@ -59,31 +82,42 @@ impl RateLimiter {
         
         Self {
             governor: Arc::new(GovernorRateLimiter::direct(quota)),
+            throttle_micros: Arc::new(AtomicU64::new(0)),
         }
     }
-    
-    /// Acquire permission to make a request (async, will wait if needed)
+}
+
+impl<C: Clock> RateLimiter<C> {
+    /// Create rate limiter driven by a custom clock
     /// 
-    /// Uses GCRA (Generic Cell Rate Algorithm) for smooth rate limiting.
-    /// This method blocks until a permit becomes available according to the rate limit.
+    /// Uses the same quota as [`RateLimiter::new`]. Intended for deterministic
+    /// tests with Governor's `FakeRelativeClock`.
+    /// 
+    /// # Panics
+    /// Panics if requests_per_minute is 0
     /// 
     /// # Example
-    /// ```no_run
-    /// # use binance_connector::rate_limiter::RateLimiter;
-    /// # async fn example() {
-    /// let limiter = RateLimiter::new(1200);
+    /// ```
+    /// use binance_connector::rate_limiter::RateLimiter;
+    /// use governor::clock::FakeRelativeClock;
+    /// use std::time::Duration;
     /// 
-    /// // This will wait if rate limit is exceeded
-    /// limiter.acquire().await;
-    /// // Make your API call here
-    /// # }
+    /// let clock = FakeRelativeClock::default();
+    /// let limiter = RateLimiter::with_clock(60, clock.clone());
+    /// 
+    /// assert!(limiter.try_acquire().is_some());
+    /// assert!(limiter.try_acquire().is_none());
+    /// 
+    /// clock.advance(Duration::from_secs(1));
+    /// assert!(limiter.try_acquire().is_some());
     /// ```
-    pub async fn acquire(&self) -> RateLimitPermit {
-        // Wait until we're allowed to proceed
-        self.governor.until_ready().await;
-        
-        RateLimitPermit {
-            _private: (),
+    pub fn with_clock(requests_per_minute: u32, clock: C) -> Self {
+        Self {
+            governor: Arc::new(GovernorRateLimiter::direct_with_clock(
+                per_minute_quota(requests_per_minute),
+                clock,
+            )),
+            throttle_micros: Arc::new(AtomicU64::new(0)),
         }
     }
     
@@ -111,6 +145,98 @@ impl RateLimiter {
             _private: (),
         })
     }
+    
+    /// Adjust the adaptive delay from the weight Binance reports as used
+    /// 
+    /// Below 80% of `limit` there is no extra delay. Above it, every
+    /// acquire waits an extra delay that grows linearly to one second at
+    /// the limit. Each call replaces the previous value, so reports of low
+    /// usage lift the throttle again.
+    /// 
+    /// # Example
+    /// ```
+    /// use binance_connector::rate_limiter::RateLimiter;
+    /// 
+    /// let limiter = RateLimiter::new(1200);
+    /// limiter.update_from_used_weight(1140, 1200);
+    /// assert!(limiter.would_block());
+    /// 
+    /// limiter.update_from_used_weight(100, 1200);
+    /// assert!(!limiter.would_block());
+    /// ```
+    pub fn update_from_used_weight(&self, used: u32, limit: u32) {
+        let delay = if limit == 0 {
+            Duration::ZERO
+        } else {
+            let ratio = used as f64 / limit as f64;
+            let pressure = ((ratio - ADAPTIVE_THRESHOLD) / (1.0 - ADAPTIVE_THRESHOLD)).clamp(0.0, 1.0);
+            ADAPTIVE_MAX_DELAY.mul_f64(pressure)
+        };
+        self.throttle_micros.store(delay.as_micros() as u64, Ordering::Relaxed);
+    }
+    
+    /// Extra delay currently added to every acquire
+    pub fn throttle_delay(&self) -> Duration {
+        Duration::from_micros(self.throttle_micros.load(Ordering::Relaxed))
+    }
+    
+    /// Whether acquires are currently being slowed down by reported weight
+    pub fn would_block(&self) -> bool {
+        !self.throttle_delay().is_zero()
+    }
+}
+
+impl<C: ReasonablyRealtime> RateLimiter<C> {
+    /// Acquire permission to make a request (async, will wait if needed)
+    /// 
+    /// Uses GCRA (Generic Cell Rate Algorithm) for smooth rate limiting.
+    /// This method blocks until a permit becomes available according to the rate limit.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// # use binance_connector::rate_limiter::RateLimiter;
+    /// # async fn example() {
+    /// let limiter = RateLimiter::new(1200);
+    /// 
+    /// // This will wait if rate limit is exceeded
+    /// limiter.acquire().await;
+    /// // Make your API call here
+    /// # }
+    /// ```
+    pub async fn acquire(&self) -> RateLimitPermit {
+        // Wait until we're allowed to proceed
+        self.governor.until_ready().await;
+        self.throttle().await;
+        
+        RateLimitPermit {
+            _private: (),
+        }
+    }
+    
+    /// Acquire permission for a request that counts as `weight` requests
+    /// 
+    /// Binance charges heavier endpoints more than one unit of request weight.
+    /// Each unit is acquired in turn, so weights above the burst capacity
+    /// still complete (they just wait longer).
+    pub async fn acquire_weight(&self, weight: u32) -> RateLimitPermit {
+        for _ in 0..weight.max(1) {
+            self.governor.until_ready().await;
+        }
+        self.throttle().await;
+        
+        RateLimitPermit {
+            _private: (),
+        }
+    }
+}
+
+impl<C: Clock> RateLimiter<C> {
+    async fn throttle(&self) {
+        let delay = self.throttle_delay();
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
 }
 
 /// RAII guard for rate limit permit
@@ -272,6 +398,64 @@ mod tests {
         assert!(elapsed <= Duration::from_millis(12000));
     }
 
+    #[tokio::test]
+    async fn test_acquire_weight_consumes_capacity() {
+        let limiter = RateLimiter::per_second(5);
+        
+        limiter.acquire_weight(5).await;
+        assert!(limiter.try_acquire().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_delay_from_used_weight() {
+        let limiter = RateLimiter::per_second(100);
+        
+        // Under the threshold nothing changes
+        limiter.update_from_used_weight(900, 1200);
+        assert!(!limiter.would_block());
+        assert_eq!(limiter.throttle_delay(), Duration::ZERO);
+        
+        // 95% of the limit: three quarters of the maximum delay
+        limiter.update_from_used_weight(1140, 1200);
+        assert!(limiter.would_block());
+        assert!((limiter.throttle_delay().as_secs_f64() - 0.75).abs() < 1e-3);
+        
+        let start = Instant::now();
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(700));
+        
+        // Over the limit is capped, and a quiet report lifts the throttle
+        limiter.update_from_used_weight(5000, 1200);
+        assert_eq!(limiter.throttle_delay(), Duration::from_secs(1));
+        limiter.update_from_used_weight(10, 1200);
+        assert!(!limiter.would_block());
+    }
+
+    #[test]
+    fn test_fake_clock_is_deterministic() {
+        use governor::clock::FakeRelativeClock;
+        
+        let clock = FakeRelativeClock::default();
+        let limiter = RateLimiter::with_clock(120, clock.clone()); // 2 req/sec, burst 2
+        
+        assert!(limiter.try_acquire().is_some());
+        assert!(limiter.try_acquire().is_some());
+        assert!(limiter.try_acquire().is_none());
+        
+        // One permit replenishes every 500ms
+        clock.advance(Duration::from_millis(499));
+        assert!(limiter.try_acquire().is_none());
+        clock.advance(Duration::from_millis(1));
+        assert!(limiter.try_acquire().is_some());
+        assert!(limiter.try_acquire().is_none());
+        
+        // A full second restores the whole burst
+        clock.advance(Duration::from_secs(1));
+        assert!(limiter.try_acquire().is_some());
+        assert!(limiter.try_acquire().is_some());
+        assert!(limiter.try_acquire().is_none());
+    }
+
     #[test]
     #[should_panic(expected = "requests_per_minute must be greater than 0")]
     fn test_zero_rate_panics() {
@@ -0,0 +1,252 @@
+//! Capture stream items to disk as newline-delimited JSON
+//!
+//! [`Recorder`] drains any stream receiver returned by
+//! [`BinanceWebSocket`](crate::BinanceWebSocket) (tickers, klines, trades,
+//! ...) and writes one JSON object per line to an [`AsyncWrite`] sink. Each
+//! line deserializes back into the model type, so recordings can be replayed
+//! with `serde_json`.
+
+use crate::error::{Error, Result};
+use crate::websocket::shutdown_signalled;
+use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt, BufWriter};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+
+/// Default time between flushes of buffered lines
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Writes items from a stream receiver as newline-delimited JSON
+///
+/// Stream errors are logged and skipped; only items are recorded. Lines are
+/// buffered and flushed every `flush_interval` (1s by default), when the
+/// stream ends and on [`RecorderHandle::shutdown`].
+///
+/// # Example
+/// ```no_run
+/// use binance_connector::{BinanceConfig, BinanceWebSocket, Recorder};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let ws = BinanceWebSocket::new(BinanceConfig::new(false))?;
+///     let trades = ws.trade_stream("BTCUSDT").await?;
+///
+///     let file = tokio::fs::File::create("btcusdt-trades.jsonl").await?;
+///     let recording = Recorder::new(trades).start(file);
+///
+///     tokio::signal::ctrl_c().await?;
+///     recording.shutdown().await?;
+///     Ok(())
+/// }
+/// ```
+pub struct Recorder<T> {
+    rx: mpsc::Receiver<Result<T>>,
+    flush_interval: Duration,
+}
+
+impl<T> Recorder<T>
+where
+    T: Serialize + Send + 'static,
+{
+    /// Record items from `rx`
+    pub fn new(rx: mpsc::Receiver<Result<T>>) -> Self {
+        Self {
+            rx,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+        }
+    }
+
+    /// Set time between flushes of buffered lines
+    pub fn flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    /// Start writing to `writer` in a background task
+    pub fn start<W>(self, writer: W) -> RecorderHandle<W>
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let task = tokio::spawn(self.run(writer, shutdown_rx));
+        RecorderHandle { shutdown_tx, task }
+    }
+
+    async fn run<W>(mut self, writer: W, mut shutdown_rx: watch::Receiver<bool>) -> Result<W>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut writer = BufWriter::new(writer);
+        let mut flush = interval(self.flush_interval);
+        flush.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut line = Vec::new();
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = shutdown_signalled(&mut shutdown_rx) => break,
+                item = self.rx.recv() => match item {
+                    Some(Ok(item)) => {
+                        line.clear();
+                        serde_json::to_writer(&mut line, &item)?;
+                        line.push(b'\n');
+                        writer.write_all(&line).await?;
+                    }
+                    Some(Err(e)) => tracing::warn!(error = %e, "recorder skipped a stream error"),
+                    None => break,
+                },
+                _ = flush.tick() => writer.flush().await?,
+            }
+        }
+
+        writer.flush().await?;
+        Ok(writer.into_inner())
+    }
+}
+
+/// Handle to a running [`Recorder`]
+///
+/// Dropping the handle leaves the recorder running until its stream ends.
+pub struct RecorderHandle<W> {
+    shutdown_tx: watch::Sender<bool>,
+    task: JoinHandle<Result<W>>,
+}
+
+impl<W> RecorderHandle<W> {
+    /// Stop recording, flush and return the writer
+    ///
+    /// Items still queued in the stream are not written. Returns the first
+    /// serialization or write error if recording failed earlier, or
+    /// `Error::IoError` if the recording task panicked.
+    pub async fn shutdown(self) -> Result<W> {
+        let _ = self.shutdown_tx.send(true);
+        self.task.await.map_err(|e| Error::IoError(e.into()))?
+    }
+
+    /// Check if recording has stopped (stream ended or a write failed)
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::models::Trade;
+    use chrono::DateTime;
+    use tokio::io::AsyncReadExt;
+
+    fn trade(id: i64, price: f64) -> Trade {
+        Trade {
+            id,
+            symbol: "BTCUSDT".to_string(),
+            price,
+            quantity: 0.5,
+            quote_quantity: price * 0.5,
+            time: DateTime::from_timestamp_millis(1_640_000_000_000 + id).unwrap(),
+            is_buyer_maker: id % 2 == 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_round_trip() {
+        let (tx, rx) = mpsc::channel(10);
+        let recording = Recorder::new(rx).start(Vec::new());
+
+        let trades = [trade(1, 43000.0), trade(2, 43000.5), trade(3, 42999.25)];
+        tx.send(Ok(trades[0].clone())).await.unwrap();
+        tx.send(Err(Error::WebSocketClosed)).await.unwrap();
+        tx.send(Ok(trades[1].clone())).await.unwrap();
+        tx.send(Ok(trades[2].clone())).await.unwrap();
+        drop(tx);
+
+        // The recorder stops by itself once the stream ends
+        while !recording.is_finished() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let buffer = recording.shutdown().await.unwrap();
+        let recorded: Vec<Trade> = String::from_utf8(buffer)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        let key = |t: &Trade| (t.id, t.price, t.quote_quantity, t.time, t.is_buyer_maker);
+        assert_eq!(
+            recorded.iter().map(key).collect::<Vec<_>>(),
+            trades.iter().map(key).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_flushes_open_stream() {
+        let (tx, rx) = mpsc::channel(10);
+        let recording = Recorder::new(rx)
+            .flush_interval(Duration::from_secs(3600))
+            .start(Vec::new());
+
+        tx.send(Ok(trade(1, 43000.0))).await.unwrap();
+        tokio::task::yield_now().await;
+        assert!(!recording.is_finished());
+
+        let buffer = recording.shutdown().await.unwrap();
+        assert_eq!(buffer.iter().filter(|&&b| b == b'\n').count(), 1);
+        drop(tx);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_handle_keeps_recording() {
+        let (tx, rx) = mpsc::channel(10);
+        let (writer, mut reader) = tokio::io::duplex(4096);
+        drop(Recorder::new(rx).flush_interval(Duration::from_millis(10)).start(writer));
+
+        tx.send(Ok(trade(1, 43000.0))).await.unwrap();
+        tx.send(Ok(trade(2, 43000.5))).await.unwrap();
+        drop(tx);
+
+        // The stream ending, not the dropped handle, stops the recorder
+        let mut recorded = String::new();
+        tokio::time::timeout(Duration::from_secs(5), reader.read_to_string(&mut recorded))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(recorded.lines().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_reports_panicked_task() {
+        struct PanickingWriter;
+
+        impl AsyncWrite for PanickingWriter {
+            fn poll_write(
+                self: std::pin::Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+                _: &[u8],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                panic!("writer panicked")
+            }
+
+            fn poll_flush(
+                self: std::pin::Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                panic!("writer panicked")
+            }
+
+            fn poll_shutdown(
+                self: std::pin::Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                std::task::Poll::Ready(Ok(()))
+            }
+        }
+
+        let (tx, rx) = mpsc::channel::<Result<Trade>>(10);
+        let recording = Recorder::new(rx).start(PanickingWriter);
+        drop(tx);
+
+        assert!(matches!(recording.shutdown().await, Err(Error::IoError(_))));
+    }
+}
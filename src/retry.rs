@@ -0,0 +1,165 @@
+//! Retry/backoff policy shared by REST requests and WebSocket reconnects
+//!
+//! Delays grow geometrically from `initial` by `multiplier`, are capped at
+//! `max_delay`, and can be randomly shortened by up to `jitter` (a fraction
+//! of the delay) so that many clients don't retry in lockstep. Full jitter,
+//! which REST retries use while `BinanceConfig::retry_jitter` is set,
+//! instead picks the whole delay uniformly between zero and the base delay.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Exponential backoff policy
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BackoffPolicy {
+    /// Delay before the first retry
+    pub initial: Duration,
+
+    /// Factor applied to the delay after each retry
+    pub multiplier: f64,
+
+    /// Upper bound for a single delay
+    pub max_delay: Duration,
+
+    /// Total attempts, including the first one
+    pub max_attempts: u32,
+
+    /// Fraction of each delay (0.0 - 1.0) that may be randomly removed
+    pub jitter: f64,
+}
+
+impl Default for BackoffPolicy {
+    /// 500ms doubling up to 30s, 5 attempts, no jitter
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(30),
+            max_attempts: 5,
+            jitter: 0.0,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Delay before retry number `retry` (0-based), without jitter
+    pub fn base_delay(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(retry.min(i32::MAX as u32) as i32);
+        let delay = self.initial.as_secs_f64() * factor;
+
+        if delay.is_finite() && delay < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(delay)
+        } else {
+            self.max_delay
+        }
+    }
+
+    /// Delay before retry number `retry` (0-based), with jitter applied
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.jittered(self.base_delay(retry), random_unit())
+    }
+
+    /// Delay before retry number `retry` (0-based), drawn uniformly from
+    /// zero up to the base delay ("full jitter")
+    ///
+    /// Ignores the `jitter` fraction; spreads concurrent clients further
+    /// apart than partial jitter at the cost of sometimes retrying at once.
+    pub fn full_jitter_delay(&self, retry: u32) -> Duration {
+        full_jittered(self.base_delay(retry), random_unit())
+    }
+
+    /// Whether another attempt is allowed after `attempts` have been made
+    pub fn should_retry(&self, attempts: u32) -> bool {
+        attempts < self.max_attempts
+    }
+
+    /// Shorten `delay` by `sample` (in [0, 1)) of the jitter fraction
+    fn jittered(&self, delay: Duration, sample: f64) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        delay.mul_f64(1.0 - jitter * sample)
+    }
+}
+
+/// Scale `delay` by `sample` (in [0, 1))
+fn full_jittered(delay: Duration, sample: f64) -> Duration {
+    delay.mul_f64(sample.clamp(0.0, 1.0))
+}
+
+/// Uniform sample in [0, 1) from std's per-process random hasher keys
+fn random_unit() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_sequence() {
+        let policy = BackoffPolicy {
+            initial: Duration::from_millis(100),
+            multiplier: 2.0,
+            max_delay: Duration::from_millis(1000),
+            max_attempts: 6,
+            jitter: 0.0,
+        };
+
+        let delays: Vec<u128> = (0..6).map(|r| policy.base_delay(r).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 800, 1000, 1000]);
+
+        // Without jitter the delay is deterministic
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+
+        // Huge retry counts saturate instead of overflowing
+        assert_eq!(policy.base_delay(u32::MAX), Duration::from_millis(1000));
+
+        assert!(policy.should_retry(5));
+        assert!(!policy.should_retry(6));
+    }
+
+    #[test]
+    fn test_jitter_bounds() {
+        let policy = BackoffPolicy {
+            initial: Duration::from_secs(1),
+            jitter: 0.25,
+            ..BackoffPolicy::default()
+        };
+        let base = policy.base_delay(0);
+
+        assert_eq!(policy.jittered(base, 0.0), base);
+        assert_eq!(policy.jittered(base, 1.0), Duration::from_millis(750));
+
+        for _ in 0..1000 {
+            let delay = policy.delay(0);
+            assert!(delay >= Duration::from_millis(750) && delay <= base);
+        }
+
+        // Out-of-range jitter is clamped
+        let full = BackoffPolicy { jitter: 5.0, ..policy };
+        assert_eq!(full.jittered(base, 1.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_full_jitter_bounds() {
+        let policy = BackoffPolicy {
+            initial: Duration::from_millis(100),
+            ..BackoffPolicy::default()
+        };
+        let base = policy.base_delay(3);
+        assert_eq!(base, Duration::from_millis(800));
+
+        assert_eq!(full_jittered(base, 0.0), Duration::ZERO);
+        assert_eq!(full_jittered(base, 0.5), Duration::from_millis(400));
+        assert!(full_jittered(base, 0.999) < base);
+
+        for _ in 0..1000 {
+            assert!(policy.full_jitter_delay(3) <= base);
+        }
+
+        // Cap still applies
+        assert!(policy.full_jitter_delay(u32::MAX) <= policy.max_delay);
+    }
+}
@@ -0,0 +1,146 @@
+//! Request metrics emitted through the `metrics` facade
+//!
+//! Compiled to no-ops unless the `metrics` feature is enabled. With it, the
+//! REST client records:
+//! - `binance.requests.total` (counter; `endpoint`, `status`)
+//! - `binance.request.duration` (histogram, seconds; `endpoint`)
+//! - `binance.request.retries` (counter; `endpoint`)
+//! - `binance.errors.total` (counter; `endpoint`, `kind`)
+//!
+//! Install any `metrics` recorder (Prometheus exporter, StatsD, ...) to
+//! collect them.
+
+use crate::error::{Error, Result};
+use reqwest::Response;
+use std::time::Duration;
+
+/// Record one HTTP attempt, and whether it is about to be retried
+#[cfg(feature = "metrics")]
+pub(crate) fn record_attempt(outcome: &Result<Response>, elapsed: Duration, retrying: bool) {
+    let (endpoint, status) = match outcome {
+        Ok(response) => (response.url().path().to_string(), response.status().as_str().to_string()),
+        Err(Error::HttpError(e)) => (endpoint_of(e), "error".to_string()),
+        Err(_) => ("unknown".to_string(), "error".to_string()),
+    };
+
+    metrics::counter!("binance.requests.total", "endpoint" => endpoint.clone(), "status" => status)
+        .increment(1);
+    metrics::histogram!("binance.request.duration", "endpoint" => endpoint.clone())
+        .record(elapsed.as_secs_f64());
+    if let Err(e) = outcome {
+        record_error(&endpoint, e);
+    }
+    if retrying {
+        metrics::counter!("binance.request.retries", "endpoint" => endpoint).increment(1);
+    }
+}
+
+/// Record an error returned to the caller for `endpoint`
+#[cfg(feature = "metrics")]
+pub(crate) fn record_error(endpoint: &str, error: &Error) {
+    metrics::counter!(
+        "binance.errors.total",
+        "endpoint" => endpoint.to_string(),
+        "kind" => error_kind(error),
+    )
+    .increment(1);
+}
+
+#[cfg(feature = "metrics")]
+fn endpoint_of(error: &reqwest::Error) -> String {
+    error.url().map_or_else(|| "unknown".to_string(), |url| url.path().to_string())
+}
+
+/// Stable label for an error variant
+#[cfg(feature = "metrics")]
+fn error_kind(error: &Error) -> &'static str {
+    match error {
+        Error::HttpError(e) if e.is_timeout() => "timeout",
+        Error::HttpError(e) if e.is_connect() => "connect",
+        Error::HttpError(_) => "http",
+        Error::ApiError { .. } => "api",
+        Error::RateLimitExceeded { .. } => "rate_limit",
+        Error::Timeout(_) => "timeout",
+        Error::DeserializationError(_) | Error::JsonError(_) => "deserialization",
+        _ => "other",
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn record_attempt(_outcome: &Result<Response>, _elapsed: Duration, _retrying: bool) {}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn record_error(_endpoint: &str, _error: &Error) {}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use crate::{BinanceClient, BinanceConfig};
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use metrics_util::MetricKind;
+
+    #[test]
+    fn test_request_metrics_are_recorded() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        metrics::with_local_recorder(&recorder, || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+
+            runtime.block_on(async {
+                let mut server = mockito::Server::new_async().await;
+                server
+                    .mock("GET", "/api/v3/ticker/price")
+                    .match_query(mockito::Matcher::Any)
+                    .with_body(r#"{"symbol":"BTCUSDT","price":"43000.00"}"#)
+                    .create_async()
+                    .await;
+                server
+                    .mock("GET", "/api/v3/time")
+                    .with_status(400)
+                    .with_body(r#"{"code":-1100,"msg":"Illegal characters"}"#)
+                    .create_async()
+                    .await;
+
+                let mut config = BinanceConfig::new(false);
+                config.base_url = Some(server.url());
+                let client = BinanceClient::new(config).unwrap();
+
+                assert!(client.get_ticker_price("BTCUSDT").await.is_ok());
+                assert!(client.get_server_time().await.is_err());
+            });
+        });
+
+        let metrics = snapshotter.snapshot().into_vec();
+        let counter = |name: &str, label: (&str, &str)| -> u64 {
+            metrics
+                .iter()
+                .filter(|(key, ..)| key.kind() == MetricKind::Counter && key.key().name() == name)
+                .filter(|(key, ..)| {
+                    key.key().labels().any(|l| l.key() == label.0 && l.value() == label.1)
+                })
+                .map(|(.., value)| match value {
+                    DebugValue::Counter(n) => *n,
+                    _ => 0,
+                })
+                .sum()
+        };
+
+        assert_eq!(counter("binance.requests.total", ("status", "200")), 1);
+        assert_eq!(counter("binance.requests.total", ("status", "400")), 1);
+        assert_eq!(counter("binance.errors.total", ("kind", "api")), 1);
+        assert_eq!(counter("binance.errors.total", ("endpoint", "/api/v3/time")), 1);
+
+        let durations: usize = metrics
+            .iter()
+            .filter(|(key, ..)| key.key().name() == "binance.request.duration")
+            .map(|(.., value)| match value {
+                DebugValue::Histogram(samples) => samples.len(),
+                _ => 0,
+            })
+            .sum();
+        assert_eq!(durations, 2);
+    }
+}
@@ -8,28 +8,405 @@
 //! - Aggregate trade stream
 
 use crate::{
+    client::BinanceClient,
     config::BinanceConfig,
     endpoints::WebSocketStreams,
     error::{Error, Result},
-    models::{Interval, Kline, OrderBook, PriceLevel, Ticker, Ticker24h, Trade},
+    models::{
+        normalize_symbol, parse_f64_or_zero, AggTrade, Interval, Kline, MiniTicker, OrderBook,
+        PriceLevel, Ticker24h, Trade,
+    },
+    retry::BackoffPolicy,
 };
+use std::collections::{BTreeSet, HashMap};
 use chrono::{DateTime, Utc};
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use tokio::net::TcpStream;
-use tokio::sync::mpsc;
-use tokio::time::{sleep, Duration};
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio_tungstenite::{
-    connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream,
+    client_async_tls_with_config,
+    connect_async_tls_with_config,
+    tungstenite,
+    tungstenite::client::IntoClientRequest,
+    tungstenite::http::header::{HeaderValue, USER_AGENT},
+    tungstenite::protocol::CloseFrame,
+    tungstenite::Message,
+    MaybeTlsStream, WebSocketStream,
 };
 
-type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+pub use tokio_tungstenite::Connector;
+
+/// Default buffer size of stream channels
+const DEFAULT_STREAM_CAPACITY: usize = 100;
+
+/// Default wait for a SUBSCRIBE/UNSUBSCRIBE response
+const DEFAULT_ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default pause between a dropped connection and the next connection attempt
+const DEFAULT_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Minimum spacing of SUBSCRIBE/UNSUBSCRIBE frames on one connection
+///
+/// Binance disconnects clients that send more than 5 messages a second.
+const CONTROL_MESSAGE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Default streams per connection when sharding
+const DEFAULT_STREAMS_PER_CONNECTION: usize = 200;
+
+/// Most streams Binance allows on one connection
+const MAX_STREAMS_PER_CONNECTION: usize = 1024;
+
+/// Largest proxy response head accepted before a `CONNECT` tunnel opens
+const MAX_PROXY_RESPONSE_HEAD: usize = 8 * 1024;
+
+/// Shortest alphanumeric URL token treated as a listen key when logging
+/// (listen keys are 60 characters; stream names and hosts are far shorter)
+const LISTEN_KEY_MIN_LEN: usize = 32;
+
+/// Per-stream settings
+///
+/// Start from [`BinanceWebSocket::stream_config`] (which carries the
+/// defaults from [`BinanceConfig`]) and apply with
+/// [`BinanceWebSocket::with_stream_config`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamConfig {
+    /// Messages buffered before the stream task waits for the consumer (at least 1)
+    pub capacity: usize,
+    /// Reconnect if no message (including pings) arrives for this long
+    pub idle_timeout: Option<Duration>,
+    /// Backoff between connection attempts
+    pub backoff: BackoffPolicy,
+    /// Pause after a dropped connection before reconnecting
+    pub reconnect_delay: Duration,
+    /// How long multiplex subscribe/unsubscribe waits for the server's response
+    pub ack_timeout: Duration,
+    /// Streams opened per connection by
+    /// [`BinanceWebSocket::sharded_typed_stream`] (1 to 1024)
+    pub streams_per_connection: usize,
+}
+
+impl StreamConfig {
+    /// Defaults for `config`: 100 messages, no idle timeout, the config's
+    /// backoff, 5s reconnect delay, 5s ack timeout, 200 streams per
+    /// connection
+    pub fn from_config(config: &BinanceConfig) -> Self {
+        Self {
+            capacity: DEFAULT_STREAM_CAPACITY,
+            idle_timeout: None,
+            backoff: config.backoff,
+            reconnect_delay: DEFAULT_RECONNECT_DELAY,
+            ack_timeout: DEFAULT_ACK_TIMEOUT,
+            streams_per_connection: DEFAULT_STREAMS_PER_CONNECTION,
+        }
+    }
+
+    /// Set channel buffer size
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Set idle timeout
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set reconnect backoff
+    pub fn backoff(mut self, policy: BackoffPolicy) -> Self {
+        self.backoff = policy;
+        self
+    }
+
+    /// Set pause before reconnecting a dropped connection
+    pub fn reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    /// Set multiplex subscribe/unsubscribe ack timeout
+    pub fn ack_timeout(mut self, timeout: Duration) -> Self {
+        self.ack_timeout = timeout;
+        self
+    }
+
+    /// Set streams per connection when sharding (clamped to 1..=1024)
+    pub fn streams_per_connection(mut self, streams: usize) -> Self {
+        self.streams_per_connection = streams.clamp(1, MAX_STREAMS_PER_CONNECTION);
+        self
+    }
+}
+
+/// Everything a stream handler needs to (re)connect
+#[derive(Clone)]
+struct WsTarget {
+    url: String,
+    user_agent: String,
+    connector: Option<Connector>,
+    proxy: Option<String>,
+    backoff: BackoffPolicy,
+    reconnect_delay: Duration,
+    idle_timeout: Option<Duration>,
+}
+
+/// Connected socket that enforces the stream's idle timeout
+struct WsStream {
+    inner: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    idle_timeout: Option<Duration>,
+}
+
+impl WsStream {
+    /// Next frame; an idle timeout surfaces as an I/O error so handlers reconnect
+    async fn next(&mut self) -> Option<tungstenite::Result<Message>> {
+        let Some(idle) = self.idle_timeout else {
+            return self.inner.next().await;
+        };
+        
+        match tokio::time::timeout(idle, self.inner.next()).await {
+            Ok(frame) => frame,
+            Err(_) => Some(Err(tungstenite::Error::Io(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("no message for {:?}", idle),
+            )))),
+        }
+    }
+
+    async fn send(&mut self, msg: Message) -> tungstenite::Result<()> {
+        self.inner.send(msg).await
+    }
+}
+
+/// Handle to a background task with graceful shutdown
+///
+/// Dropping the handle leaves the task running; call [`StreamHandle::shutdown`]
+/// to stop it and wait for it to finish.
+pub struct StreamHandle {
+    shutdown_tx: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl StreamHandle {
+    /// Spawn a task that receives a shutdown signal receiver
+    pub(crate) fn spawn<F, Fut>(f: F) -> Self
+    where
+        F: FnOnce(watch::Receiver<bool>) -> Fut,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let task = tokio::spawn(f(shutdown_rx));
+        Self { shutdown_tx, task }
+    }
+
+    /// Signal the task to stop and wait for it to exit
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_tx.send(true);
+        let _ = self.task.await;
+    }
+
+    /// Check if the task has exited
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+/// Wait until shutdown is signalled on `shutdown`
+///
+/// A dropped sender means its handle was dropped, not that shutdown was
+/// requested, so this then never resolves and the task keeps running.
+pub(crate) async fn shutdown_signalled(shutdown: &mut watch::Receiver<bool>) {
+    if shutdown.changed().await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Subscribe/unsubscribe control for a multiplexed stream
+///
+/// Returned by [`BinanceWebSocket::multiplex_stream`]. Cloning shares the
+/// same connection. Dropping every handle stops further (un)subscriptions
+/// but leaves the data stream running.
+/// 
+/// Requests are queued and sent at most 5 per second, the most Binance
+/// accepts before closing the connection; time spent queued counts towards
+/// the ack timeout.
+/// 
+/// The handle tracks which streams are active. After a reconnect the
+/// connection starts empty and re-subscribes to all of them, reporting each
+/// as [`StreamEvent::Resubscribed`] once the server confirms.
+#[derive(Clone)]
+pub struct MultiplexHandle {
+    commands: mpsc::Sender<MultiplexCommand>,
+    next_id: Arc<AtomicU64>,
+    ack_timeout: Duration,
+    subscriptions: Arc<Mutex<BTreeSet<String>>>,
+}
+
+/// State the multiplex connection task shares with its handles
+struct MultiplexSession {
+    subscriptions: Arc<Mutex<BTreeSet<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+/// Control request sent to the multiplex connection task
+struct MultiplexCommand {
+    method: &'static str,
+    params: Vec<String>,
+    id: u64,
+    ack: oneshot::Sender<Result<()>>,
+}
+
+impl MultiplexHandle {
+    /// Add streams to the connection
+    /// 
+    /// Waits for the server to confirm; fails with
+    /// `WebSocketError("subscribe ack timeout")` if no response arrives within
+    /// the stream's `ack_timeout`.
+    pub async fn subscribe(&self, streams: &[&str]) -> Result<()> {
+        self.request("SUBSCRIBE", streams).await
+    }
+
+    /// Remove streams from the connection
+    /// 
+    /// Waits for confirmation like [`subscribe`](Self::subscribe).
+    pub async fn unsubscribe(&self, streams: &[&str]) -> Result<()> {
+        self.request("UNSUBSCRIBE", streams).await
+    }
+
+    /// Streams currently subscribed, sorted
+    pub fn subscriptions(&self) -> Vec<String> {
+        self.lock_subscriptions().iter().cloned().collect()
+    }
+
+    async fn request(&self, method: &'static str, streams: &[&str]) -> Result<()> {
+        let (ack, ack_rx) = oneshot::channel();
+        let command = MultiplexCommand {
+            method,
+            params: streams.iter().map(|s| s.to_string()).collect(),
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            ack,
+        };
+        
+        self.commands.send(command).await.map_err(|_| Error::WebSocketClosed)?;
+        
+        // On timeout the receiver is dropped, so a late ack is discarded
+        match tokio::time::timeout(self.ack_timeout, ack_rx).await {
+            Ok(Ok(result)) => {
+                result?;
+                let mut subscriptions = self.lock_subscriptions();
+                for stream in streams {
+                    if method == "SUBSCRIBE" {
+                        subscriptions.insert(stream.to_string());
+                    } else {
+                        subscriptions.remove(*stream);
+                    }
+                }
+                Ok(())
+            }
+            Ok(Err(_)) => Err(Error::WebSocketClosed),
+            Err(_) => Err(Error::WebSocketError(format!(
+                "{} ack timeout",
+                method.to_lowercase()
+            ))),
+        }
+    }
+
+    fn lock_subscriptions(&self) -> std::sync::MutexGuard<'_, BTreeSet<String>> {
+        // The set stays consistent even if a holder panicked
+        self.subscriptions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Typed market data event from a combined stream
+#[derive(Debug, Clone)]
+pub enum MarketEvent {
+    Ticker(Ticker24h),
+    MiniTicker(MiniTicker),
+    Kline(Kline),
+    Trade(Trade),
+    AggTrade(AggTrade),
+    Depth(OrderBook),
+}
+
+/// Item emitted by streams that report more than data
+/// 
+/// Besides data, these streams report their connection lifecycle: every
+/// session starts with `Connected`, and a dropped session is reported as
+/// `Disconnected` followed by `Reconnecting` before the next attempt.
+#[derive(Debug, Clone)]
+pub enum StreamEvent<T> {
+    /// A data message
+    Data(T),
+    /// Connection established (initially and after every reconnect)
+    Connected,
+    /// Connection lost; `reason` describes the close or error
+    Disconnected { reason: String },
+    /// About to reconnect; `attempt` counts reconnects since the last
+    /// successful connection, starting at 1
+    Reconnecting { attempt: u32 },
+    /// Sequenced stream skipped ahead (e.g. after a reconnect); consumers
+    /// maintaining state from diffs should resnapshot
+    Gap {
+        stream: String,
+        expected: i64,
+        got: i64,
+    },
+    /// Stream subscribed again on a new connection after a reconnect
+    Resubscribed { stream: String },
+}
+
+/// Raw message from a combined stream, tagged with its stream name
+#[derive(Debug, Clone, Deserialize)]
+pub struct CombinedMessage {
+    /// Stream name (e.g. `btcusdt@kline_1m`)
+    pub stream: String,
+    /// Event payload, undecoded
+    pub data: serde_json::Value,
+}
+
+impl CombinedMessage {
+    /// Symbol the stream belongs to, uppercased (`None` for all-market streams)
+    pub fn symbol(&self) -> Option<String> {
+        let (symbol, _) = self.stream.split_once('@')?;
+        (!symbol.starts_with('!')).then(|| symbol.to_uppercase())
+    }
+
+    /// Stream type without parameters (e.g. `kline`, `depth`, `aggTrade`)
+    pub fn event_type(&self) -> &str {
+        let kind = self.kind();
+        match kind.split_once('_') {
+            Some((base, _)) => base,
+            None if kind.starts_with("depth") => "depth",
+            None => kind,
+        }
+    }
+
+    /// Kline interval, for `<symbol>@kline_<interval>` streams
+    pub fn interval(&self) -> Option<Interval> {
+        self.kind().strip_prefix("kline_")?.parse().ok()
+    }
+
+    /// Stream type segment, e.g. `kline_1m` in `btcusdt@kline_1m`
+    fn kind(&self) -> &str {
+        let mut parts = self.stream.split('@');
+        let first = parts.next().unwrap_or_default();
+        match first.strip_prefix('!') {
+            Some(all_market) => all_market,
+            None => parts.next().unwrap_or_default(),
+        }
+    }
+}
 
 /// WebSocket connection manager
 #[derive(Clone)]
 pub struct BinanceWebSocket {
     config: Arc<BinanceConfig>,
+    connector: Option<Connector>,
+    stream_config: StreamConfig,
 }
 
 impl BinanceWebSocket {
@@ -37,10 +414,101 @@ impl BinanceWebSocket {
     pub fn new(config: BinanceConfig) -> Result<Self> {
         config.validate()?;
         Ok(Self {
+            stream_config: StreamConfig::from_config(&config),
             config: Arc::new(config),
+            connector: None,
         })
     }
 
+    /// Create WebSocket client with a custom TLS connector
+    /// 
+    /// Use this to pin certificates, add private roots or control SNI for
+    /// `wss://` endpoints. The connector is reused for every reconnect.
+    /// 
+    /// The connector only wraps the TCP stream in TLS; with
+    /// [`proxy_url`](BinanceConfig::proxy_url) set, TLS runs inside the
+    /// proxy tunnel.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceConfig, BinanceWebSocket};
+    /// use binance_connector::websocket::Connector;
+    /// 
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tls = native_tls::TlsConnector::builder().build()?;
+    /// let ws = BinanceWebSocket::with_connector(
+    ///     BinanceConfig::new(false),
+    ///     Connector::NativeTls(tls),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_connector(config: BinanceConfig, connector: Connector) -> Result<Self> {
+        let mut ws = Self::new(config)?;
+        ws.connector = Some(connector);
+        Ok(ws)
+    }
+
+    /// Settings applied to streams opened from this client
+    pub fn stream_config(&self) -> StreamConfig {
+        self.stream_config.clone()
+    }
+
+    /// Copy of this client whose streams use `stream_config`
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceConfig, BinanceWebSocket};
+    /// use std::time::Duration;
+    /// 
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let ws = BinanceWebSocket::new(BinanceConfig::new(false))?;
+    /// 
+    /// // Deep buffer for the busy trade tape
+    /// let trades = ws
+    ///     .with_stream_config(ws.stream_config().capacity(10_000))
+    ///     .trade_stream("BTCUSDT")
+    ///     .await?;
+    /// 
+    /// // Reconnect quickly if the depth stream goes quiet
+    /// let depth = ws
+    ///     .with_stream_config(ws.stream_config().idle_timeout(Duration::from_secs(5)))
+    ///     .depth_stream("BTCUSDT")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_stream_config(&self, stream_config: StreamConfig) -> Self {
+        Self {
+            stream_config,
+            ..self.clone()
+        }
+    }
+
+    fn target(&self, url: String) -> WsTarget {
+        WsTarget {
+            url,
+            user_agent: self.config.get_user_agent().to_string(),
+            connector: self.connector.clone(),
+            proxy: self.config.proxy_url.clone(),
+            backoff: self.stream_config.backoff,
+            reconnect_delay: self.stream_config.reconnect_delay,
+            idle_timeout: self.stream_config.idle_timeout,
+        }
+    }
+
+    /// Combined stream URL (`/stream?streams=a/b`) on the WebSocket host
+    fn combined_url(&self, streams: &[&str]) -> String {
+        let ws_url = self.config.get_ws_url();
+        let base = ws_url.strip_suffix("/ws").unwrap_or(&ws_url);
+        
+        if streams.is_empty() {
+            format!("{}/stream", base)
+        } else {
+            format!("{}/stream?streams={}", base, streams.join("/"))
+        }
+    }
+
     /// Stream real-time ticker updates for a symbol
     /// 
     /// # Arguments
@@ -72,14 +540,15 @@ impl BinanceWebSocket {
         &self,
         symbol: &str,
     ) -> Result<mpsc::Receiver<Result<Ticker24h>>> {
+        let symbol = &normalize_symbol(symbol);
         let stream_name = WebSocketStreams::ticker(symbol);
-        let url = format!("{}/{}", self.config.get_ws_url(), stream_name);
+        let target = self.target(format!("{}/{}", self.config.get_ws_url(), stream_name));
         
-        let (tx, rx) = mpsc::channel(100);
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
         let symbol = symbol.to_string();
         
         tokio::spawn(async move {
-            if let Err(e) = Self::ticker_stream_handler(url, symbol, tx.clone()).await {
+            if let Err(e) = Self::ticker_stream_handler(target, symbol, tx.clone()).await {
                 let _ = tx.send(Err(e)).await;
             }
         });
@@ -92,6 +561,8 @@ impl BinanceWebSocket {
     /// # Arguments
     /// * `symbol` - Trading pair (e.g., "BTCUSDT")
     /// * `interval` - Candlestick interval
+    /// * `timezone` - UTC offset for candle boundaries (e.g. "+08:00"), `None`
+    ///   for UTC
     /// 
     /// # Example
     /// ```no_run
@@ -103,7 +574,7 @@ impl BinanceWebSocket {
     ///     let config = BinanceConfig::new(false);
     ///     let ws = BinanceWebSocket::new(config)?;
     ///     
-    ///     let mut stream = ws.kline_stream("BTCUSDT", Interval::Minutes1).await?;
+    ///     let mut stream = ws.kline_stream("BTCUSDT", Interval::Minutes1, None).await?;
     ///     
     ///     while let Some(result) = stream.recv().await {
     ///         match result {
@@ -123,15 +594,17 @@ impl BinanceWebSocket {
         &self,
         symbol: &str,
         interval: Interval,
+        timezone: impl Into<Option<&str>>,
     ) -> Result<mpsc::Receiver<Result<Kline>>> {
-        let stream_name = WebSocketStreams::kline(symbol, &interval.to_string());
-        let url = format!("{}/{}", self.config.get_ws_url(), stream_name);
+        let symbol = &normalize_symbol(symbol);
+        let stream_name = WebSocketStreams::kline(symbol, &interval.to_string(), timezone.into());
+        let target = self.target(format!("{}/{}", self.config.get_ws_url(), stream_name));
         
-        let (tx, rx) = mpsc::channel(100);
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
         let symbol = symbol.to_string();
         
         tokio::spawn(async move {
-            if let Err(e) = Self::kline_stream_handler(url, symbol, tx.clone()).await {
+            if let Err(e) = Self::kline_stream_handler(target, symbol, tx.clone()).await {
                 let _ = tx.send(Err(e)).await;
             }
         });
@@ -144,14 +617,15 @@ impl BinanceWebSocket {
     /// # Arguments
     /// * `symbol` - Trading pair (e.g., "BTCUSDT")
     pub async fn trade_stream(&self, symbol: &str) -> Result<mpsc::Receiver<Result<Trade>>> {
+        let symbol = &normalize_symbol(symbol);
         let stream_name = WebSocketStreams::trade(symbol);
-        let url = format!("{}/{}", self.config.get_ws_url(), stream_name);
+        let target = self.target(format!("{}/{}", self.config.get_ws_url(), stream_name));
         
-        let (tx, rx) = mpsc::channel(100);
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
         let symbol = symbol.to_string();
         
         tokio::spawn(async move {
-            if let Err(e) = Self::trade_stream_handler(url, symbol, tx.clone()).await {
+            if let Err(e) = Self::trade_stream_handler(target, symbol, tx.clone()).await {
                 let _ = tx.send(Err(e)).await;
             }
         });
@@ -159,19 +633,127 @@ impl BinanceWebSocket {
         Ok(rx)
     }
 
+    /// Stream trades in batches
+    /// 
+    /// Collects trades from [`trade_stream`](Self::trade_stream) and emits
+    /// them once `batch_size` have arrived or `max_delay` has passed since
+    /// the first trade of the batch, whichever comes first. An error flushes
+    /// the pending batch before it is forwarded.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Trading pair (e.g., "BTCUSDT")
+    /// * `batch_size` - Maximum trades per batch (at least 1)
+    /// * `max_delay` - Longest a trade waits before its batch is emitted
+    pub async fn trade_stream_batched(
+        &self,
+        symbol: &str,
+        batch_size: usize,
+        max_delay: Duration,
+    ) -> Result<mpsc::Receiver<Result<Vec<Trade>>>> {
+        let mut trades = self.trade_stream(symbol).await?;
+        let batch_size = batch_size.max(1);
+        
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
+        
+        tokio::spawn(async move {
+            let mut batch = Vec::with_capacity(batch_size);
+            let mut deadline = None;
+            
+            loop {
+                // None once the batch's max_delay has elapsed
+                let item = match deadline {
+                    Some(at) => tokio::time::timeout_at(at, trades.recv()).await.ok(),
+                    None => Some(trades.recv().await),
+                };
+                
+                let error = match item {
+                    Some(Some(Ok(trade))) => {
+                        if batch.is_empty() {
+                            deadline = Some(Instant::now() + max_delay);
+                        }
+                        batch.push(trade);
+                        if batch.len() < batch_size {
+                            continue;
+                        }
+                        None
+                    }
+                    Some(Some(Err(e))) => Some(e),
+                    Some(None) => {
+                        if !batch.is_empty() {
+                            let _ = tx.send(Ok(batch)).await;
+                        }
+                        return;
+                    }
+                    None => None,
+                };
+                
+                deadline = None;
+                if !batch.is_empty() && tx.send(Ok(std::mem::take(&mut batch))).await.is_err() {
+                    return;
+                }
+                if let Some(e) = error {
+                    if tx.send(Err(e)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        
+        Ok(rx)
+    }
+
     /// Stream order book depth updates
     /// 
     /// # Arguments
     /// * `symbol` - Trading pair (e.g., "BTCUSDT")
     pub async fn depth_stream(&self, symbol: &str) -> Result<mpsc::Receiver<Result<OrderBook>>> {
+        let symbol = &normalize_symbol(symbol);
+        let stream_name = WebSocketStreams::depth(symbol);
+        let target = self.target(format!("{}/{}", self.config.get_ws_url(), stream_name));
+        
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
+        let symbol = symbol.to_string();
+        
+        tokio::spawn(async move {
+            if let Err(e) = Self::depth_stream_handler(target, symbol, tx.clone()).await {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+        
+        Ok(rx)
+    }
+
+    /// Stream a locally maintained order book
+    /// 
+    /// Fetches a REST snapshot, then applies diff depth updates to it,
+    /// emitting the full book after each update. The book is re-synced
+    /// automatically if an update is missed.
+    /// 
+    /// # Arguments
+    /// * `client` - REST client that fetches the snapshots; re-syncs count
+    ///   against its rate limiter like any other request
+    /// * `symbol` - Trading pair (e.g., "BTCUSDT")
+    /// * `max_levels` - Keep only the best N levels per side (None keeps all).
+    ///   Levels dropped by truncation are not restored, so a side may hold
+    ///   fewer than N levels after removals near the top of the book.
+    pub async fn managed_order_book(
+        &self,
+        client: &BinanceClient,
+        symbol: &str,
+        max_levels: Option<usize>,
+    ) -> Result<mpsc::Receiver<Result<OrderBook>>> {
+        let symbol = &normalize_symbol(symbol);
         let stream_name = WebSocketStreams::depth(symbol);
-        let url = format!("{}/{}", self.config.get_ws_url(), stream_name);
+        let target = self.target(format!("{}/{}", self.config.get_ws_url(), stream_name));
+        let client = client.clone();
         
-        let (tx, rx) = mpsc::channel(100);
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
         let symbol = symbol.to_string();
         
         tokio::spawn(async move {
-            if let Err(e) = Self::depth_stream_handler(url, symbol, tx.clone()).await {
+            if let Err(e) =
+                Self::managed_book_handler(target, client, symbol, max_levels, None, tx.clone()).await
+            {
                 let _ = tx.send(Err(e)).await;
             }
         });
@@ -179,6 +761,65 @@ impl BinanceWebSocket {
         Ok(rx)
     }
 
+    /// Stream a managed order book, emitting only meaningful changes
+    /// 
+    /// Every diff is still applied as in
+    /// [`managed_order_book`](Self::managed_order_book), but the book is
+    /// emitted at most once per `min_interval`; updates in between are
+    /// coalesced into the latest state. With `only_top_n_levels`, books whose
+    /// best N bids and asks match the last emitted book are skipped. Errors
+    /// are forwarded immediately.
+    /// 
+    /// # Arguments
+    /// * `client` - REST client that fetches the snapshots
+    /// * `symbol` - Trading pair (e.g., "BTCUSDT")
+    /// * `min_interval` - Shortest time between two emitted books
+    /// * `only_top_n_levels` - Emit only when the best N levels per side change
+    pub async fn depth_stream_throttled(
+        &self,
+        client: &BinanceClient,
+        symbol: &str,
+        min_interval: Duration,
+        only_top_n_levels: Option<usize>,
+    ) -> Result<mpsc::Receiver<Result<OrderBook>>> {
+        let books = self.managed_order_book(client, symbol, None).await?;
+        Ok(throttle_books(books, min_interval, only_top_n_levels, self.stream_config.capacity))
+    }
+
+    /// Snapshot an order book and keep it updated
+    /// 
+    /// Backs [`BinanceClient::live_order_book`]: the stream is connected and
+    /// the snapshot taken before returning, and the same session then feeds
+    /// the managed book so no update between the two is lost.
+    pub(crate) async fn live_order_book(
+        &self,
+        client: BinanceClient,
+        symbol: &str,
+        max_levels: Option<usize>,
+    ) -> Result<(OrderBook, mpsc::Receiver<Result<OrderBook>>)> {
+        let symbol = &normalize_symbol(symbol);
+        let stream_name = WebSocketStreams::depth(symbol);
+        let target = self.target(format!("{}/{}", self.config.get_ws_url(), stream_name));
+        
+        let (ws_stream, sync) =
+            Self::open_managed_book(&target, &client, symbol, max_levels).await?;
+        let snapshot = sync.book.clone();
+        
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
+        let symbol = symbol.to_string();
+        
+        tokio::spawn(async move {
+            let session = Some((ws_stream, sync));
+            if let Err(e) =
+                Self::managed_book_handler(target, client, symbol, max_levels, session, tx.clone()).await
+            {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+        
+        Ok((snapshot, rx))
+    }
+
     /// Stream mini ticker (lightweight ticker updates)
     /// 
     /// # Arguments
@@ -186,15 +827,16 @@ impl BinanceWebSocket {
     pub async fn mini_ticker_stream(
         &self,
         symbol: &str,
-    ) -> Result<mpsc::Receiver<Result<Ticker>>> {
+    ) -> Result<mpsc::Receiver<Result<MiniTicker>>> {
+        let symbol = &normalize_symbol(symbol);
         let stream_name = WebSocketStreams::mini_ticker(symbol);
-        let url = format!("{}/{}", self.config.get_ws_url(), stream_name);
+        let target = self.target(format!("{}/{}", self.config.get_ws_url(), stream_name));
         
-        let (tx, rx) = mpsc::channel(100);
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
         let symbol = symbol.to_string();
         
         tokio::spawn(async move {
-            if let Err(e) = Self::mini_ticker_stream_handler(url, symbol, tx.clone()).await {
+            if let Err(e) = Self::mini_ticker_stream_handler(target, symbol, tx.clone()).await {
                 let _ = tx.send(Err(e)).await;
             }
         });
@@ -234,13 +876,12 @@ impl BinanceWebSocket {
         &self,
         streams: &[&str],
     ) -> Result<mpsc::Receiver<Result<String>>> {
-        let streams_param = streams.join("/");
-        let url = format!("{}/stream?streams={}", self.config.get_ws_url(), streams_param);
+        let target = self.target(self.combined_url(streams));
         
-        let (tx, rx) = mpsc::channel(100);
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
         
         tokio::spawn(async move {
-            if let Err(e) = Self::raw_stream_handler(url, tx.clone()).await {
+            if let Err(e) = Self::raw_stream_handler(target, tx.clone()).await {
                 let _ = tx.send(Err(e)).await;
             }
         });
@@ -248,78 +889,304 @@ impl BinanceWebSocket {
         Ok(rx)
     }
 
-    // ============================================================
-    // PRIVATE STREAM HANDLERS
-    // ============================================================
-
-    async fn ticker_stream_handler(
-        url: String,
-        symbol: String,
-        tx: mpsc::Sender<Result<Ticker24h>>,
-    ) -> Result<()> {
-        loop {
-            match Self::connect_with_retry(&url).await {
-                Ok(ws_stream) => {
-                    if let Err(e) = Self::handle_ticker_messages(ws_stream, &symbol, &tx).await {
-                        let _ = tx.send(Err(e)).await;
-                    }
-                }
-                Err(e) => {
-                    let _ = tx.send(Err(e)).await;
-                }
-            }
-            
-            // Reconnect after delay
-            sleep(Duration::from_secs(5)).await;
-        }
-    }
-
-    async fn handle_ticker_messages(
-        mut ws_stream: WsStream,
-        symbol: &str,
-        tx: &mpsc::Sender<Result<Ticker24h>>,
-    ) -> Result<()> {
-        while let Some(msg) = ws_stream.next().await {
-            match msg {
-                Ok(Message::Text(text)) => {
-                    match serde_json::from_str::<WsTickerData>(&text) {
-                        Ok(data) => {
-                            let ticker = data.to_ticker24h()?;
-                            if tx.send(Ok(ticker)).await.is_err() {
-                                return Ok(()); // Channel closed
-                            }
-                        }
-                        Err(e) => {
-                            let _ = tx.send(Err(Error::DeserializationError(e.to_string()))).await;
-                        }
-                    }
-                }
-                Ok(Message::Ping(data)) => {
-                    ws_stream.send(Message::Pong(data)).await
-                        .map_err(|e| Error::WebSocketError(e.to_string()))?;
-                }
-                Ok(Message::Close(_)) => {
-                    return Err(Error::WebSocketClosed);
-                }
-                Err(e) => {
-                    return Err(Error::WebSocketError(e.to_string()));
-                }
-                _ => {}
+    /// Stream multiple streams combined, tagged with their stream name
+    /// 
+    /// A lighter alternative to [`combined_typed_stream`](Self::combined_typed_stream):
+    /// payloads are left as JSON, and [`CombinedMessage`] helpers tell which
+    /// symbol and stream type each message belongs to.
+    /// 
+    /// # Arguments
+    /// * `streams` - List of stream names (e.g., ["btcusdt@kline_1m", "ethusdt@trade"])
+    pub async fn combined_stream_messages(
+        &self,
+        streams: &[&str],
+    ) -> Result<mpsc::Receiver<Result<CombinedMessage>>> {
+        let target = self.target(self.combined_url(streams));
+        
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
+        
+        tokio::spawn(async move {
+            if let Err(e) = Self::combined_messages_handler(target, tx.clone()).await {
+                let _ = tx.send(Err(e)).await;
             }
-        }
+        });
         
-        Err(Error::WebSocketClosed)
+        Ok(rx)
     }
 
-    async fn kline_stream_handler(
-        url: String,
-        symbol: String,
-        tx: mpsc::Sender<Result<Kline>>,
-    ) -> Result<()> {
-        loop {
-            match Self::connect_with_retry(&url).await {
-                Ok(ws_stream) => {
-                    if let Err(e) = Self::handle_kline_messages(ws_stream, &symbol, &tx).await {
+    /// Open a combined stream whose subscriptions can change at runtime
+    /// 
+    /// Starts with `streams` (may be empty) and returns a [`MultiplexHandle`]
+    /// to subscribe to or unsubscribe from streams on the same connection,
+    /// plus the receiver of tagged messages from all of them.
+    /// 
+    /// After a reconnect the active subscriptions are restored and each
+    /// restored stream is reported as [`StreamEvent::Resubscribed`].
+    /// Connection changes are reported as lifecycle events (see
+    /// [`StreamEvent`]).
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceConfig, BinanceWebSocket, StreamEvent};
+    /// 
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let ws = BinanceWebSocket::new(BinanceConfig::new(false))?;
+    /// let (handle, mut messages) = ws.multiplex_stream(&["btcusdt@trade"]).await?;
+    /// 
+    /// handle.subscribe(&["ethusdt@trade"]).await?;
+    /// while let Some(Ok(event)) = messages.recv().await {
+    ///     match event {
+    ///         StreamEvent::Data(msg) => println!("{:?} {}", msg.symbol(), msg.event_type()),
+    ///         StreamEvent::Resubscribed { stream } => println!("restored {}", stream),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn multiplex_stream(
+        &self,
+        streams: &[&str],
+    ) -> Result<(MultiplexHandle, mpsc::Receiver<Result<StreamEvent<CombinedMessage>>>)> {
+        let target = self.target(self.combined_url(streams));
+        // Reconnects start empty and re-subscribe to the tracked set, so
+        // initial streams that were unsubscribed stay gone
+        let reconnect_target = self.target(self.combined_url(&[]));
+        
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
+        let (commands_tx, commands_rx) = mpsc::channel(16);
+        let handle = MultiplexHandle {
+            commands: commands_tx,
+            next_id: Arc::new(AtomicU64::new(1)),
+            ack_timeout: self.stream_config.ack_timeout,
+            subscriptions: Arc::new(Mutex::new(
+                streams.iter().map(|s| s.to_string()).collect(),
+            )),
+        };
+        let session = MultiplexSession {
+            subscriptions: handle.subscriptions.clone(),
+            next_id: handle.next_id.clone(),
+        };
+        
+        tokio::spawn(async move {
+            if let Err(e) = Self::multiplex_handler(
+                target,
+                reconnect_target,
+                session,
+                commands_rx,
+                tx.clone(),
+            )
+            .await
+            {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+        
+        Ok((handle, rx))
+    }
+
+    /// Stream typed events from multiple streams over one connection
+    /// 
+    /// Like [`combined_stream`](Self::combined_stream), but decodes each
+    /// message into a [`MarketEvent`]. Streams carrying monotonic ids (diff
+    /// depth and aggregate trades) are checked for continuity across
+    /// reconnects: replayed events are dropped and skipped ranges are
+    /// reported as [`StreamEvent::Gap`] before the next event. Connection
+    /// changes are reported as lifecycle events (see [`StreamEvent`]).
+    /// 
+    /// # Arguments
+    /// * `streams` - List of stream names (e.g., ["btcusdt@depth", "ethusdt@aggTrade"])
+    pub async fn combined_typed_stream(
+        &self,
+        streams: &[&str],
+    ) -> Result<mpsc::Receiver<Result<StreamEvent<MarketEvent>>>> {
+        let target = self.target(self.combined_url(streams));
+        
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
+        
+        tokio::spawn(async move {
+            if let Err(e) = Self::combined_typed_stream_handler(target, tx.clone()).await {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+        
+        Ok(rx)
+    }
+
+    /// Stream klines of every interval for a symbol over one connection
+    /// 
+    /// Subscribes to one kline stream per [`Interval`] through
+    /// [`combined_typed_stream`](Self::combined_typed_stream) and forwards
+    /// only the klines; their `interval` field tells the timeframes apart.
+    /// `1s` candles are left out on Binance.US, which does not offer them.
+    /// 
+    /// # Arguments
+    /// * `symbol` - Trading pair (e.g., "BTCUSDT")
+    pub async fn all_intervals_kline_stream(
+        &self,
+        symbol: &str,
+    ) -> Result<mpsc::Receiver<Result<Kline>>> {
+        let seconds_supported = !self.config.get_ws_url().contains("binance.us");
+        let intervals: Vec<Interval> = Interval::ALL
+            .into_iter()
+            .filter(|&interval| seconds_supported || interval != Interval::Seconds1)
+            .collect();
+        let streams = WebSocketStreams::klines(symbol, &intervals);
+        let streams: Vec<&str> = streams.iter().map(String::as_str).collect();
+        let mut events = self.combined_typed_stream(&streams).await?;
+        
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
+        
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                let item = match event {
+                    Ok(StreamEvent::Data(MarketEvent::Kline(kline))) => Ok(kline),
+                    Ok(_) => continue,
+                    Err(e) => Err(e),
+                };
+                if tx.send(item).await.is_err() {
+                    return;
+                }
+            }
+        });
+        
+        Ok(rx)
+    }
+
+    /// Stream typed events from many streams spread over several connections
+    /// 
+    /// Binance caps a connection at 1024 streams, so `streams` is split into
+    /// shards of `stream_config().streams_per_connection` (200 by default),
+    /// each opened like [`combined_typed_stream`](Self::combined_typed_stream)
+    /// and reconnecting on its own. Events from all shards are merged into
+    /// one receiver; lifecycle events (see [`StreamEvent`]) are reported once
+    /// per shard.
+    /// 
+    /// Returns the number of shards (connections) opened and the receiver.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use binance_connector::{BinanceConfig, BinanceWebSocket};
+    /// 
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let ws = BinanceWebSocket::new(BinanceConfig::new(false))?;
+    /// let names: Vec<String> = ["btcusdt", "ethusdt", "bnbusdt"]
+    ///     .iter()
+    ///     .map(|s| format!("{}@aggTrade", s))
+    ///     .collect();
+    /// let streams: Vec<&str> = names.iter().map(String::as_str).collect();
+    /// 
+    /// let (shards, mut events) = ws.sharded_typed_stream(&streams).await?;
+    /// println!("{} connections", shards);
+    /// while let Some(event) = events.recv().await {
+    ///     println!("{:?}", event?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sharded_typed_stream(
+        &self,
+        streams: &[&str],
+    ) -> Result<(usize, mpsc::Receiver<Result<StreamEvent<MarketEvent>>>)> {
+        let shards = shard_streams(streams, self.stream_config.streams_per_connection);
+        
+        let (tx, rx) = mpsc::channel(self.stream_config.capacity);
+        
+        for shard in &shards {
+            let target = self.target(self.combined_url(shard));
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                if let Err(e) = Self::combined_typed_stream_handler(target, tx.clone()).await {
+                    let _ = tx.send(Err(e)).await;
+                }
+            });
+        }
+        
+        Ok((shards.len(), rx))
+    }
+
+    // ============================================================
+    // PRIVATE STREAM HANDLERS
+    // ============================================================
+
+    async fn ticker_stream_handler(
+        target: WsTarget,
+        symbol: String,
+        tx: mpsc::Sender<Result<Ticker24h>>,
+    ) -> Result<()> {
+        let mut attempt = 0;
+        
+        loop {
+            match Self::connect_with_retry(&target).await {
+                Ok(ws_stream) => {
+                    attempt = 0;
+                    if let Err(e) = Self::handle_ticker_messages(ws_stream, &symbol, &tx).await {
+                        let _ = tx.send(Err(e)).await;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e)).await;
+                }
+            }
+            
+            attempt += 1;
+            if !Self::reconnect_pause(&target, &tx, attempt).await {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn handle_ticker_messages(
+        mut ws_stream: WsStream,
+        _symbol: &str,
+        tx: &mpsc::Sender<Result<Ticker24h>>,
+    ) -> Result<()> {
+        while let Some(msg) = ws_stream.next().await {
+            match msg {
+                Ok(Message::Text(text)) => {
+                    match serde_json::from_str::<WsTickerData>(&text) {
+                        Ok(data) => {
+                            let ticker = data.to_ticker24h()?;
+                            if tx.send(Ok(ticker)).await.is_err() {
+                                return Ok(()); // Channel closed
+                            }
+                        }
+                        Err(e) => {
+                            if let Some(err) = frame_error(&text, "24hrTicker", e) {
+                                let _ = tx.send(Err(err)).await;
+                            }
+                        }
+                    }
+                }
+                Ok(Message::Ping(data)) => {
+                    ws_stream.send(Message::Pong(data)).await
+                        .map_err(|e| Error::WebSocketError(e.to_string()))?;
+                }
+                Ok(Message::Close(frame)) => {
+                    return Err(close_error(frame));
+                }
+                Err(e) => {
+                    return Err(Error::WebSocketError(e.to_string()));
+                }
+                _ => {}
+            }
+        }
+        
+        Err(Error::WebSocketClosed)
+    }
+
+    async fn kline_stream_handler(
+        target: WsTarget,
+        symbol: String,
+        tx: mpsc::Sender<Result<Kline>>,
+    ) -> Result<()> {
+        let mut attempt = 0;
+        
+        loop {
+            match Self::connect_with_retry(&target).await {
+                Ok(ws_stream) => {
+                    attempt = 0;
+                    if let Err(e) = Self::handle_kline_messages(ws_stream, &symbol, &tx).await {
                         let _ = tx.send(Err(e)).await;
                     }
                 }
@@ -328,7 +1195,10 @@ impl BinanceWebSocket {
                 }
             }
             
-            sleep(Duration::from_secs(5)).await;
+            attempt += 1;
+            if !Self::reconnect_pause(&target, &tx, attempt).await {
+                return Ok(());
+            }
         }
     }
 
@@ -348,7 +1218,9 @@ impl BinanceWebSocket {
                             }
                         }
                         Err(e) => {
-                            let _ = tx.send(Err(Error::DeserializationError(e.to_string()))).await;
+                            if let Some(err) = frame_error(&text, "kline", e) {
+                                let _ = tx.send(Err(err)).await;
+                            }
                         }
                     }
                 }
@@ -356,8 +1228,8 @@ impl BinanceWebSocket {
                     ws_stream.send(Message::Pong(data)).await
                         .map_err(|e| Error::WebSocketError(e.to_string()))?;
                 }
-                Ok(Message::Close(_)) => {
-                    return Err(Error::WebSocketClosed);
+                Ok(Message::Close(frame)) => {
+                    return Err(close_error(frame));
                 }
                 Err(e) => {
                     return Err(Error::WebSocketError(e.to_string()));
@@ -370,13 +1242,16 @@ impl BinanceWebSocket {
     }
 
     async fn trade_stream_handler(
-        url: String,
+        target: WsTarget,
         symbol: String,
         tx: mpsc::Sender<Result<Trade>>,
     ) -> Result<()> {
+        let mut attempt = 0;
+        
         loop {
-            match Self::connect_with_retry(&url).await {
+            match Self::connect_with_retry(&target).await {
                 Ok(ws_stream) => {
+                    attempt = 0;
                     if let Err(e) = Self::handle_trade_messages(ws_stream, &symbol, &tx).await {
                         let _ = tx.send(Err(e)).await;
                     }
@@ -386,7 +1261,10 @@ impl BinanceWebSocket {
                 }
             }
             
-            sleep(Duration::from_secs(5)).await;
+            attempt += 1;
+            if !Self::reconnect_pause(&target, &tx, attempt).await {
+                return Ok(());
+            }
         }
     }
 
@@ -406,7 +1284,9 @@ impl BinanceWebSocket {
                             }
                         }
                         Err(e) => {
-                            let _ = tx.send(Err(Error::DeserializationError(e.to_string()))).await;
+                            if let Some(err) = frame_error(&text, "trade", e) {
+                                let _ = tx.send(Err(err)).await;
+                            }
                         }
                     }
                 }
@@ -414,8 +1294,8 @@ impl BinanceWebSocket {
                     ws_stream.send(Message::Pong(data)).await
                         .map_err(|e| Error::WebSocketError(e.to_string()))?;
                 }
-                Ok(Message::Close(_)) => {
-                    return Err(Error::WebSocketClosed);
+                Ok(Message::Close(frame)) => {
+                    return Err(close_error(frame));
                 }
                 Err(e) => {
                     return Err(Error::WebSocketError(e.to_string()));
@@ -428,13 +1308,16 @@ impl BinanceWebSocket {
     }
 
     async fn depth_stream_handler(
-        url: String,
+        target: WsTarget,
         symbol: String,
         tx: mpsc::Sender<Result<OrderBook>>,
     ) -> Result<()> {
+        let mut attempt = 0;
+        
         loop {
-            match Self::connect_with_retry(&url).await {
+            match Self::connect_with_retry(&target).await {
                 Ok(ws_stream) => {
+                    attempt = 0;
                     if let Err(e) = Self::handle_depth_messages(ws_stream, &symbol, &tx).await {
                         let _ = tx.send(Err(e)).await;
                     }
@@ -444,7 +1327,10 @@ impl BinanceWebSocket {
                 }
             }
             
-            sleep(Duration::from_secs(5)).await;
+            attempt += 1;
+            if !Self::reconnect_pause(&target, &tx, attempt).await {
+                return Ok(());
+            }
         }
     }
 
@@ -464,7 +1350,9 @@ impl BinanceWebSocket {
                             }
                         }
                         Err(e) => {
-                            let _ = tx.send(Err(Error::DeserializationError(e.to_string()))).await;
+                            if let Some(err) = frame_error(&text, "depthUpdate", e) {
+                                let _ = tx.send(Err(err)).await;
+                            }
                         }
                     }
                 }
@@ -472,8 +1360,8 @@ impl BinanceWebSocket {
                     ws_stream.send(Message::Pong(data)).await
                         .map_err(|e| Error::WebSocketError(e.to_string()))?;
                 }
-                Ok(Message::Close(_)) => {
-                    return Err(Error::WebSocketClosed);
+                Ok(Message::Close(frame)) => {
+                    return Err(close_error(frame));
                 }
                 Err(e) => {
                     return Err(Error::WebSocketError(e.to_string()));
@@ -485,15 +1373,27 @@ impl BinanceWebSocket {
         Err(Error::WebSocketClosed)
     }
 
-    async fn mini_ticker_stream_handler(
-        url: String,
+    async fn managed_book_handler(
+        target: WsTarget,
+        client: BinanceClient,
         symbol: String,
-        tx: mpsc::Sender<Result<Ticker>>,
+        max_levels: Option<usize>,
+        mut session: Option<(WsStream, OrderBookSync)>,
+        tx: mpsc::Sender<Result<OrderBook>>,
     ) -> Result<()> {
+        let mut attempt = 0;
+        
         loop {
-            match Self::connect_with_retry(&url).await {
-                Ok(ws_stream) => {
-                    if let Err(e) = Self::handle_mini_ticker_messages(ws_stream, &symbol, &tx).await {
+            let opened = match session.take() {
+                Some(session) => Ok(session),
+                None => Self::open_managed_book(&target, &client, &symbol, max_levels).await,
+            };
+            
+            match opened {
+                Ok((ws_stream, sync)) => {
+                    attempt = 0;
+                    let result = Self::handle_managed_book_messages(ws_stream, sync, &tx).await;
+                    if let Err(e) = result {
                         let _ = tx.send(Err(e)).await;
                     }
                 }
@@ -502,27 +1402,48 @@ impl BinanceWebSocket {
                 }
             }
             
-            sleep(Duration::from_secs(5)).await;
+            attempt += 1;
+            if !Self::reconnect_pause(&target, &tx, attempt).await {
+                return Ok(());
+            }
         }
     }
 
-    async fn handle_mini_ticker_messages(
-        mut ws_stream: WsStream,
+    /// Connect the diff stream, then take the REST snapshot to sync against
+    async fn open_managed_book(
+        target: &WsTarget,
+        client: &BinanceClient,
         symbol: &str,
-        tx: &mpsc::Sender<Result<Ticker>>,
+        max_levels: Option<usize>,
+    ) -> Result<(WsStream, OrderBookSync)> {
+        let ws_stream = Self::connect_with_retry(target).await?;
+        
+        // Diffs arriving while the snapshot is fetched stay buffered in the socket
+        let snapshot = client.get_depth(symbol, MANAGED_BOOK_SNAPSHOT_DEPTH).await?;
+        
+        Ok((ws_stream, OrderBookSync::new(snapshot, max_levels)))
+    }
+
+    async fn handle_managed_book_messages(
+        mut ws_stream: WsStream,
+        mut sync: OrderBookSync,
+        tx: &mpsc::Sender<Result<OrderBook>>,
     ) -> Result<()> {
         while let Some(msg) = ws_stream.next().await {
             match msg {
                 Ok(Message::Text(text)) => {
-                    match serde_json::from_str::<WsMiniTickerData>(&text) {
+                    match serde_json::from_str::<WsDepthData>(&text) {
                         Ok(data) => {
-                            let ticker = data.to_ticker();
-                            if tx.send(Ok(ticker)).await.is_err() {
-                                return Ok(());
+                            if let Some(book) = sync.apply(&data)? {
+                                if tx.send(Ok(book.clone())).await.is_err() {
+                                    return Ok(());
+                                }
                             }
                         }
                         Err(e) => {
-                            let _ = tx.send(Err(Error::DeserializationError(e.to_string()))).await;
+                            if let Some(err) = frame_error(&text, "depthUpdate", e) {
+                                let _ = tx.send(Err(err)).await;
+                            }
                         }
                     }
                 }
@@ -530,8 +1451,8 @@ impl BinanceWebSocket {
                     ws_stream.send(Message::Pong(data)).await
                         .map_err(|e| Error::WebSocketError(e.to_string()))?;
                 }
-                Ok(Message::Close(_)) => {
-                    return Err(Error::WebSocketClosed);
+                Ok(Message::Close(frame)) => {
+                    return Err(close_error(frame));
                 }
                 Err(e) => {
                     return Err(Error::WebSocketError(e.to_string()));
@@ -543,34 +1464,19 @@ impl BinanceWebSocket {
         Err(Error::WebSocketClosed)
     }
 
-    async fn raw_stream_handler(
-        url: String,
-        tx: mpsc::Sender<Result<String>>,
+    async fn mini_ticker_stream_handler(
+        target: WsTarget,
+        symbol: String,
+        tx: mpsc::Sender<Result<MiniTicker>>,
     ) -> Result<()> {
+        let mut attempt = 0;
+        
         loop {
-            match Self::connect_with_retry(&url).await {
-                Ok(mut ws_stream) => {
-                    while let Some(msg) = ws_stream.next().await {
-                        match msg {
-                            Ok(Message::Text(text)) => {
-                                if tx.send(Ok(text.to_string())).await.is_err() {
-                                    return Ok(());
-                                }
-                            }
-                            Ok(Message::Ping(data)) => {
-                                ws_stream.send(Message::Pong(data)).await
-                                    .map_err(|e| Error::WebSocketError(e.to_string()))?;
-                            }
-                            Ok(Message::Close(_)) => {
-                                let _ = tx.send(Err(Error::WebSocketClosed)).await;
-                                break;
-                            }
-                            Err(e) => {
-                                let _ = tx.send(Err(Error::WebSocketError(e.to_string()))).await;
-                                break;
-                            }
-                            _ => {}
-                        }
+            match Self::connect_with_retry(&target).await {
+                Ok(ws_stream) => {
+                    attempt = 0;
+                    if let Err(e) = Self::handle_mini_ticker_messages(ws_stream, &symbol, &tx).await {
+                        let _ = tx.send(Err(e)).await;
                     }
                 }
                 Err(e) => {
@@ -578,49 +1484,710 @@ impl BinanceWebSocket {
                 }
             }
             
-            sleep(Duration::from_secs(5)).await;
+            attempt += 1;
+            if !Self::reconnect_pause(&target, &tx, attempt).await {
+                return Ok(());
+            }
         }
     }
 
-    // ============================================================
-    // CONNECTION HELPERS
-    // ============================================================
+    async fn handle_mini_ticker_messages(
+        mut ws_stream: WsStream,
+        _symbol: &str,
+        tx: &mpsc::Sender<Result<MiniTicker>>,
+    ) -> Result<()> {
+        while let Some(msg) = ws_stream.next().await {
+            match msg {
+                Ok(Message::Text(text)) => {
+                    match serde_json::from_str::<WsMiniTickerData>(&text) {
+                        Ok(data) => {
+                            let ticker = data.to_mini_ticker();
+                            if tx.send(Ok(ticker)).await.is_err() {
+                                return Ok(());
+                            }
+                        }
+                        Err(e) => {
+                            if let Some(err) = frame_error(&text, "24hrMiniTicker", e) {
+                                let _ = tx.send(Err(err)).await;
+                            }
+                        }
+                    }
+                }
+                Ok(Message::Ping(data)) => {
+                    ws_stream.send(Message::Pong(data)).await
+                        .map_err(|e| Error::WebSocketError(e.to_string()))?;
+                }
+                Ok(Message::Close(frame)) => {
+                    return Err(close_error(frame));
+                }
+                Err(e) => {
+                    return Err(Error::WebSocketError(e.to_string()));
+                }
+                _ => {}
+            }
+        }
+        
+        Err(Error::WebSocketClosed)
+    }
 
-    async fn connect_with_retry(url: &str) -> Result<WsStream> {
-        let max_retries = 5;
-        let mut attempts = 0;
+    async fn combined_messages_handler(
+        target: WsTarget,
+        tx: mpsc::Sender<Result<CombinedMessage>>,
+    ) -> Result<()> {
+        let mut attempt = 0;
         
         loop {
-            attempts += 1;
-            
-            match connect_async(url).await {
-                Ok((ws_stream, _)) => return Ok(ws_stream),
-                Err(e) if attempts >= max_retries => {
-                    return Err(Error::WebSocketError(format!(
-                        "Failed to connect after {} attempts: {}",
-                        max_retries, e
-                    )));
+            match Self::connect_with_retry(&target).await {
+                Ok(ws_stream) => {
+                    attempt = 0;
+                    if let Err(e) = Self::handle_combined_messages(ws_stream, &tx).await {
+                        let _ = tx.send(Err(e)).await;
+                    }
                 }
-                Err(_) => {
-                    let delay = Duration::from_secs(2u64.pow(attempts - 1));
-                    sleep(delay).await;
+                Err(e) => {
+                    let _ = tx.send(Err(e)).await;
                 }
             }
+            
+            attempt += 1;
+            if !Self::reconnect_pause(&target, &tx, attempt).await {
+                return Ok(());
+            }
         }
     }
-}
-
-// ============================================================
-// WEBSOCKET DATA STRUCTURES
-// ============================================================
 
-#[derive(Debug, Deserialize)]
-struct WsTickerData {
-    #[serde(rename = "e")]
-    event_type: String,
-    #[serde(rename = "s")]
-    symbol: String,
-    #[serde(rename = "p")]
+    async fn handle_combined_messages(
+        mut ws_stream: WsStream,
+        tx: &mpsc::Sender<Result<CombinedMessage>>,
+    ) -> Result<()> {
+        while let Some(msg) = ws_stream.next().await {
+            match msg {
+                Ok(Message::Text(text)) => {
+                    match serde_json::from_str::<CombinedMessage>(&text) {
+                        Ok(message) => {
+                            if tx.send(Ok(message)).await.is_err() {
+                                return Ok(());
+                            }
+                        }
+                        Err(e) => {
+                            if let Some(err) = frame_error(&text, "", e) {
+                                let _ = tx.send(Err(err)).await;
+                            }
+                        }
+                    }
+                }
+                Ok(Message::Ping(data)) => {
+                    ws_stream.send(Message::Pong(data)).await
+                        .map_err(|e| Error::WebSocketError(e.to_string()))?;
+                }
+                Ok(Message::Close(frame)) => {
+                    return Err(close_error(frame));
+                }
+                Err(e) => {
+                    return Err(Error::WebSocketError(e.to_string()));
+                }
+                _ => {}
+            }
+        }
+        
+        Err(Error::WebSocketClosed)
+    }
+
+    async fn multiplex_handler(
+        target: WsTarget,
+        reconnect_target: WsTarget,
+        session: MultiplexSession,
+        mut commands: mpsc::Receiver<MultiplexCommand>,
+        tx: mpsc::Sender<Result<StreamEvent<CombinedMessage>>>,
+    ) -> Result<()> {
+        let mut target = &target;
+        let mut resubscribe = Vec::new();
+        let mut attempt = 0;
+        
+        loop {
+            match Self::connect_with_retry(target).await {
+                Ok(ws_stream) => {
+                    attempt = 0;
+                    if tx.send(Ok(StreamEvent::Connected)).await.is_err() {
+                        return Ok(());
+                    }
+                    if let Err(e) = Self::handle_multiplex_messages(
+                        ws_stream,
+                        &mut commands,
+                        resubscribe,
+                        &session.next_id,
+                        &tx,
+                    )
+                    .await
+                    {
+                        let reason = e.to_string();
+                        let _ = tx.send(Ok(StreamEvent::Disconnected { reason })).await;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e)).await;
+                }
+            }
+            
+            attempt += 1;
+            if tx.send(Ok(StreamEvent::Reconnecting { attempt })).await.is_err() {
+                return Ok(());
+            }
+            if !Self::reconnect_pause(target, &tx, attempt).await {
+                return Ok(());
+            }
+            
+            target = &reconnect_target;
+            resubscribe = session
+                .subscriptions
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .cloned()
+                .collect();
+        }
+    }
+
+    async fn handle_multiplex_messages(
+        mut ws_stream: WsStream,
+        commands: &mut mpsc::Receiver<MultiplexCommand>,
+        resubscribe: Vec<String>,
+        next_id: &AtomicU64,
+        tx: &mpsc::Sender<Result<StreamEvent<CombinedMessage>>>,
+    ) -> Result<()> {
+        enum Event {
+            Command(Option<MultiplexCommand>),
+            Frame(Option<tungstenite::Result<Message>>),
+        }
+        
+        // Requests awaiting a response, by id; dropped with the connection
+        let mut pending: HashMap<u64, oneshot::Sender<Result<()>>> = HashMap::new();
+        
+        // Earliest time the next control frame may be sent
+        let mut next_control = Instant::now();
+        
+        // Restore the previous connection's streams before anything else
+        let mut resubscribing = None;
+        if !resubscribe.is_empty() {
+            let id = next_id.fetch_add(1, Ordering::Relaxed);
+            let frame = serde_json::json!({
+                "method": "SUBSCRIBE",
+                "params": resubscribe,
+                "id": id,
+            });
+            ws_stream.send(Message::text(frame.to_string())).await
+                .map_err(|e| Error::WebSocketError(e.to_string()))?;
+            resubscribing = Some((id, resubscribe));
+            next_control += CONTROL_MESSAGE_INTERVAL;
+        }
+        
+        loop {
+            // Commands wait in the channel until the next frame may be sent
+            let accepting = !commands.is_closed();
+            let paced_command = async {
+                tokio::time::sleep_until(next_control).await;
+                commands.recv().await
+            };
+            let event = tokio::select! {
+                command = paced_command, if accepting => Event::Command(command),
+                frame = ws_stream.next() => Event::Frame(frame),
+            };
+            
+            match event {
+                Event::Command(Some(command)) => {
+                    // Forget requests whose caller already gave up
+                    pending.retain(|_, ack| !ack.is_closed());
+                    
+                    let frame = serde_json::json!({
+                        "method": command.method,
+                        "params": command.params,
+                        "id": command.id,
+                    });
+                    ws_stream.send(Message::text(frame.to_string())).await
+                        .map_err(|e| Error::WebSocketError(e.to_string()))?;
+                    pending.insert(command.id, command.ack);
+                    next_control = Instant::now() + CONTROL_MESSAGE_INTERVAL;
+                }
+                Event::Command(None) => {}
+                Event::Frame(None) => return Err(Error::WebSocketClosed),
+                Event::Frame(Some(Ok(Message::Text(text)))) => {
+                    match serde_json::from_str::<CombinedMessage>(&text) {
+                        Ok(message) => {
+                            if tx.send(Ok(StreamEvent::Data(message))).await.is_err() {
+                                return Ok(());
+                            }
+                        }
+                        Err(e) => match control_response(&text) {
+                            Some((id, result))
+                                if resubscribing.as_ref().is_some_and(|(r, _)| *r == id) =>
+                            {
+                                let (_, streams) = resubscribing.take().unwrap_or_default();
+                                if let Err(e) = result {
+                                    let _ = tx.send(Err(e)).await;
+                                    continue;
+                                }
+                                for stream in streams {
+                                    let event = StreamEvent::Resubscribed { stream };
+                                    if tx.send(Ok(event)).await.is_err() {
+                                        return Ok(());
+                                    }
+                                }
+                            }
+                            Some((id, result)) => {
+                                if let Some(ack) = pending.remove(&id) {
+                                    let _ = ack.send(result);
+                                }
+                            }
+                            None => {
+                                if let Some(err) = frame_error(&text, "", e) {
+                                    let _ = tx.send(Err(err)).await;
+                                }
+                            }
+                        },
+                    }
+                }
+                Event::Frame(Some(Ok(Message::Ping(data)))) => {
+                    ws_stream.send(Message::Pong(data)).await
+                        .map_err(|e| Error::WebSocketError(e.to_string()))?;
+                }
+                Event::Frame(Some(Ok(Message::Close(frame)))) => {
+                    return Err(close_error(frame));
+                }
+                Event::Frame(Some(Err(e))) => {
+                    return Err(Error::WebSocketError(e.to_string()));
+                }
+                Event::Frame(Some(Ok(_))) => {}
+            }
+        }
+    }
+
+    async fn raw_stream_handler(
+        target: WsTarget,
+        tx: mpsc::Sender<Result<String>>,
+    ) -> Result<()> {
+        let mut attempt = 0;
+        
+        loop {
+            match Self::connect_with_retry(&target).await {
+                Ok(mut ws_stream) => {
+                    attempt = 0;
+                    while let Some(msg) = ws_stream.next().await {
+                        match msg {
+                            Ok(Message::Text(text))
+                                if tx.send(Ok(text.to_string())).await.is_err() =>
+                            {
+                                return Ok(());
+                            }
+                            Ok(Message::Ping(data)) => {
+                                ws_stream.send(Message::Pong(data)).await
+                                    .map_err(|e| Error::WebSocketError(e.to_string()))?;
+                            }
+                            Ok(Message::Close(frame)) => {
+                                let _ = tx.send(Err(close_error(frame))).await;
+                                break;
+                            }
+                            Err(e) => {
+                                let _ = tx.send(Err(Error::WebSocketError(e.to_string()))).await;
+                                break;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e)).await;
+                }
+            }
+            
+            attempt += 1;
+            if !Self::reconnect_pause(&target, &tx, attempt).await {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn combined_typed_stream_handler(
+        target: WsTarget,
+        tx: mpsc::Sender<Result<StreamEvent<MarketEvent>>>,
+    ) -> Result<()> {
+        // Sequence state outlives each connection so reconnects can be checked
+        let mut sequences = SequenceTracker::default();
+        let mut attempt = 0;
+        
+        loop {
+            match Self::connect_with_retry(&target).await {
+                Ok(ws_stream) => {
+                    attempt = 0;
+                    if tx.send(Ok(StreamEvent::Connected)).await.is_err() {
+                        return Ok(());
+                    }
+                    let result =
+                        Self::handle_combined_typed_messages(ws_stream, &mut sequences, &tx).await;
+                    if let Err(e) = result {
+                        let reason = e.to_string();
+                        let _ = tx.send(Ok(StreamEvent::Disconnected { reason })).await;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e)).await;
+                }
+            }
+            
+            attempt += 1;
+            if tx.send(Ok(StreamEvent::Reconnecting { attempt })).await.is_err() {
+                return Ok(());
+            }
+            if !Self::reconnect_pause(&target, &tx, attempt).await {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn handle_combined_typed_messages(
+        mut ws_stream: WsStream,
+        sequences: &mut SequenceTracker,
+        tx: &mpsc::Sender<Result<StreamEvent<MarketEvent>>>,
+    ) -> Result<()> {
+        while let Some(msg) = ws_stream.next().await {
+            match msg {
+                Ok(Message::Text(text)) => {
+                    let frame = match serde_json::from_str::<CombinedMessage>(&text) {
+                        Ok(frame) => frame,
+                        Err(e) => {
+                            if let Some(err) = frame_error(&text, "", e) {
+                                let _ = tx.send(Err(err)).await;
+                            }
+                            continue;
+                        }
+                    };
+                    
+                    let (event, sequence) = match decode_market_event(frame.data) {
+                        Ok(decoded) => decoded,
+                        Err(e) => {
+                            let _ = tx.send(Err(e)).await;
+                            continue;
+                        }
+                    };
+                    
+                    let mut items = Vec::with_capacity(2);
+                    match sequence.map(|(first, last)| sequences.check(&frame.stream, first, last)) {
+                        Some(SequenceCheck::Duplicate) => continue,
+                        Some(SequenceCheck::Gap { expected, got }) => {
+                            items.push(StreamEvent::Gap {
+                                stream: frame.stream,
+                                expected,
+                                got,
+                            });
+                        }
+                        Some(SequenceCheck::InOrder) | None => {}
+                    }
+                    items.push(StreamEvent::Data(event));
+                    
+                    for item in items {
+                        if tx.send(Ok(item)).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                }
+                Ok(Message::Ping(data)) => {
+                    ws_stream.send(Message::Pong(data)).await
+                        .map_err(|e| Error::WebSocketError(e.to_string()))?;
+                }
+                Ok(Message::Close(frame)) => {
+                    return Err(close_error(frame));
+                }
+                Err(e) => {
+                    return Err(Error::WebSocketError(e.to_string()));
+                }
+                _ => {}
+            }
+        }
+        
+        Err(Error::WebSocketClosed)
+    }
+
+    // ============================================================
+    // CONNECTION HELPERS
+    // ============================================================
+
+    /// Wait before reconnecting a dropped stream, logging the reconnect
+    /// 
+    /// `attempt` counts the handler's reconnects since it last connected.
+    /// Returns false, without waiting out the pause, once the consumer has
+    /// dropped the receiver, so the handler exits instead of reconnecting.
+    async fn reconnect_pause<T>(target: &WsTarget, tx: &mpsc::Sender<T>, attempt: u32) -> bool {
+        if tx.is_closed() {
+            return false;
+        }
+        tracing::info!(
+            url = %redact_url(&target.url),
+            attempt,
+            delay_ms = target.reconnect_delay.as_millis() as u64,
+            "websocket disconnected, reconnecting"
+        );
+        tokio::select! {
+            _ = sleep(target.reconnect_delay) => !tx.is_closed(),
+            _ = tx.closed() => false,
+        }
+    }
+
+    async fn connect_with_retry(target: &WsTarget) -> Result<WsStream> {
+        let mut attempts = 0;
+        
+        loop {
+            attempts += 1;
+            
+            let mut request = target.url.as_str().into_client_request()
+                .map_err(|e| Error::WebSocketError(e.to_string()))?;
+            let user_agent = HeaderValue::from_str(&target.user_agent)
+                .map_err(|e| Error::WebSocketError(e.to_string()))?;
+            request.headers_mut().insert(USER_AGENT, user_agent);
+            
+            let connection = match &target.proxy {
+                Some(proxy) => match proxy_tunnel(proxy, &target.url).await {
+                    Ok(tunnel) => {
+                        client_async_tls_with_config(request, tunnel, None, target.connector.clone())
+                            .await
+                    }
+                    Err(e) => Err(tungstenite::Error::Io(e)),
+                },
+                None => {
+                    connect_async_tls_with_config(request, None, false, target.connector.clone())
+                        .await
+                }
+            };
+            
+            match connection {
+                Ok((inner, _)) => {
+                    return Ok(WsStream {
+                        inner,
+                        idle_timeout: target.idle_timeout,
+                    })
+                }
+                Err(e) if !target.backoff.should_retry(attempts) => {
+                    return Err(Error::WebSocketError(format!(
+                        "Failed to connect after {} attempts: {}",
+                        attempts, e
+                    )));
+                }
+                Err(e) => {
+                    let delay = target.backoff.delay(attempts - 1);
+                    tracing::warn!(
+                        url = %redact_url(&target.url),
+                        attempt = attempts,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "websocket connection attempt failed, backing off"
+                    );
+                    sleep(delay).await;
+                }
+            }
+        }
+    }
+}
+
+// ============================================================
+// WEBSOCKET DATA STRUCTURES
+// ============================================================
+
+/// Forward the latest book from `books` at most once per `min_interval`
+/// 
+/// With `top_n`, books whose best `top_n` levels per side equal those of the
+/// last forwarded book are dropped.
+fn throttle_books(
+    mut books: mpsc::Receiver<Result<OrderBook>>,
+    min_interval: Duration,
+    top_n: Option<usize>,
+    capacity: usize,
+) -> mpsc::Receiver<Result<OrderBook>> {
+    fn top_levels(book: &OrderBook, n: usize) -> (Vec<PriceLevel>, Vec<PriceLevel>) {
+        (
+            book.bids.iter().take(n).cloned().collect(),
+            book.asks.iter().take(n).cloned().collect(),
+        )
+    }
+    
+    let (tx, rx) = mpsc::channel(capacity);
+    
+    tokio::spawn(async move {
+        let mut pending: Option<OrderBook> = None;
+        let mut last_top = None;
+        let mut next_emit = Instant::now();
+        
+        loop {
+            // None once the pending book is due
+            let item = match pending {
+                Some(_) => tokio::time::timeout_at(next_emit, books.recv()).await.ok(),
+                None => Some(books.recv().await),
+            };
+            
+            match item {
+                Some(Some(Ok(book))) => {
+                    let unchanged = top_n.is_some_and(|n| last_top.as_ref() == Some(&top_levels(&book, n)));
+                    pending = (!unchanged).then_some(book);
+                    if pending.is_none() || Instant::now() < next_emit {
+                        continue;
+                    }
+                }
+                Some(Some(Err(e))) => {
+                    if tx.send(Err(e)).await.is_err() {
+                        return;
+                    }
+                    continue;
+                }
+                Some(None) => {
+                    if let Some(book) = pending {
+                        let _ = tx.send(Ok(book)).await;
+                    }
+                    return;
+                }
+                None => {}
+            }
+            
+            if let Some(book) = pending.take() {
+                last_top = top_n.map(|n| top_levels(&book, n));
+                if tx.send(Ok(book)).await.is_err() {
+                    return;
+                }
+                next_emit = Instant::now() + min_interval;
+            }
+        }
+    });
+    
+    rx
+}
+
+/// Split `streams` into consecutive groups of at most `per_connection`
+fn shard_streams<'a>(streams: &'a [&'a str], per_connection: usize) -> Vec<&'a [&'a str]> {
+    streams.chunks(per_connection.max(1)).collect()
+}
+
+/// TCP connection to `url`'s host tunnelled through an HTTP proxy
+/// 
+/// Sends `CONNECT host:port` and waits for a 2xx answer; TLS and the
+/// WebSocket handshake then run over the returned stream.
+async fn proxy_tunnel(proxy: &str, url: &str) -> std::io::Result<TcpStream> {
+    let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidInput, msg.to_string());
+    
+    let proxy = url::Url::parse(proxy).map_err(|_| invalid("invalid proxy URL"))?;
+    let proxy_host = proxy.host_str().ok_or_else(|| invalid("proxy URL has no host"))?;
+    let proxy_port = proxy.port_or_known_default().unwrap_or(80);
+    
+    let url = url::Url::parse(url).map_err(|_| invalid("invalid WebSocket URL"))?;
+    let authority = match (url.host_str(), url.port_or_known_default()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        _ => return Err(invalid("WebSocket URL has no host")),
+    };
+    
+    let mut stream = TcpStream::connect(format!("{}:{}", proxy_host, proxy_port)).await?;
+    stream
+        .write_all(format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n\r\n", authority).as_bytes())
+        .await?;
+    
+    // Byte by byte so nothing past the response head is consumed
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_PROXY_RESPONSE_HEAD {
+            return Err(std::io::Error::other("proxy response head too large"));
+        }
+        head.push(stream.read_u8().await?);
+    }
+    
+    let head = String::from_utf8_lossy(&head);
+    let status_line = head.lines().next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some(code) if code.starts_with('2') => Ok(stream),
+        _ => Err(std::io::Error::other(format!("proxy refused CONNECT: {}", status_line))),
+    }
+}
+
+/// URL safe to log: listen keys (long alphanumeric tokens) are masked
+fn redact_url(url: &str) -> String {
+    let mut redacted = String::with_capacity(url.len());
+    let mut token = String::new();
+    fn flush(token: &mut String, redacted: &mut String) {
+        if token.len() >= LISTEN_KEY_MIN_LEN {
+            redacted.push_str("<redacted>");
+        } else {
+            redacted.push_str(token);
+        }
+        token.clear();
+    }
+    
+    for c in url.chars() {
+        if c.is_ascii_alphanumeric() {
+            token.push(c);
+        } else {
+            flush(&mut token, &mut redacted);
+            redacted.push(c);
+        }
+    }
+    flush(&mut token, &mut redacted);
+    redacted
+}
+
+/// Error for a server close, keeping the close code and reason if sent
+fn close_error(frame: Option<CloseFrame>) -> Error {
+    match frame {
+        Some(frame) => Error::WebSocketClosedWithReason {
+            code: frame.code.into(),
+            reason: frame.reason.to_string(),
+        },
+        None => Error::WebSocketClosed,
+    }
+}
+
+/// Parse a response to a control request: `{"result": null, "id": 1}` or
+/// `{"error": {...}, "id": 1}`
+fn control_response(text: &str) -> Option<(u64, Result<()>)> {
+    let serde_json::Value::Object(frame) = serde_json::from_str(text).ok()? else {
+        return None;
+    };
+    let id = frame.get("id")?.as_u64()?;
+    
+    let result = match frame.get("error") {
+        Some(error) => Err(Error::ApiError {
+            code: error.get("code").and_then(|c| c.as_i64()).unwrap_or(0) as i32,
+            msg: error.get("msg").and_then(|m| m.as_str()).unwrap_or_default().to_string(),
+        }),
+        None => Ok(()),
+    };
+    
+    Some((id, result))
+}
+
+/// Decide what to report for a frame that failed to deserialize
+/// 
+/// Control responses (`{"result": null, "id": 1}`) are ignored, error
+/// responses become `ApiError`, and events of a type other than `expected`
+/// become `UnknownEvent`. Anything else is a genuine deserialization error.
+fn frame_error(text: &str, expected: &str, err: serde_json::Error) -> Option<Error> {
+    let Ok(serde_json::Value::Object(frame)) = serde_json::from_str(text) else {
+        return Some(Error::JsonError(err));
+    };
+    
+    if let Some(error) = frame.get("error") {
+        return Some(Error::ApiError {
+            code: error.get("code").and_then(|c| c.as_i64()).unwrap_or(0) as i32,
+            msg: error.get("msg").and_then(|m| m.as_str()).unwrap_or_default().to_string(),
+        });
+    }
+    
+    match frame.get("e").and_then(|e| e.as_str()) {
+        Some(event) if event != expected => Some(Error::UnknownEvent(event.to_string())),
+        None if frame.contains_key("id") => None,
+        _ => Some(Error::JsonError(err)),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct WsTickerData {
+    #[serde(rename = "e")]
+    event_type: String,
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "p")]
     price_change: String,
     #[serde(rename = "P")]
     price_change_percent: String,
@@ -660,18 +2227,18 @@ impl WsTickerData {
     fn to_ticker24h(&self) -> Result<Ticker24h> {
         Ok(Ticker24h {
             symbol: self.symbol.clone(),
-            price_change: self.price_change.parse().unwrap_or(0.0),
-            price_change_percent: self.price_change_percent.parse().unwrap_or(0.0),
-            weighted_avg_price: self.weighted_avg_price.parse().unwrap_or(0.0),
-            prev_close_price: self.prev_close.parse().unwrap_or(0.0),
-            last_price: self.last_price.parse().unwrap_or(0.0),
-            bid_price: self.bid_price.parse().unwrap_or(0.0),
-            ask_price: self.ask_price.parse().unwrap_or(0.0),
-            open_price: self.open_price.parse().unwrap_or(0.0),
-            high_price: self.high_price.parse().unwrap_or(0.0),
-            low_price: self.low_price.parse().unwrap_or(0.0),
-            volume: self.volume.parse().unwrap_or(0.0),
-            quote_volume: self.quote_volume.parse().unwrap_or(0.0),
+            price_change: parse_f64_or_zero(&self.price_change),
+            price_change_percent: parse_f64_or_zero(&self.price_change_percent),
+            weighted_avg_price: parse_f64_or_zero(&self.weighted_avg_price),
+            prev_close_price: parse_f64_or_zero(&self.prev_close),
+            last_price: parse_f64_or_zero(&self.last_price),
+            bid_price: parse_f64_or_zero(&self.bid_price),
+            ask_price: parse_f64_or_zero(&self.ask_price),
+            open_price: parse_f64_or_zero(&self.open_price),
+            high_price: parse_f64_or_zero(&self.high_price),
+            low_price: parse_f64_or_zero(&self.low_price),
+            volume: parse_f64_or_zero(&self.volume),
+            quote_volume: parse_f64_or_zero(&self.quote_volume),
             open_time: DateTime::from_timestamp_millis(self.open_time).unwrap_or_default(),
             close_time: DateTime::from_timestamp_millis(self.close_time).unwrap_or_default(),
             first_id: self.first_trade_id,
@@ -682,6 +2249,7 @@ impl WsTickerData {
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 struct WsKlineData {
     #[serde(rename = "e")]
     event_type: String,
@@ -717,6 +2285,8 @@ struct WsKline {
     taker_buy_quote: String,
     #[serde(rename = "x")]
     is_closed: bool,
+    #[serde(rename = "i")]
+    interval: Interval,
 }
 
 impl WsKlineData {
@@ -725,24 +2295,28 @@ impl WsKlineData {
             symbol,
             open_time: DateTime::from_timestamp_millis(self.kline.open_time).unwrap_or_default(),
             close_time: DateTime::from_timestamp_millis(self.kline.close_time).unwrap_or_default(),
-            open: self.kline.open.parse().unwrap_or(0.0),
-            high: self.kline.high.parse().unwrap_or(0.0),
-            low: self.kline.low.parse().unwrap_or(0.0),
-            close: self.kline.close.parse().unwrap_or(0.0),
-            volume: self.kline.volume.parse().unwrap_or(0.0),
-            quote_volume: self.kline.quote_volume.parse().unwrap_or(0.0),
+            open: parse_f64_or_zero(&self.kline.open),
+            high: parse_f64_or_zero(&self.kline.high),
+            low: parse_f64_or_zero(&self.kline.low),
+            close: parse_f64_or_zero(&self.kline.close),
+            volume: parse_f64_or_zero(&self.kline.volume),
+            quote_volume: parse_f64_or_zero(&self.kline.quote_volume),
             trades: self.kline.trades,
-            taker_buy_base: self.kline.taker_buy_base.parse().unwrap_or(0.0),
-            taker_buy_quote: self.kline.taker_buy_quote.parse().unwrap_or(0.0),
+            taker_buy_base: parse_f64_or_zero(&self.kline.taker_buy_base),
+            taker_buy_quote: parse_f64_or_zero(&self.kline.taker_buy_quote),
             is_closed: self.kline.is_closed,
+            interval: Some(self.kline.interval),
         })
     }
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 struct WsTradeData {
     #[serde(rename = "e")]
     event_type: String,
+    #[serde(rename = "s")]
+    symbol: String,
     #[serde(rename = "t")]
     trade_id: i64,
     #[serde(rename = "p")]
@@ -757,8 +2331,8 @@ struct WsTradeData {
 
 impl WsTradeData {
     fn to_trade(&self, symbol: String) -> Result<Trade> {
-        let price: f64 = self.price.parse().unwrap_or(0.0);
-        let quantity: f64 = self.quantity.parse().unwrap_or(0.0);
+        let price: f64 = parse_f64_or_zero(&self.price);
+        let quantity: f64 = parse_f64_or_zero(&self.quantity);
         
         Ok(Trade {
             id: self.trade_id,
@@ -773,40 +2347,236 @@ impl WsTradeData {
 }
 
 #[derive(Debug, Deserialize)]
-struct WsDepthData {
+#[allow(dead_code)]
+struct WsAggTradeData {
     #[serde(rename = "e")]
     event_type: String,
     #[serde(rename = "s")]
     symbol: String,
-    #[serde(rename = "U")]
-    first_update_id: i64,
-    #[serde(rename = "u")]
-    last_update_id: i64,
-    #[serde(rename = "b")]
-    bids: Vec<(String, String)>,
     #[serde(rename = "a")]
-    asks: Vec<(String, String)>,
-}
-
-impl WsDepthData {
-    fn to_order_book(&self, symbol: String) -> Result<OrderBook> {
+    agg_trade_id: i64,
+    #[serde(rename = "p")]
+    price: String,
+    #[serde(rename = "q")]
+    quantity: String,
+    #[serde(rename = "f")]
+    first_trade_id: i64,
+    #[serde(rename = "l")]
+    last_trade_id: i64,
+    #[serde(rename = "T")]
+    trade_time: i64,
+    #[serde(rename = "m")]
+    is_buyer_maker: bool,
+}
+
+impl WsAggTradeData {
+    fn to_agg_trade(&self) -> AggTrade {
+        AggTrade {
+            id: self.agg_trade_id,
+            symbol: self.symbol.clone(),
+            price: parse_f64_or_zero(&self.price),
+            quantity: parse_f64_or_zero(&self.quantity),
+            first_trade_id: self.first_trade_id,
+            last_trade_id: self.last_trade_id,
+            time: DateTime::from_timestamp_millis(self.trade_time).unwrap_or_default(),
+            is_buyer_maker: self.is_buyer_maker,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct WsDepthData {
+    #[serde(rename = "e")]
+    event_type: String,
+    #[serde(rename = "E", default)]
+    event_time: Option<i64>,
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "U")]
+    first_update_id: i64,
+    #[serde(rename = "u")]
+    last_update_id: i64,
+    #[serde(rename = "b")]
+    bids: Vec<(String, String)>,
+    #[serde(rename = "a")]
+    asks: Vec<(String, String)>,
+}
+
+impl WsDepthData {
+    fn levels(raw: &[(String, String)]) -> Vec<PriceLevel> {
+        raw.iter().map(|(p, q)| PriceLevel {
+            price: parse_f64_or_zero(p),
+            quantity: parse_f64_or_zero(q),
+        }).collect()
+    }
+
+    /// Event time, falling back to `received_at` if absent or invalid
+    fn timestamp(&self, received_at: DateTime<Utc>) -> DateTime<Utc> {
+        self.event_time
+            .and_then(DateTime::from_timestamp_millis)
+            .unwrap_or(received_at)
+    }
+
+    fn to_order_book(&self, symbol: String) -> Result<OrderBook> {
+        let received_at = Utc::now();
         Ok(OrderBook {
             symbol,
             last_update_id: self.last_update_id,
-            bids: self.bids.iter().map(|(p, q)| PriceLevel {
-                price: p.parse().unwrap_or(0.0),
-                quantity: q.parse().unwrap_or(0.0),
-            }).collect(),
-            asks: self.asks.iter().map(|(p, q)| PriceLevel {
-                price: p.parse().unwrap_or(0.0),
-                quantity: q.parse().unwrap_or(0.0),
-            }).collect(),
-            timestamp: Utc::now(),
+            bids: Self::levels(&self.bids),
+            asks: Self::levels(&self.asks),
+            timestamp: self.timestamp(received_at),
+            received_at,
+            requested_limit: None,
         })
     }
 }
 
+/// Decode a combined stream payload by its event type
+/// 
+/// Also returns the `(first, last)` update ids for sequenced streams.
+fn decode_market_event(data: serde_json::Value) -> Result<(MarketEvent, Option<(i64, i64)>)> {
+    fn parse<T: serde::de::DeserializeOwned>(data: serde_json::Value) -> Result<T> {
+        Ok(serde_json::from_value(data)?)
+    }
+    
+    let event_type = data
+        .get("e")
+        .and_then(|e| e.as_str())
+        .ok_or_else(|| Error::DeserializationError("Missing event type".to_string()))?
+        .to_string();
+    
+    match event_type.as_str() {
+        "24hrTicker" => Ok((MarketEvent::Ticker(parse::<WsTickerData>(data)?.to_ticker24h()?), None)),
+        "24hrMiniTicker" => Ok((
+            MarketEvent::MiniTicker(parse::<WsMiniTickerData>(data)?.to_mini_ticker()),
+            None,
+        )),
+        "kline" => {
+            let kline = parse::<WsKlineData>(data)?;
+            Ok((MarketEvent::Kline(kline.to_kline(kline.symbol.clone())?), None))
+        }
+        "trade" => {
+            let trade = parse::<WsTradeData>(data)?;
+            Ok((MarketEvent::Trade(trade.to_trade(trade.symbol.clone())?), None))
+        }
+        "aggTrade" => {
+            let trade = parse::<WsAggTradeData>(data)?;
+            let sequence = (trade.agg_trade_id, trade.agg_trade_id);
+            Ok((MarketEvent::AggTrade(trade.to_agg_trade()), Some(sequence)))
+        }
+        "depthUpdate" => {
+            let depth = parse::<WsDepthData>(data)?;
+            let sequence = (depth.first_update_id, depth.last_update_id);
+            Ok((MarketEvent::Depth(depth.to_order_book(depth.symbol.clone())?), Some(sequence)))
+        }
+        _ => Err(Error::UnknownEvent(event_type)),
+    }
+}
+
+/// Outcome of checking an event's update ids against its stream's history
+#[derive(Debug, PartialEq, Eq)]
+enum SequenceCheck {
+    InOrder,
+    /// Already seen (e.g. replayed after a reconnect)
+    Duplicate,
+    Gap { expected: i64, got: i64 },
+}
+
+/// Last seen update id per stream name
+#[derive(Default)]
+struct SequenceTracker {
+    last_ids: HashMap<String, i64>,
+}
+
+impl SequenceTracker {
+    fn check(&mut self, stream: &str, first_id: i64, last_id: i64) -> SequenceCheck {
+        let Some(&prev) = self.last_ids.get(stream) else {
+            self.last_ids.insert(stream.to_string(), last_id);
+            return SequenceCheck::InOrder;
+        };
+        
+        if last_id <= prev {
+            return SequenceCheck::Duplicate;
+        }
+        self.last_ids.insert(stream.to_string(), last_id);
+        
+        if first_id > prev + 1 {
+            SequenceCheck::Gap {
+                expected: prev + 1,
+                got: first_id,
+            }
+        } else {
+            SequenceCheck::InOrder
+        }
+    }
+}
+
+/// REST snapshot depth used to seed a managed order book
+const MANAGED_BOOK_SNAPSHOT_DEPTH: usize = 1000;
+
+/// Keeps a REST snapshot in sync with diff depth events
+///
+/// Follows Binance's rules: events older than the snapshot are dropped, the
+/// first applied event must straddle `lastUpdateId + 1`, and each later event
+/// must start right after the previous one.
+struct OrderBookSync {
+    book: OrderBook,
+    max_levels: Option<usize>,
+    synced: bool,
+}
+
+impl OrderBookSync {
+    fn new(mut snapshot: OrderBook, max_levels: Option<usize>) -> Self {
+        if let Some(max) = max_levels {
+            snapshot.truncate_levels(max);
+        }
+        Self {
+            book: snapshot,
+            max_levels,
+            synced: false,
+        }
+    }
+
+    /// Apply a diff event, returning the updated book or `None` if the
+    /// event predates the snapshot. Errors on a sequence gap.
+    fn apply(&mut self, data: &WsDepthData) -> Result<Option<&OrderBook>> {
+        let last_id = self.book.last_update_id;
+        
+        if data.last_update_id <= last_id {
+            return Ok(None);
+        }
+        
+        let in_sequence = if self.synced {
+            data.first_update_id == last_id + 1
+        } else {
+            data.first_update_id <= last_id + 1
+        };
+        if !in_sequence {
+            return Err(Error::WebSocketError(format!(
+                "Order book update gap: expected {}, got {}",
+                last_id + 1,
+                data.first_update_id
+            )));
+        }
+        
+        self.book.apply_diff(
+            &WsDepthData::levels(&data.bids),
+            &WsDepthData::levels(&data.asks),
+            data.last_update_id,
+        );
+        self.book.timestamp = data.timestamp(self.book.received_at);
+        if let Some(max) = self.max_levels {
+            self.book.truncate_levels(max);
+        }
+        self.synced = true;
+        
+        Ok(Some(&self.book))
+    }
+}
+
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 struct WsMiniTickerData {
     #[serde(rename = "e")]
     event_type: String,
@@ -814,16 +2584,31 @@ struct WsMiniTickerData {
     symbol: String,
     #[serde(rename = "c")]
     close_price: String,
+    #[serde(rename = "o")]
+    open_price: String,
+    #[serde(rename = "h")]
+    high_price: String,
+    #[serde(rename = "l")]
+    low_price: String,
+    #[serde(rename = "v")]
+    volume: String,
+    #[serde(rename = "q")]
+    quote_volume: String,
     #[serde(rename = "E")]
     event_time: i64,
 }
 
 impl WsMiniTickerData {
-    fn to_ticker(&self) -> Ticker {
-        Ticker {
+    fn to_mini_ticker(&self) -> MiniTicker {
+        MiniTicker {
             symbol: self.symbol.clone(),
-            price: self.close_price.parse().unwrap_or(0.0),
-            timestamp: DateTime::from_timestamp_millis(self.event_time).unwrap_or_default(),
+            close_price: parse_f64_or_zero(&self.close_price),
+            open_price: parse_f64_or_zero(&self.open_price),
+            high_price: parse_f64_or_zero(&self.high_price),
+            low_price: parse_f64_or_zero(&self.low_price),
+            volume: parse_f64_or_zero(&self.volume),
+            quote_volume: parse_f64_or_zero(&self.quote_volume),
+            event_time: DateTime::from_timestamp_millis(self.event_time).unwrap_or_default(),
         }
     }
 }
@@ -839,6 +2624,1118 @@ mod tests {
         assert!(ws.is_ok());
     }
 
+    fn frame_error_for(text: &str, expected: &str) -> Option<Error> {
+        let err = serde_json::from_str::<WsTradeData>(text).unwrap_err();
+        frame_error(text, expected, err)
+    }
+
+    fn combined(stream: &str) -> CombinedMessage {
+        CombinedMessage {
+            stream: stream.to_string(),
+            data: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_combined_message_stream_name_parsing() {
+        let kline = combined("btcusdt@kline_1m");
+        assert_eq!(kline.symbol().as_deref(), Some("BTCUSDT"));
+        assert_eq!(kline.event_type(), "kline");
+        assert_eq!(kline.interval(), Some(Interval::Minutes1));
+        
+        let agg = combined("ethusdt@aggTrade");
+        assert_eq!(agg.symbol().as_deref(), Some("ETHUSDT"));
+        assert_eq!(agg.event_type(), "aggTrade");
+        assert_eq!(agg.interval(), None);
+        
+        let depth = combined("bnbusdt@depth20@100ms");
+        assert_eq!(depth.symbol().as_deref(), Some("BNBUSDT"));
+        assert_eq!(depth.event_type(), "depth");
+        
+        let all_market = combined("!miniTicker@arr");
+        assert_eq!(all_market.symbol(), None);
+        assert_eq!(all_market.event_type(), "miniTicker");
+        
+        let frame: CombinedMessage = serde_json::from_str(
+            r#"{"stream":"btcusdt@kline_4h","data":{"e":"kline"}}"#
+        ).unwrap();
+        assert_eq!(frame.interval(), Some(Interval::Hours4));
+        assert_eq!(frame.data["e"], "kline");
+    }
+
+    #[test]
+    fn test_frame_error_classification() {
+        assert!(frame_error_for(r#"{"result":null,"id":1}"#, "trade").is_none());
+        assert!(matches!(
+            frame_error_for(r#"{"e":"newEvent","E":1}"#, "trade"),
+            Some(Error::UnknownEvent(e)) if e == "newEvent"
+        ));
+        assert!(matches!(
+            frame_error_for(r#"{"error":{"code":2,"msg":"Invalid request"},"id":1}"#, "trade"),
+            Some(Error::ApiError { code: 2, .. })
+        ));
+        assert!(matches!(
+            frame_error_for(r#"{"e":"trade","t":"oops"}"#, "trade"),
+            Some(Error::JsonError(_))
+        ));
+        assert!(matches!(
+            frame_error_for("not json", "trade"),
+            Some(Error::JsonError(_))
+        ));
+    }
+
+    /// Serve `frames` to the first client that connects, then hold the socket open
+    async fn serve_frames(frames: Vec<&'static str>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            for frame in frames {
+                ws.send(Message::text(frame)).await.unwrap();
+            }
+            while ws.next().await.is_some() {}
+        });
+        
+        format!("ws://{}", addr)
+    }
+
+    /// Client pointed at a local test server
+    fn local_ws(url: String) -> BinanceWebSocket {
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(url);
+        BinanceWebSocket::new(config).unwrap()
+    }
+
+    /// `local_ws` that reconnects 50ms after a drop instead of 5s
+    fn fast_reconnect_ws(url: String) -> BinanceWebSocket {
+        let ws = local_ws(url);
+        ws.with_stream_config(ws.stream_config().reconnect_delay(Duration::from_millis(50)))
+    }
+
+    /// Next successful item from a stream, failing the test after 5s
+    async fn next_item<T>(rx: &mut mpsc::Receiver<Result<T>>) -> T {
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("timed out waiting for stream item")
+            .expect("stream ended")
+            .expect("stream yielded an error")
+    }
+
+    #[tokio::test]
+    async fn test_local_ticker_stream() {
+        let url = serve_frames(vec![
+            r#"{"e":"24hrTicker","E":1672515782136,"s":"BTCUSDT","p":"1000.00","P":"2.381","w":"42500.00","x":"42000.00","c":"43000.00","Q":"0.1","b":"42999.00","B":"1.0","a":"43001.00","A":"2.0","o":"42000.00","h":"43500.00","l":"41500.00","v":"1000.0","q":"43000000.0","O":1672429382136,"C":1672515782136,"F":1,"L":1000,"n":1000}"#,
+        ])
+        .await;
+        let mut stream = local_ws(url).ticker_stream("BTCUSDT").await.unwrap();
+        
+        let ticker = next_item(&mut stream).await;
+        assert_eq!(ticker.symbol, "BTCUSDT");
+        assert_eq!(ticker.last_price, 43000.0);
+        assert_eq!(ticker.try_spread(), Some(2.0));
+        assert_eq!(ticker.open_time.timestamp_millis(), 1672429382136);
+        assert_eq!(ticker.count, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_stream_through_connect_proxy() {
+        let ws_url = serve_frames(vec![
+            r#"{"e":"trade","E":1,"s":"BTCUSDT","t":7,"p":"43000.00","q":"0.5","T":1640000000000,"m":false}"#,
+        ])
+        .await;
+        let ws_addr = ws_url.trim_start_matches("ws://").to_string();
+        
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_addr = listener.local_addr().unwrap();
+        let (request_tx, request_rx) = oneshot::channel();
+        let upstream_addr = ws_addr.clone();
+        
+        tokio::spawn(async move {
+            let (mut client, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            while !head.ends_with(b"\r\n\r\n") {
+                head.push(client.read_u8().await.unwrap());
+            }
+            let _ = request_tx.send(String::from_utf8(head).unwrap());
+            
+            let mut upstream = TcpStream::connect(upstream_addr).await.unwrap();
+            client.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").await.unwrap();
+            let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+        });
+        
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(ws_url);
+        config.proxy_url = Some(format!("http://{}", proxy_addr));
+        let mut stream = BinanceWebSocket::new(config).unwrap().trade_stream("BTCUSDT").await.unwrap();
+        
+        let trade = next_item(&mut stream).await;
+        assert_eq!(trade.id, 7);
+        
+        let request = request_rx.await.unwrap();
+        assert!(request.starts_with(&format!("CONNECT {} HTTP/1.1\r\n", ws_addr)));
+    }
+
+    fn book_with_bids(update_id: i64, bids: &[(f64, f64)]) -> OrderBook {
+        OrderBook {
+            symbol: "BTCUSDT".to_string(),
+            last_update_id: update_id,
+            bids: bids
+                .iter()
+                .map(|&(price, quantity)| PriceLevel { price, quantity })
+                .collect(),
+            asks: vec![PriceLevel { price: 101.0, quantity: 1.0 }],
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            requested_limit: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_throttle_books_coalesces_rapid_updates() {
+        let (tx, books) = mpsc::channel(100);
+        let mut throttled = throttle_books(books, Duration::from_millis(200), None, 100);
+        
+        for id in 1..=10 {
+            tx.send(Ok(book_with_bids(id, &[(100.0, id as f64)]))).await.unwrap();
+        }
+        
+        // The first book goes out at once, the rest collapse into the latest
+        assert_eq!(next_item(&mut throttled).await.last_update_id, 1);
+        let started = Instant::now();
+        assert_eq!(next_item(&mut throttled).await.last_update_id, 10);
+        assert!(started.elapsed() >= Duration::from_millis(150));
+        
+        let quiet = tokio::time::timeout(Duration::from_millis(300), throttled.recv()).await;
+        assert!(quiet.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_throttle_books_top_levels() {
+        let (tx, books) = mpsc::channel(100);
+        let mut throttled = throttle_books(books, Duration::ZERO, Some(2), 100);
+        
+        tx.send(Ok(book_with_bids(1, &[(100.0, 1.0), (99.0, 1.0), (98.0, 1.0)]))).await.unwrap();
+        assert_eq!(next_item(&mut throttled).await.last_update_id, 1);
+        
+        // Only the third level changes: nothing is emitted
+        tx.send(Ok(book_with_bids(2, &[(100.0, 1.0), (99.0, 1.0), (98.0, 5.0)]))).await.unwrap();
+        // The second level changes
+        tx.send(Ok(book_with_bids(3, &[(100.0, 1.0), (99.0, 2.0), (98.0, 5.0)]))).await.unwrap();
+        assert_eq!(next_item(&mut throttled).await.last_update_id, 3);
+        
+        drop(tx);
+        assert!(throttled.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_proxy_tunnel_refused() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_addr = listener.local_addr().unwrap();
+        
+        tokio::spawn(async move {
+            let (mut client, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            while !head.ends_with(b"\r\n\r\n") {
+                head.push(client.read_u8().await.unwrap());
+            }
+            client
+                .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n")
+                .await
+                .unwrap();
+        });
+        
+        let err = proxy_tunnel(&format!("http://{}", proxy_addr), "wss://stream.binance.com:9443/ws")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("407"));
+    }
+
+    #[tokio::test]
+    async fn test_local_kline_stream() {
+        let url = serve_frames(vec![
+            r#"{"e":"kline","E":1672515782136,"s":"ETHUSDT","k":{"t":1672515780000,"T":1672515839999,"s":"ETHUSDT","i":"1m","f":100,"L":200,"o":"1200.00","c":"1201.50","h":"1202.00","l":"1199.00","v":"50.0","n":101,"x":true,"q":"60000.0","V":"20.0","Q":"24000.0","B":"0"}}"#,
+        ])
+        .await;
+        let mut stream = local_ws(url).kline_stream("ETHUSDT", Interval::Minutes1, None).await.unwrap();
+        
+        let kline = next_item(&mut stream).await;
+        assert_eq!(kline.symbol, "ETHUSDT");
+        assert_eq!(kline.open, 1200.0);
+        assert_eq!(kline.close, 1201.5);
+        assert_eq!(kline.high, 1202.0);
+        assert_eq!(kline.low, 1199.0);
+        assert_eq!(kline.trades, 101);
+        assert_eq!(kline.taker_buy_quote, 24000.0);
+        assert!(kline.is_closed);
+        assert_eq!(kline.interval, Some(Interval::Minutes1));
+    }
+
+    #[tokio::test]
+    async fn test_local_depth_stream() {
+        let url = serve_frames(vec![
+            r#"{"e":"depthUpdate","E":1672515782136,"s":"BNBUSDT","U":157,"u":160,"b":[["250.10","10.0"],["250.00","5.5"]],"a":[["250.20","3.0"]]}"#,
+        ])
+        .await;
+        let mut stream = local_ws(url).depth_stream("BNBUSDT").await.unwrap();
+        
+        let book = next_item(&mut stream).await;
+        assert_eq!(book.symbol, "BNBUSDT");
+        assert_eq!(book.last_update_id, 160);
+        assert_eq!(book.bids.len(), 2);
+        assert_eq!(book.bids[1].quantity, 5.5);
+        assert_eq!(book.asks[0].price, 250.2);
+        // Book time is the server's event time, not the receive time
+        assert_eq!(book.timestamp.timestamp_millis(), 1672515782136);
+        assert!(book.received_at > book.timestamp);
+    }
+
+    #[tokio::test]
+    async fn test_local_trade_stream_reconnects() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        
+        tokio::spawn(async move {
+            // First session drops straight away, second delivers a trade
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            ws.close(None).await.unwrap();
+            
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            ws.send(Message::text(
+                r#"{"e":"trade","E":1,"s":"BTCUSDT","t":7,"p":"43000.00","q":"0.5","T":1640000000000,"m":false}"#,
+            ))
+            .await
+            .unwrap();
+            while ws.next().await.is_some() {}
+        });
+        
+        let mut stream = fast_reconnect_ws(format!("ws://{}", addr))
+            .trade_stream("BTCUSDT")
+            .await
+            .unwrap();
+        
+        let dropped = tokio::time::timeout(Duration::from_secs(5), stream.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(dropped, Err(Error::WebSocketClosed)));
+        
+        let trade = tokio::time::timeout(Duration::from_secs(5), stream.recv())
+            .await
+            .expect("should reconnect")
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade.id, 7);
+        assert_eq!(trade.quote_quantity, 21500.0);
+    }
+
+    #[tokio::test]
+    async fn test_trade_stream_batched() {
+        const TRADE: &str =
+            r#"{"e":"trade","E":1,"s":"BTCUSDT","t":1,"p":"43000.00","q":"0.5","T":1640000000000,"m":true}"#;
+        let url = serve_frames(vec![TRADE; 7]).await;
+        let mut stream = local_ws(url)
+            .trade_stream_batched("BTCUSDT", 3, Duration::from_millis(300))
+            .await
+            .unwrap();
+        
+        // Trades arrive faster than max_delay, so batches fill up first
+        assert_eq!(next_item(&mut stream).await.len(), 3);
+        assert_eq!(next_item(&mut stream).await.len(), 3);
+        
+        // The remainder goes out once max_delay has passed
+        let started = Instant::now();
+        assert_eq!(next_item(&mut stream).await.len(), 1);
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_subscription_ack_is_ignored() {
+        let url = serve_frames(vec![
+            r#"{"result":null,"id":1}"#,
+            r#"{"e":"trade","E":1,"s":"BTCUSDT","t":12345,"p":"43000.00","q":"0.5","T":1640000000000,"m":true}"#,
+        ])
+        .await;
+        
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(url);
+        let ws = BinanceWebSocket::new(config).unwrap();
+        let mut stream = ws.trade_stream("BTCUSDT").await.unwrap();
+        
+        let first = tokio::time::timeout(Duration::from_secs(5), stream.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let trade = first.expect("ack frame should not produce an error");
+        assert_eq!(trade.id, 12345);
+    }
+
+    #[test]
+    fn test_with_connector() {
+        let tls = native_tls::TlsConnector::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .unwrap();
+        let ws = BinanceWebSocket::with_connector(BinanceConfig::new(false), Connector::NativeTls(tls))
+            .unwrap();
+        
+        let target = ws.target("wss://stream.binance.com:9443/ws/btcusdt@trade".to_string());
+        assert!(matches!(target.connector, Some(Connector::NativeTls(_))));
+    }
+
+    #[tokio::test]
+    async fn test_custom_connector_is_used_for_streams() {
+        let url = serve_frames(vec![
+            r#"{"e":"trade","E":1,"s":"BTCUSDT","t":7,"p":"43000.00","q":"0.5","T":1640000000000,"m":false}"#,
+        ])
+        .await;
+        
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(url);
+        let ws = BinanceWebSocket::with_connector(config, Connector::Plain).unwrap();
+        let mut stream = ws.trade_stream("BTCUSDT").await.unwrap();
+        
+        assert_eq!(next_item(&mut stream).await.id, 7);
+    }
+
+    #[tokio::test]
+    async fn test_live_order_book_continues_from_snapshot() {
+        // Diffs are queued on the socket before the snapshot is fetched
+        let url = serve_frames(vec![
+            r#"{"e":"depthUpdate","E":1,"s":"BTCUSDT","U":95,"u":99,"b":[["43000.00","9"]],"a":[]}"#,
+            r#"{"e":"depthUpdate","E":2,"s":"BTCUSDT","U":98,"u":101,"b":[["43000.00","3"]],"a":[]}"#,
+            r#"{"e":"depthUpdate","E":3,"s":"BTCUSDT","U":102,"u":102,"b":[],"a":[["43001.00","0"]]}"#,
+        ])
+        .await;
+        
+        let mut rest = mockito::Server::new_async().await;
+        let depth = rest.mock("GET", "/api/v3/depth")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{
+                "lastUpdateId": 100,
+                "bids": [["43000.00", "1.5"], ["42999.00", "2.0"]],
+                "asks": [["43001.00", "1.2"], ["43002.00", "1.8"]]
+            }"#)
+            .create_async()
+            .await;
+        
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(url);
+        config.base_url = Some(rest.url());
+        let client = BinanceClient::new(config.clone()).unwrap();
+        let ws = BinanceWebSocket::new(config).unwrap();
+        
+        let (snapshot, mut updates) = ws.live_order_book(client, "BTCUSDT", None).await.unwrap();
+        assert_eq!(snapshot.last_update_id, 100);
+        assert_eq!(snapshot.bids[0].quantity, 1.5);
+        
+        // The pre-snapshot diff is skipped; the straddling one applies
+        let book = next_item(&mut updates).await;
+        assert_eq!(book.last_update_id, 101);
+        assert_eq!(book.bids[0].quantity, 3.0);
+        
+        let book = next_item(&mut updates).await;
+        assert_eq!(book.last_update_id, 102);
+        assert_eq!(book.asks[0].price, 43002.0);
+        
+        depth.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_managed_order_book_uses_callers_client() {
+        let url = serve_frames(vec![
+            r#"{"e":"depthUpdate","E":1,"s":"BTCUSDT","U":100,"u":101,"b":[["43000.00","3.0"]],"a":[]}"#,
+        ])
+        .await;
+
+        let mut rest = mockito::Server::new_async().await;
+        let depth = rest.mock("GET", "/api/v3/depth")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("x-mbx-used-weight-1m", "250")
+            .with_body(r#"{"lastUpdateId": 100, "bids": [["43000.00", "1.5"]], "asks": []}"#)
+            .create_async()
+            .await;
+
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(url);
+        config.base_url = Some(rest.url());
+        let client = BinanceClient::new(config.clone()).unwrap();
+        let ws = BinanceWebSocket::new(config).unwrap();
+
+        let mut books = ws.managed_order_book(&client, "BTCUSDT", None).await.unwrap();
+        assert_eq!(next_item(&mut books).await.bids[0].quantity, 3.0);
+
+        // The snapshot went through the caller's client
+        assert_eq!(client.used_weight(), Some(250));
+        depth.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_stream_config_capacity_override() {
+        let url = serve_frames(vec![]).await;
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(url);
+        let ws = BinanceWebSocket::new(config).unwrap();
+        
+        let stream = ws
+            .with_stream_config(ws.stream_config().capacity(500))
+            .trade_stream("BTCUSDT")
+            .await
+            .unwrap();
+        assert_eq!(stream.max_capacity(), 500);
+        
+        // The original client keeps the defaults
+        assert_eq!(ws.stream_config().capacity, DEFAULT_STREAM_CAPACITY);
+    }
+
+    #[tokio::test]
+    async fn test_stream_config_idle_timeout_reconnects() {
+        // Server accepts but never sends anything
+        let url = serve_frames(vec![]).await;
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(url);
+        let ws = BinanceWebSocket::new(config).unwrap();
+        
+        let mut stream = ws
+            .with_stream_config(ws.stream_config().idle_timeout(Duration::from_millis(100)))
+            .trade_stream("BTCUSDT")
+            .await
+            .unwrap();
+        
+        let item = tokio::time::timeout(Duration::from_secs(5), stream.recv())
+            .await
+            .expect("idle timeout should fire")
+            .unwrap();
+        assert!(matches!(item, Err(Error::WebSocketError(msg)) if msg.contains("no message")));
+    }
+
+    #[tokio::test]
+    async fn test_close_frame_reason_is_reported() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            let frame = CloseFrame {
+                code: tungstenite::protocol::frame::coding::CloseCode::Policy,
+                reason: "Too many requests".into(),
+            };
+            ws.close(Some(frame)).await.unwrap();
+            while ws.next().await.is_some() {}
+        });
+        
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(format!("ws://{}", addr));
+        let ws = BinanceWebSocket::new(config).unwrap();
+        let mut stream = ws.trade_stream("BTCUSDT").await.unwrap();
+        
+        let item = tokio::time::timeout(Duration::from_secs(5), stream.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match item {
+            Err(Error::WebSocketClosedWithReason { code, reason }) => {
+                assert_eq!(code, 1008);
+                assert_eq!(reason, "Too many requests");
+            }
+            other => panic!("expected close reason, got {:?}", other),
+        }
+        
+        assert!(matches!(close_error(None), Error::WebSocketClosed));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::result_large_err)] // Handshake callback signature is fixed by tungstenite
+    async fn test_handshake_sends_user_agent() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (ua_tx, ua_rx) = oneshot::channel();
+        
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let callback = |request: &tungstenite::handshake::server::Request, response| {
+                let ua = request.headers().get(USER_AGENT).cloned();
+                let _ = ua_tx.send(ua);
+                Ok(response)
+            };
+            let mut ws = tokio_tungstenite::accept_hdr_async(socket, callback).await.unwrap();
+            while ws.next().await.is_some() {}
+        });
+        
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(format!("ws://{}", addr));
+        config.user_agent = Some("my-bot/1.0".to_string());
+        let ws = BinanceWebSocket::new(config).unwrap();
+        let _stream = ws.trade_stream("BTCUSDT").await.unwrap();
+        
+        let ua = tokio::time::timeout(Duration::from_secs(5), ua_rx).await.unwrap().unwrap();
+        assert_eq!(ua.unwrap(), "my-bot/1.0");
+    }
+
+    #[tokio::test]
+    async fn test_multiplex_subscribe_ack_timeout() {
+        // Server accepts but never answers the SUBSCRIBE
+        let url = serve_frames(vec![]).await;
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(url);
+        let ws = BinanceWebSocket::new(config).unwrap();
+        
+        let (handle, _messages) = ws
+            .with_stream_config(ws.stream_config().ack_timeout(Duration::from_millis(200)))
+            .multiplex_stream(&[])
+            .await
+            .unwrap();
+        
+        let result = tokio::time::timeout(Duration::from_secs(5), handle.subscribe(&["btcusdt@trade"]))
+            .await
+            .expect("subscribe should not hang");
+        assert!(matches!(result, Err(Error::WebSocketError(msg)) if msg == "subscribe ack timeout"));
+    }
+
+    #[tokio::test]
+    async fn test_multiplex_subscribe_acked() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                assert_eq!(request["method"], "SUBSCRIBE");
+                assert_eq!(request["params"][0], "btcusdt@trade");
+        
+                // Unrelated id first: must not resolve this request
+                ws.send(Message::text(r#"{"result":null,"id":999}"#)).await.unwrap();
+                let ack = format!(r#"{{"result":null,"id":{}}}"#, request["id"]);
+                ws.send(Message::text(ack)).await.unwrap();
+                ws.send(Message::text(
+                    r#"{"stream":"btcusdt@trade","data":{"e":"trade","s":"BTCUSDT"}}"#,
+                ))
+                .await
+                .unwrap();
+            }
+        });
+        
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(format!("ws://{}", addr));
+        let ws = BinanceWebSocket::new(config).unwrap();
+        
+        let (handle, mut messages) = ws.multiplex_stream(&[]).await.unwrap();
+        handle.subscribe(&["btcusdt@trade"]).await.unwrap();
+        
+        assert!(matches!(next_item(&mut messages).await, StreamEvent::Connected));
+        let message = match next_item(&mut messages).await {
+            StreamEvent::Data(message) => message,
+            other => panic!("expected data, got {:?}", other),
+        };
+        assert_eq!(message.stream, "btcusdt@trade");
+        assert_eq!(message.symbol().as_deref(), Some("BTCUSDT"));
+        assert_eq!(handle.subscriptions(), vec!["btcusdt@trade"]);
+    }
+
+    #[tokio::test]
+    async fn test_multiplex_paces_control_frames() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (times_tx, times_rx) = oneshot::channel();
+        
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            let mut received = Vec::new();
+            while received.len() < 20 {
+                let Some(Ok(Message::Text(text))) = ws.next().await else { break };
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                received.push(Instant::now());
+                let ack = format!(r#"{{"result":null,"id":{}}}"#, request["id"]);
+                ws.send(Message::text(ack)).await.unwrap();
+            }
+            let _ = times_tx.send(received);
+            while ws.next().await.is_some() {}
+        });
+        
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(format!("ws://{}", addr));
+        let ws = BinanceWebSocket::new(config).unwrap();
+        let (handle, _messages) = ws
+            .with_stream_config(ws.stream_config().ack_timeout(Duration::from_secs(10)))
+            .multiplex_stream(&[])
+            .await
+            .unwrap();
+        
+        let streams: Vec<String> = (0..20).map(|i| format!("sym{}usdt@trade", i)).collect();
+        let requests = streams.iter().map(|stream| async { handle.subscribe(&[stream]).await });
+        for result in futures_util::future::join_all(requests).await {
+            result.unwrap();
+        }
+        assert_eq!(handle.subscriptions().len(), 20);
+        
+        // No more than 5 frames in any one second (with slack for scheduling)
+        let times = times_rx.await.unwrap();
+        assert_eq!(times.len(), 20);
+        for window in times.windows(6) {
+            assert!(window[5] - window[0] >= Duration::from_millis(900));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dropped_receiver_stops_reconnect() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let ws = local_ws(format!("ws://{}", addr));
+        
+        let (tx, mut rx) = mpsc::channel(10);
+        let handler = tokio::spawn(BinanceWebSocket::trade_stream_handler(
+            ws.target(format!("ws://{}/btcusdt@trade", addr)),
+            "BTCUSDT".to_string(),
+            tx,
+        ));
+        
+        // Accept the first connection and close it right away
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut server = tokio_tungstenite::accept_async(socket).await.unwrap();
+        server.close(None).await.unwrap();
+        
+        // Drop the receiver once the disconnect is reported, during the pause
+        let disconnect = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap();
+        assert!(matches!(disconnect, Some(Err(_))));
+        drop(rx);
+        
+        tokio::time::timeout(Duration::from_secs(1), handler)
+            .await
+            .expect("handler should exit without waiting out the reconnect pause")
+            .unwrap()
+            .unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(200), listener.accept()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_multiplex_resubscribes_after_reconnect() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (resubscribe_tx, resubscribe_rx) = oneshot::channel();
+        
+        tokio::spawn(async move {
+            // First session acks the subscribe and unsubscribe, sends a trade
+            // and drops the connection
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            for _ in 0..2 {
+                let Some(Ok(Message::Text(text))) = ws.next().await else { return };
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let ack = format!(r#"{{"result":null,"id":{}}}"#, request["id"]);
+                ws.send(Message::text(ack)).await.unwrap();
+            }
+            ws.send(Message::text(
+                r#"{"stream":"btcusdt@trade","data":{"e":"trade","s":"BTCUSDT"}}"#,
+            ))
+            .await
+            .unwrap();
+            ws.close(None).await.unwrap();
+            
+            // Second session must restore the subscription unprompted
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            let Some(Ok(Message::Text(text))) = ws.next().await else { return };
+            let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+            let ack = format!(r#"{{"result":null,"id":{}}}"#, request["id"]);
+            let _ = resubscribe_tx.send(request);
+            ws.send(Message::text(ack)).await.unwrap();
+            ws.send(Message::text(
+                r#"{"stream":"btcusdt@trade","data":{"e":"trade","s":"BTCUSDT"}}"#,
+            ))
+            .await
+            .unwrap();
+            while ws.next().await.is_some() {}
+        });
+        
+        let ws = fast_reconnect_ws(format!("ws://{}", addr));
+        let (handle, mut messages) = ws.multiplex_stream(&["ethusdt@trade"]).await.unwrap();
+        handle.subscribe(&["btcusdt@trade"]).await.unwrap();
+        handle.unsubscribe(&["ethusdt@trade"]).await.unwrap();
+        assert_eq!(handle.subscriptions(), vec!["btcusdt@trade"]);
+        
+        assert!(matches!(next_item(&mut messages).await, StreamEvent::Connected));
+        assert!(matches!(next_item(&mut messages).await, StreamEvent::Data(_)));
+        assert!(matches!(
+            next_item(&mut messages).await,
+            StreamEvent::Disconnected { .. }
+        ));
+        assert!(matches!(
+            next_item(&mut messages).await,
+            StreamEvent::Reconnecting { attempt: 1 }
+        ));
+        
+        let request = tokio::time::timeout(Duration::from_secs(5), resubscribe_rx)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(request["method"], "SUBSCRIBE");
+        assert_eq!(request["params"], serde_json::json!(["btcusdt@trade"]));
+        
+        assert!(matches!(next_item(&mut messages).await, StreamEvent::Connected));
+        assert!(matches!(
+            next_item(&mut messages).await,
+            StreamEvent::Resubscribed { stream } if stream == "btcusdt@trade"
+        ));
+        assert!(matches!(
+            next_item(&mut messages).await,
+            StreamEvent::Data(message) if message.stream == "btcusdt@trade"
+        ));
+    }
+
+    #[test]
+    fn test_sequence_tracker_detects_gaps() {
+        let mut tracker = SequenceTracker::default();
+        let stream = "btcusdt@depth";
+        
+        assert_eq!(tracker.check(stream, 100, 105), SequenceCheck::InOrder);
+        assert_eq!(tracker.check(stream, 106, 110), SequenceCheck::InOrder);
+        // Replayed after a reconnect
+        assert_eq!(tracker.check(stream, 101, 110), SequenceCheck::Duplicate);
+        // Overlapping range is still continuous
+        assert_eq!(tracker.check(stream, 108, 112), SequenceCheck::InOrder);
+        // Skipped 113..=119
+        assert_eq!(
+            tracker.check(stream, 120, 125),
+            SequenceCheck::Gap { expected: 113, got: 120 }
+        );
+        assert_eq!(tracker.check(stream, 126, 126), SequenceCheck::InOrder);
+        
+        // Streams are tracked independently
+        assert_eq!(tracker.check("ethusdt@aggTrade", 5, 5), SequenceCheck::InOrder);
+        assert_eq!(
+            tracker.check("ethusdt@aggTrade", 7, 7),
+            SequenceCheck::Gap { expected: 6, got: 7 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_combined_typed_stream_reports_gap() {
+        let url = serve_frames(vec![
+            r#"{"stream":"btcusdt@depth","data":{"e":"depthUpdate","E":1,"s":"BTCUSDT","U":1,"u":5,"b":[["100.0","1.0"]],"a":[]}}"#,
+            r#"{"stream":"btcusdt@depth","data":{"e":"depthUpdate","E":2,"s":"BTCUSDT","U":9,"u":10,"b":[],"a":[["101.0","2.0"]]}}"#,
+            r#"{"stream":"btcusdt@ticker","data":{"e":"24hrTicker","E":3,"s":"BTCUSDT","p":"1","P":"0.1","w":"100","x":"99","c":"100","Q":"1","b":"99.9","B":"1","a":"100.1","A":"1","o":"99","h":"101","l":"98","v":"10","q":"1000","O":0,"C":86400000,"F":1,"L":10,"n":10}}"#,
+        ])
+        .await;
+        
+        let mut config = BinanceConfig::new(false);
+        config.ws_url = Some(url);
+        let ws = BinanceWebSocket::new(config).unwrap();
+        let mut stream = ws
+            .combined_typed_stream(&["btcusdt@depth", "btcusdt@ticker"])
+            .await
+            .unwrap();
+        
+        assert!(matches!(next_item(&mut stream).await, StreamEvent::Connected));
+        assert!(matches!(
+            next_item(&mut stream).await,
+            StreamEvent::Data(MarketEvent::Depth(book)) if book.last_update_id == 5
+        ));
+        assert!(matches!(
+            next_item(&mut stream).await,
+            StreamEvent::Gap { expected: 6, got: 9, .. }
+        ));
+        assert!(matches!(
+            next_item(&mut stream).await,
+            StreamEvent::Data(MarketEvent::Depth(book)) if book.last_update_id == 10
+        ));
+        assert!(matches!(
+            next_item(&mut stream).await,
+            StreamEvent::Data(MarketEvent::Ticker(t)) if t.symbol == "BTCUSDT"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_combined_typed_stream_lifecycle() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            ws.send(Message::text(
+                r#"{"stream":"btcusdt@trade","data":{"e":"trade","E":1,"s":"BTCUSDT","t":1,"p":"1.0","q":"1.0","T":1,"m":true}}"#,
+            ))
+            .await
+            .unwrap();
+            ws.close(Some(CloseFrame {
+                code: tungstenite::protocol::frame::coding::CloseCode::Away,
+                reason: "maintenance".into(),
+            }))
+            .await
+            .unwrap();
+        });
+        
+        let ws = fast_reconnect_ws(format!("ws://{}", addr));
+        let mut stream = ws.combined_typed_stream(&["btcusdt@trade"]).await.unwrap();
+        
+        assert!(matches!(next_item(&mut stream).await, StreamEvent::Connected));
+        assert!(matches!(
+            next_item(&mut stream).await,
+            StreamEvent::Data(MarketEvent::Trade(_))
+        ));
+        assert!(matches!(
+            next_item(&mut stream).await,
+            StreamEvent::Disconnected { reason } if reason.contains("maintenance")
+        ));
+        assert!(matches!(
+            next_item(&mut stream).await,
+            StreamEvent::Reconnecting { attempt: 1 }
+        ));
+    }
+
+    #[test]
+    fn test_shard_streams() {
+        let names: Vec<String> = (0..500).map(|i| format!("sym{}@trade", i)).collect();
+        let streams: Vec<&str> = names.iter().map(String::as_str).collect();
+        
+        let shards = shard_streams(&streams, DEFAULT_STREAMS_PER_CONNECTION);
+        assert_eq!(
+            shards.iter().map(|shard| shard.len()).collect::<Vec<_>>(),
+            vec![200, 200, 100]
+        );
+        assert_eq!(shards.concat(), streams);
+        
+        assert_eq!(shard_streams(&streams, 500).len(), 1);
+        assert!(shard_streams(&[], 200).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sharded_typed_stream_shard_count() {
+        let names: Vec<String> = (0..500).map(|i| format!("sym{}@trade", i)).collect();
+        let streams: Vec<&str> = names.iter().map(String::as_str).collect();
+        let ws = local_ws("ws://127.0.0.1:9/ws".to_string());
+        
+        let (shards, _events) = ws.sharded_typed_stream(&streams).await.unwrap();
+        assert_eq!(shards, 3);
+        
+        let (shards, _events) = ws
+            .with_stream_config(ws.stream_config().streams_per_connection(2000))
+            .sharded_typed_stream(&streams)
+            .await
+            .unwrap();
+        assert_eq!(shards, 1);
+    }
+
+    #[test]
+    fn test_redact_url() {
+        let key = "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1";
+        assert_eq!(
+            redact_url(&format!("wss://stream.binance.com:9443/ws/{}", key)),
+            "wss://stream.binance.com:9443/ws/<redacted>"
+        );
+        assert_eq!(
+            redact_url("wss://stream.binance.com:9443/stream?streams=btcusdt@trade/ethusdt@depth"),
+            "wss://stream.binance.com:9443/stream?streams=btcusdt@trade/ethusdt@depth"
+        );
+    }
+
+    /// Collects the fields of every event as strings
+    #[derive(Clone, Default)]
+    struct EventCapture(Arc<Mutex<Vec<HashMap<String, String>>>>);
+
+    struct FieldVisitor(HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl tracing::Subscriber for EventCapture {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            tracing::span::Id::from_u64(1)
+        }
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+        fn event(&self, event: &tracing::Event<'_>) {
+            let mut visitor = FieldVisitor(HashMap::new());
+            event.record(&mut visitor);
+            self.0.lock().unwrap().push(visitor.0);
+        }
+        fn enter(&self, _: &tracing::span::Id) {}
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[tokio::test]
+    async fn test_connect_retry_logs_attempts() {
+        let capture = EventCapture::default();
+        let _guard = tracing::subscriber::set_default(capture.clone());
+        
+        // Nothing listens on the port once the listener is dropped
+        let addr = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let ws = local_ws(format!("ws://{}/ws", addr));
+        let policy = BackoffPolicy {
+            initial: Duration::from_millis(10),
+            max_attempts: 2,
+            ..Default::default()
+        };
+        let target = ws
+            .with_stream_config(ws.stream_config().backoff(policy))
+            .target(format!("ws://{}/ws", addr));
+        
+        assert!(BinanceWebSocket::connect_with_retry(&target).await.is_err());
+        
+        let events = capture.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event["attempt"], "1");
+        assert_eq!(event["delay_ms"], "10");
+        assert_eq!(event["url"], format!("ws://{}/ws", addr));
+        assert!(event.contains_key("error"));
+        assert!(event["message"].contains("backing off"));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_pause_logs_attempt() {
+        let capture = EventCapture::default();
+        let _guard = tracing::subscriber::set_default(capture.clone());
+
+        let url = "ws://127.0.0.1:1/ws".to_string();
+        let target = fast_reconnect_ws(url.clone()).target(url);
+        let (tx, rx) = mpsc::channel::<()>(1);
+
+        // Dropping the receiver cuts the pause short
+        let pause = BinanceWebSocket::reconnect_pause(&target, &tx, 3);
+        let (reconnect, _) = tokio::join!(pause, async move { drop(rx) });
+        assert!(!reconnect);
+
+        let events = capture.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event["attempt"], "3");
+        assert_eq!(event["delay_ms"], "50");
+        assert_eq!(event["url"], "ws://127.0.0.1:1/ws");
+        assert!(event["message"].contains("reconnecting"));
+    }
+
+    fn depth_event(first: i64, last: i64, bids: &str, asks: &str) -> WsDepthData {
+        serde_json::from_str(&format!(
+            r#"{{"e":"depthUpdate","E":1,"s":"BTCUSDT","U":{},"u":{},"b":{},"a":{}}}"#,
+            first, last, bids, asks
+        ))
+        .unwrap()
+    }
+
+    fn snapshot() -> OrderBook {
+        let level = |price: f64| PriceLevel { price, quantity: 1.0 };
+        OrderBook {
+            symbol: "BTCUSDT".to_string(),
+            last_update_id: 100,
+            bids: (0..5).map(|i| level(100.0 - i as f64)).collect(),
+            asks: (0..5).map(|i| level(101.0 + i as f64)).collect(),
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            requested_limit: None,
+        }
+    }
+
+    #[test]
+    fn test_managed_book_respects_max_levels() {
+        let mut sync = OrderBookSync::new(snapshot(), Some(3));
+        assert_eq!(sync.book.bids.len(), 3);
+        assert_eq!(sync.book.asks.len(), 3);
+        
+        let events = [
+            depth_event(95, 101, r#"[["100.5","2"]]"#, r#"[["100.8","2"]]"#),
+            depth_event(102, 102, r#"[["99.5","1"],["100.7","1"]]"#, r#"[]"#),
+            depth_event(103, 103, r#"[["100.7","0"]]"#, r#"[["100.9","3"],["100.6","1"]]"#),
+        ];
+        
+        for event in &events {
+            let book = sync.apply(event).unwrap().unwrap();
+            assert!(book.bids.len() <= 3);
+            assert!(book.asks.len() <= 3);
+        }
+        
+        let prices = |levels: &[PriceLevel]| levels.iter().map(|l| l.price).collect::<Vec<_>>();
+        // Truncated levels are gone, so a removal can leave a side short
+        assert_eq!(prices(&sync.book.bids), vec![100.5, 100.0]);
+        assert_eq!(prices(&sync.book.asks), vec![100.6, 100.8, 100.9]);
+        assert_eq!(sync.book.last_update_id, 103);
+    }
+
+    #[test]
+    fn test_managed_book_sequencing() {
+        let mut sync = OrderBookSync::new(snapshot(), None);
+        
+        // Older than the snapshot
+        assert!(sync.apply(&depth_event(90, 100, "[]", "[]")).unwrap().is_none());
+        // First event must straddle lastUpdateId + 1
+        assert!(sync.apply(&depth_event(102, 105, "[]", "[]")).is_err());
+        assert!(sync.apply(&depth_event(99, 105, "[]", "[]")).unwrap().is_some());
+        // Later events must be contiguous
+        assert!(sync.apply(&depth_event(107, 108, "[]", "[]")).is_err());
+        assert!(sync.apply(&depth_event(106, 108, "[]", "[]")).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_managed_book_uses_event_time() {
+        let mut sync = OrderBookSync::new(snapshot(), None);
+        
+        let book = sync.apply(&depth_event(99, 101, "[]", "[]")).unwrap().unwrap();
+        assert_eq!(book.timestamp.timestamp_millis(), 1);
+        assert!(book.received_at > book.timestamp);
+        
+        // Without an event time the receive time is used
+        let event: WsDepthData = serde_json::from_str(
+            r#"{"e":"depthUpdate","s":"BTCUSDT","U":102,"u":102,"b":[],"a":[]}"#,
+        )
+        .unwrap();
+        let book = sync.apply(&event).unwrap().unwrap();
+        assert_eq!(book.timestamp, book.received_at);
+    }
+
+    #[test]
+    fn test_mini_ticker_deserialization() {
+        let frame = r#"{
+            "e": "24hrMiniTicker",
+            "E": 1672515782136,
+            "s": "BNBBTC",
+            "c": "0.0025",
+            "o": "0.0010",
+            "h": "0.0025",
+            "l": "0.0010",
+            "v": "10000",
+            "q": "18"
+        }"#;
+        
+        let ticker = serde_json::from_str::<WsMiniTickerData>(frame)
+            .unwrap()
+            .to_mini_ticker();
+        
+        assert_eq!(ticker.symbol, "BNBBTC");
+        assert_eq!(ticker.price(), 0.0025);
+        assert_eq!(ticker.open_price, 0.0010);
+        assert_eq!(ticker.high_price, 0.0025);
+        assert_eq!(ticker.low_price, 0.0010);
+        assert_eq!(ticker.volume, 10000.0);
+        assert_eq!(ticker.quote_volume, 18.0);
+        assert_eq!(ticker.event_time.timestamp_millis(), 1672515782136);
+        
+        // Combined streams keep the server event time too
+        let data = serde_json::from_str(frame).unwrap();
+        match decode_market_event(data).unwrap() {
+            (MarketEvent::MiniTicker(decoded), _) => assert_eq!(decoded, ticker),
+            other => panic!("expected a mini ticker, got {:?}", other),
+        }
+    }
+
     #[tokio::test]
     #[ignore] // Only run manually (connects to real WebSocket)
     async fn test_ticker_stream() {
@@ -862,7 +3759,7 @@ mod tests {
         let config = BinanceConfig::new(false);
         let ws = BinanceWebSocket::new(config).unwrap();
         
-        let mut stream = ws.kline_stream("BTCUSDT", Interval::Minutes1).await.unwrap();
+        let mut stream = ws.kline_stream("BTCUSDT", Interval::Minutes1, None).await.unwrap();
         
         if let Some(result) = stream.recv().await {
             assert!(result.is_ok());
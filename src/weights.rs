@@ -0,0 +1,116 @@
+//! Request weights charged against the rate limiter
+//!
+//! Binance charges every REST endpoint a request weight and adjusts the
+//! values from time to time. [`WeightTable`] holds the published defaults
+//! and lets individual endpoints be overridden from the configuration.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Request weight per endpoint path
+///
+/// Endpoints without an override use [`default_weight`]. An override
+/// replaces the weight of its path for every request size.
+///
+/// # Example
+/// ```
+/// use binance_connector::WeightTable;
+///
+/// let weights = WeightTable::new().with_weight("/api/v3/depth", 10);
+/// assert_eq!(weights.weight("/api/v3/depth", Some(5000)), 10);
+/// assert_eq!(weights.weight("/api/v3/klines", None), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WeightTable {
+    overrides: HashMap<String, u32>,
+}
+
+impl WeightTable {
+    /// Table with the published default weights
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Charge `weight` for every request to `path` (e.g. `/api/v3/depth`)
+    pub fn with_weight(mut self, path: impl Into<String>, weight: u32) -> Self {
+        self.overrides.insert(path.into(), weight);
+        self
+    }
+
+    /// Whether no endpoint is overridden
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    /// Weight of one request to `path`
+    ///
+    /// `size` is the endpoint's sizing parameter: `limit` for depth, the
+    /// number of symbols for ticker endpoints (`None` meaning all symbols).
+    /// Other endpoints ignore it.
+    pub fn weight(&self, path: &str, size: Option<usize>) -> u32 {
+        self.overrides
+            .get(path)
+            .copied()
+            .unwrap_or_else(|| default_weight(path, size))
+    }
+}
+
+/// Published Binance spot weight of one request to `path`
+///
+/// Endpoints not listed here count as 1.
+pub fn default_weight(path: &str, size: Option<usize>) -> u32 {
+    match path {
+        "/api/v3/depth" => match size.unwrap_or(100) {
+            0..=100 => 5,
+            101..=500 => 25,
+            501..=1000 => 50,
+            _ => 250,
+        },
+        "/api/v3/ticker/24hr" => match size {
+            Some(0..=20) => 2,
+            Some(21..=100) => 40,
+            _ => 80,
+        },
+        "/api/v3/ticker/price" | "/api/v3/ticker/bookTicker" => match size {
+            Some(1) => 2,
+            _ => 4,
+        },
+        "/api/v3/exchangeInfo" | "/api/v3/allOrders" | "/api/v3/myTrades" => 20,
+        "/api/v3/trades" | "/api/v3/historicalTrades" => 25,
+        "/api/v3/aggTrades" => 4,
+        "/api/v3/klines" | "/api/v3/uiKlines" | "/api/v3/avgPrice" => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoints::Endpoints;
+
+    #[test]
+    fn test_default_weights() {
+        assert_eq!(default_weight(Endpoints::depth(), None), 5);
+        assert_eq!(default_weight(Endpoints::depth(), Some(500)), 25);
+        assert_eq!(default_weight(Endpoints::depth(), Some(5000)), 250);
+        assert_eq!(default_weight(Endpoints::ticker_24h(), Some(1)), 2);
+        assert_eq!(default_weight(Endpoints::ticker_24h(), Some(50)), 40);
+        assert_eq!(default_weight(Endpoints::ticker_24h(), None), 80);
+        assert_eq!(default_weight(Endpoints::book_ticker(), None), 4);
+        assert_eq!(default_weight(Endpoints::exchange_info(), None), 20);
+        assert_eq!(default_weight(Endpoints::ping(), None), 1);
+    }
+
+    #[test]
+    fn test_override() {
+        let weights = WeightTable::new().with_weight(Endpoints::exchange_info(), 10);
+        assert_eq!(weights.weight(Endpoints::exchange_info(), None), 10);
+        assert_eq!(weights.weight(Endpoints::trades(), None), 25);
+
+        // Serializes as a plain path -> weight map
+        let json = serde_json::to_string(&weights).unwrap();
+        assert_eq!(json, r#"{"/api/v3/exchangeInfo":10}"#);
+        assert_eq!(serde_json::from_str::<WeightTable>(&json).unwrap(), weights);
+    }
+}
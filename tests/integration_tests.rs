@@ -83,7 +83,7 @@ async fn test_get_ticker_24h() {
     assert!(ticker.volume > 0.0);
     assert!(ticker.high_price >= ticker.low_price);
     assert!(ticker.ask_price >= ticker.bid_price);
-    assert!(ticker.spread() >= 0.0);
+    assert!(ticker.try_spread().is_some_and(|s| s >= 0.0));
     
     println!("ETH/USDT: ${} (24h change: {:.2}%)",
         ticker.last_price,
@@ -1,6 +1,9 @@
 //! Mock server tests (no real API calls needed)
 
-use binance_connector::{BinanceClient, BinanceConfig};
+use binance_connector::{
+    BinanceClient, BinanceConfig, CachingClient, CancelReplaceMode, CancelReplaceStatus,
+    NewOrderRespType, OcoOrderRequest, OrderSide, OrderStatus, OrderType, Symbol, SymbolFilter, TimeInForce,
+};
 use mockito::{Server, Matcher};
 
 async fn create_mock_client(server: &Server) -> BinanceClient {
@@ -31,10 +34,93 @@ async fn test_mock_ticker_price() {
     
     assert_eq!(ticker.symbol, "BTCUSDT");
     assert_eq!(ticker.price, 43250.50);
+    // The price endpoint reports no server time
+    assert!(ticker.timestamp.is_none());
     
     mock.assert_async().await;
 }
 
+#[tokio::test]
+async fn test_mock_malformed_price_is_error() {
+    let mut server = Server::new_async().await;
+
+    let mock = server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()))
+        .with_status(200)
+        .with_body(r#"{"symbol": "BTCUSDT", "price": "43,250.50"}"#)
+        .create_async()
+        .await;
+
+    let client = create_mock_client(&server).await;
+    let err = client.get_ticker_price("BTCUSDT").await.unwrap_err();
+
+    // Reported with the field name instead of read as a zero price
+    match err {
+        binance_connector::Error::DeserializationError(msg) => assert!(msg.starts_with("price")),
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_symbol_normalized() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()))
+        .with_status(200)
+        .with_body(r#"{"symbol": "BTCUSDT", "price": "43250.50"}"#)
+        .expect(3)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    for symbol in ["BTC/USDT", "btc-usdt", "BTCUSDT"] {
+        assert_eq!(client.get_ticker_price(symbol).await.unwrap().symbol, "BTCUSDT");
+    }
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_ticker_symbol_mismatch() {
+    let mut server = Server::new_async().await;
+    
+    let price = server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()))
+        .with_status(200)
+        .with_body(r#"{"symbol": "ETHUSDT", "price": "2250.50"}"#)
+        .create_async()
+        .await;
+    let book = server.mock("GET", "/api/v3/ticker/bookTicker")
+        .match_query(Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()))
+        .with_status(200)
+        .with_body(r#"{
+            "symbol": "ETHUSDT",
+            "bidPrice": "2250.00", "bidQty": "1.0",
+            "askPrice": "2250.50", "askQty": "2.0"
+        }"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    
+    match client.get_ticker_price("BTCUSDT").await {
+        Err(binance_connector::Error::DeserializationError(msg)) => {
+            assert!(msg.contains("BTCUSDT") && msg.contains("ETHUSDT"));
+        }
+        other => panic!("expected symbol mismatch, got {:?}", other),
+    }
+    assert!(matches!(
+        client.get_book_ticker("BTCUSDT").await,
+        Err(binance_connector::Error::DeserializationError(_))
+    ));
+    
+    price.assert_async().await;
+    book.assert_async().await;
+}
+
 #[tokio::test]
 async fn test_mock_24h_ticker() {
     let mut server = Server::new_async().await;
@@ -198,4 +284,1758 @@ async fn test_mock_depth() {
     assert_eq!(order_book.asks[0].price, 43001.0);
     
     mock.assert_async().await;
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_mock_time_sync_updates_offset() {
+    let mut server = Server::new_async().await;
+    
+    // Server clock one minute ahead of ours
+    let server_time = chrono::Utc::now().timestamp_millis() + 60_000;
+    let mock = server.mock("GET", "/api/v3/time")
+        .with_status(200)
+        .with_body(format!(r#"{{"serverTime": {}}}"#, server_time))
+        .expect_at_least(1)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    assert_eq!(client.time_offset_ms(), 0);
+    
+    let handle = client.spawn_time_sync(std::time::Duration::from_millis(50));
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    handle.shutdown().await;
+    
+    let offset = client.time_offset_ms();
+    assert!((55_000..=60_500).contains(&offset), "unexpected offset: {}", offset);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_time_sync_outlives_dropped_handle() {
+    let mut server = Server::new_async().await;
+    
+    let server_time = chrono::Utc::now().timestamp_millis();
+    let mock = server.mock("GET", "/api/v3/time")
+        .with_status(200)
+        .with_body(format!(r#"{{"serverTime": {}}}"#, server_time))
+        .expect_at_least(4)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    drop(client.spawn_time_sync(std::time::Duration::from_millis(50)));
+    
+    // Syncs keep arriving after the handle is gone
+    tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+    mock.assert_async().await;
+}
+
+async fn create_mock_auth_client(server: &Server) -> BinanceClient {
+    let mut config = BinanceConfig::with_auth("test_key".to_string(), "test_secret".to_string(), false);
+    config.base_url = Some(server.url());
+    config.enable_retries = false;
+    
+    BinanceClient::new(config).unwrap()
+}
+
+/// BTCUSDT with a 0.01 tick and 0.00001 step, as listed on Binance
+fn btcusdt() -> Symbol {
+    Symbol {
+        symbol: "BTCUSDT".to_string(),
+        status: "TRADING".to_string(),
+        base_asset: "BTC".to_string(),
+        quote_asset: "USDT".to_string(),
+        base_asset_precision: 8,
+        quote_asset_precision: 8,
+        order_types: vec![],
+        filters: vec![
+            SymbolFilter::PriceFilter { min_price: 0.01, max_price: 1_000_000.0, tick_size: 0.01 },
+            SymbolFilter::LotSize { min_qty: 0.00001, max_qty: 9000.0, step_size: 0.00001 },
+        ],
+        permissions: vec![],
+    }
+}
+
+async fn mock_ticker_price(server: &mut Server, price: &str) -> mockito::Mock {
+    server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(format!(r#"{{"symbol": "BTCUSDT", "price": "{}"}}"#, price))
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn test_mock_place_oco_order() {
+    let mut server = Server::new_async().await;
+    
+    let price_mock = mock_ticker_price(&mut server, "43000.00").await;
+    let mock = server.mock("POST", "/api/v3/order/oco")
+        .match_header("X-MBX-APIKEY", "test_key")
+        .match_header("content-type", "application/x-www-form-urlencoded")
+        .match_query(Matcher::Missing)
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+            Matcher::UrlEncoded("side".into(), "SELL".into()),
+            Matcher::UrlEncoded("stopPrice".into(), "42000".into()),
+            Matcher::UrlEncoded("stopLimitTimeInForce".into(), "GTC".into()),
+            Matcher::UrlEncoded("newOrderRespType".into(), "FULL".into()),
+            Matcher::Regex("timestamp=\\d+".into()),
+            Matcher::Regex("signature=[0-9a-f]{64}".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{
+            "orderListId": 0,
+            "contingencyType": "OCO",
+            "listStatusType": "EXEC_STARTED",
+            "listOrderStatus": "EXECUTING",
+            "listClientOrderId": "JYVpp3F0f5CAG15DhtrqLp",
+            "transactionTime": 1563417480525,
+            "symbol": "BTCUSDT",
+            "orders": [
+                {"symbol": "BTCUSDT", "orderId": 2, "clientOrderId": "Kk7sqHb9J6mJWTMDVW7Vos"},
+                {"symbol": "BTCUSDT", "orderId": 3, "clientOrderId": "xTXKaGYd4bluPVp78IVRvl"}
+            ],
+            "orderReports": [
+                {
+                    "symbol": "BTCUSDT",
+                    "orderId": 2,
+                    "orderListId": 0,
+                    "clientOrderId": "Kk7sqHb9J6mJWTMDVW7Vos",
+                    "transactTime": 1563417480525,
+                    "price": "41900.00",
+                    "origQty": "0.5",
+                    "executedQty": "0.0",
+                    "cummulativeQuoteQty": "0.0",
+                    "status": "NEW",
+                    "timeInForce": "GTC",
+                    "type": "STOP_LOSS_LIMIT",
+                    "side": "SELL",
+                    "stopPrice": "42000.00"
+                },
+                {
+                    "symbol": "BTCUSDT",
+                    "orderId": 3,
+                    "orderListId": 0,
+                    "clientOrderId": "xTXKaGYd4bluPVp78IVRvl",
+                    "transactTime": 1563417480525,
+                    "price": "44000.00",
+                    "origQty": "0.5",
+                    "executedQty": "0.0",
+                    "cummulativeQuoteQty": "0.0",
+                    "status": "NEW",
+                    "timeInForce": "GTC",
+                    "type": "LIMIT_MAKER",
+                    "side": "SELL"
+                }
+            ]
+        }"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    let oco = client
+        .place_oco_order(&btcusdt(), OcoOrderRequest::new(OrderSide::Sell, 0.5, 44000.0, 42000.0, 41900.0))
+        .await
+        .unwrap();
+    
+    assert_eq!(oco.order_list_id, 0);
+    assert_eq!(oco.contingency_type, "OCO");
+    assert_eq!(oco.orders.len(), 2);
+    assert_eq!(oco.orders[0].order_type, "STOP_LOSS_LIMIT");
+    assert_eq!(oco.orders[0].stop_price, Some(42000.0));
+    assert_eq!(oco.orders[1].order_type, "LIMIT_MAKER");
+    assert_eq!(oco.orders[1].stop_price, None);
+    assert!(oco.orders.iter().all(|o| o.status == OrderStatus::New));
+    
+    price_mock.assert_async().await;
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_place_order_ack() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("POST", "/api/v3/order")
+        .match_header("X-MBX-APIKEY", "test_key")
+        .match_query(Matcher::Missing)
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+            Matcher::UrlEncoded("side".into(), "BUY".into()),
+            Matcher::UrlEncoded("type".into(), "LIMIT".into()),
+            Matcher::UrlEncoded("timeInForce".into(), "GTC".into()),
+            Matcher::UrlEncoded("price".into(), "40000".into()),
+            Matcher::UrlEncoded("newOrderRespType".into(), "ACK".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{
+            "symbol": "BTCUSDT",
+            "orderId": 28,
+            "orderListId": -1,
+            "clientOrderId": "6gCrw2kRUAF9CvJDGP16IP",
+            "transactTime": 1507725176595
+        }"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    let order = client
+        .place_order(&btcusdt(), OrderSide::Buy, OrderType::Limit, 0.01, 40000.0, NewOrderRespType::Ack)
+        .await
+        .unwrap();
+    
+    assert_eq!(order.order_id, 28);
+    assert_eq!(order.order_list_id, -1);
+    assert_eq!(order.transact_time.timestamp_millis(), 1507725176595);
+    assert!(order.order.is_none());
+    assert!(order.fills.is_empty());
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_place_market_order_defaults_to_full() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("POST", "/api/v3/order")
+        .match_query(Matcher::Missing)
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("type".into(), "MARKET".into()),
+            Matcher::UrlEncoded("quantity".into(), "0.3".into()),
+            Matcher::UrlEncoded("newOrderRespType".into(), "FULL".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{
+            "symbol": "BTCUSDT",
+            "orderId": 29,
+            "orderListId": -1,
+            "clientOrderId": "6gCrw2kRUAF9CvJDGP16IQ",
+            "transactTime": 1507725176595,
+            "price": "0.00000000",
+            "origQty": "0.30000000",
+            "executedQty": "0.30000000",
+            "cummulativeQuoteQty": "12900.00000000",
+            "status": "FILLED",
+            "timeInForce": "GTC",
+            "type": "MARKET",
+            "side": "BUY",
+            "fills": [
+                {"price": "43000.00", "qty": "0.20000000", "commission": "0.00020000",
+                 "commissionAsset": "BTC", "tradeId": 56},
+                {"price": "43000.00", "qty": "0.10000000", "commission": "0.00010000",
+                 "commissionAsset": "BTC", "tradeId": 57}
+            ]
+        }"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    // 0.30000000000000004 must not reach the exchange
+    let order = client
+        .place_order(&btcusdt(), OrderSide::Buy, OrderType::Market, 0.1 + 0.2, None, None)
+        .await
+        .unwrap();
+    
+    let details = order.order.unwrap();
+    assert_eq!(details.status, OrderStatus::Filled);
+    assert_eq!(details.executed_qty, 0.3);
+    assert_eq!(order.fills.len(), 2);
+    assert_eq!(order.fills[0].trade_id, 56);
+    assert_eq!(order.fills[0].quantity, 0.2);
+    assert_eq!(order.fills[1].commission, 0.0001);
+    assert_eq!(order.fills[1].commission_asset, "BTC");
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_place_limit_order_requires_price() {
+    let server = Server::new_async().await;
+    let client = create_mock_auth_client(&server).await;
+    
+    let result = client
+        .place_order(&btcusdt(), OrderSide::Sell, OrderType::Limit, 0.1, None, None)
+        .await;
+    assert!(matches!(result, Err(binance_connector::Error::InvalidOrder(_))));
+}
+
+#[tokio::test]
+async fn test_mock_oco_rejects_stop_on_wrong_side() {
+    let mut server = Server::new_async().await;
+    
+    let _price_mock = mock_ticker_price(&mut server, "43000.00").await;
+    let mock = server.mock("POST", "/api/v3/order/oco")
+        .match_query(Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    
+    // A SELL stop above the current price would trigger immediately
+    let result = client
+        .place_oco_order(
+            &btcusdt(),
+            OcoOrderRequest::new(OrderSide::Sell, 0.5, 44000.0, 43500.0, 43400.0)
+                .stop_limit_time_in_force(TimeInForce::Fok)
+                .resp_type(NewOrderRespType::Ack),
+        )
+        .await;
+    assert!(matches!(result, Err(binance_connector::Error::InvalidOrder(_))));
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_get_all_orders() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/allOrders")
+        .match_header("X-MBX-APIKEY", "test_key")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+            Matcher::UrlEncoded("orderId".into(), "1".into()),
+            Matcher::UrlEncoded("limit".into(), "2".into()),
+            Matcher::Regex("signature=[0-9a-f]{64}".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"[
+            {
+                "symbol": "BTCUSDT",
+                "orderId": 1,
+                "orderListId": -1,
+                "clientOrderId": "myOrder1",
+                "price": "43000.00",
+                "origQty": "1.0",
+                "executedQty": "1.0",
+                "cummulativeQuoteQty": "43000.00",
+                "status": "FILLED",
+                "timeInForce": "GTC",
+                "type": "LIMIT",
+                "side": "BUY",
+                "stopPrice": "0.0",
+                "icebergQty": "0.0",
+                "time": 1640000000000,
+                "updateTime": 1640000100000,
+                "isWorking": true,
+                "origQuoteOrderQty": "0.000000"
+            },
+            {
+                "symbol": "BTCUSDT",
+                "orderId": 2,
+                "orderListId": -1,
+                "clientOrderId": "myOrder2",
+                "price": "45000.00",
+                "origQty": "0.5",
+                "executedQty": "0.0",
+                "cummulativeQuoteQty": "0.0",
+                "status": "CANCELED",
+                "timeInForce": "GTC",
+                "type": "LIMIT",
+                "side": "SELL",
+                "stopPrice": "0.0",
+                "icebergQty": "0.0",
+                "time": 1640000200000,
+                "updateTime": 1640000300000,
+                "isWorking": true,
+                "origQuoteOrderQty": "0.000000"
+            }
+        ]"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    let orders = client.get_all_orders("BTCUSDT", Some(1), None, None, 2).await.unwrap();
+    
+    assert_eq!(orders.len(), 2);
+    assert_eq!(orders[0].status, OrderStatus::Filled);
+    assert_eq!(orders[0].side, OrderSide::Buy);
+    assert_eq!(orders[0].cumulative_quote_qty, 43000.0);
+    assert_eq!(orders[1].status, OrderStatus::Canceled);
+    assert_eq!(orders[1].executed_qty, 0.0);
+    assert_eq!(orders[1].time.unwrap().timestamp_millis(), 1640000200000);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_get_all_orders_limit_validation() {
+    let server = Server::new_async().await;
+    let client = create_mock_auth_client(&server).await;
+    
+    let result = client.get_all_orders("BTCUSDT", None, None, None, 1001).await;
+    assert!(matches!(result, Err(binance_connector::Error::ConfigError(_))));
+}
+
+#[tokio::test]
+async fn test_get_klines_configured_limit() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/klines")
+        .match_query(Matcher::UrlEncoded("limit".into(), "1000".into()))
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    assert!(client.get_klines("BTCUSDT", binance_connector::Interval::Hours1, 1000).await.is_ok());
+    
+    // A region capping klines at 500 rejects the same limit before sending it
+    let regional = client.clone_with_config(|config| config.max_kline_limit = 500).unwrap();
+    let result = regional.get_klines("BTCUSDT", binance_connector::Interval::Hours1, 1000).await;
+    assert!(matches!(result, Err(binance_connector::Error::ConfigError(_))));
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_signed_requests_reuse_time_sync() {
+    let mut server = Server::new_async().await;
+    
+    let time = server.mock("GET", "/api/v3/time")
+        .with_status(200)
+        .with_body_from_request(|_| {
+            let server_time = chrono::Utc::now().timestamp_millis() + 5_000;
+            format!(r#"{{"serverTime":{}}}"#, server_time).into()
+        })
+        .expect(2)
+        .create_async()
+        .await;
+    let trades = server.mock("GET", "/api/v3/myTrades")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body("[]")
+        .expect(2)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    client.get_my_trades("BTCUSDT", None, None, None, None).await.unwrap();
+    let offset = client.time_offset_ms();
+    assert!((4_500..=5_500).contains(&offset), "unexpected offset: {}", offset);
+    
+    // Second request within the staleness window reuses the offset
+    client.get_my_trades("BTCUSDT", None, None, None, None).await.unwrap();
+    assert_eq!(client.time_offset_ms(), offset);
+    
+    // Forcing always asks the server
+    client.force_time_sync().await.unwrap();
+    
+    time.assert_async().await;
+    trades.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_get_my_trades() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/myTrades")
+        .match_header("X-MBX-APIKEY", "test_key")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+            Matcher::UrlEncoded("fromId".into(), "28457".into()),
+            Matcher::UrlEncoded("startTime".into(), "1640000000000".into()),
+            Matcher::Regex("signature=[0-9a-f]{64}".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"[
+            {
+                "symbol": "BTCUSDT",
+                "id": 28457,
+                "orderId": 100234,
+                "orderListId": -1,
+                "price": "43000.00",
+                "qty": "0.5",
+                "quoteQty": "21500.00",
+                "commission": "0.0005",
+                "commissionAsset": "BTC",
+                "time": 1640000000000,
+                "isBuyer": true,
+                "isMaker": false,
+                "isBestMatch": true
+            }
+        ]"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    let trades = client
+        .get_my_trades("BTCUSDT", Some(28457), Some(1640000000000), None, 500)
+        .await
+        .unwrap();
+    
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].order_id, 100234);
+    assert_eq!(trades[0].commission, 0.0005);
+    assert_eq!(trades[0].commission_asset, "BTC");
+    assert!(trades[0].is_buyer);
+    assert!(!trades[0].is_maker);
+    assert!((trades[0].net_quantity("BTC") - 0.4995).abs() < 1e-12);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_klines_without_limit() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/klines")
+        .match_query(Matcher::Exact("symbol=BTCUSDT&interval=5m".into()))
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let klines = client
+        .get_klines("BTCUSDT", binance_connector::Interval::Minutes5, None)
+        .await
+        .unwrap();
+    
+    assert!(klines.is_empty());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_closed_klines_drop_forming_candle() {
+    let mut server = Server::new_async().await;
+    
+    // Last candle closes in 2100, so it is still forming
+    let mock = server.mock("GET", "/api/v3/klines")
+        .match_query(Matcher::Exact("symbol=BTCUSDT&interval=5m&limit=3".into()))
+        .with_status(200)
+        .with_body(r#"[
+            [1640000000000, "43000.00", "43100.00", "42900.00", "43050.00", "100.5",
+             1640000299999, "4320000.00", 1000, "50.25", "2160000.00", "0"],
+            [1640000300000, "43050.00", "43200.00", "43000.00", "43150.00", "80.0",
+             1640000599999, "3450000.00", 800, "40.00", "1725000.00", "0"],
+            [4102444800000, "43150.00", "43160.00", "43140.00", "43155.00", "1.0",
+             4102445099999, "43155.00", 10, "0.50", "21577.50", "0"]
+        ]"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let klines = client
+        .get_closed_klines("BTCUSDT", binance_connector::Interval::Minutes5, 2)
+        .await
+        .unwrap();
+    
+    assert_eq!(klines.len(), 2);
+    assert_eq!(klines[0].close, 43050.0);
+    assert_eq!(klines[1].close, 43150.0);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_caching_client_reuses_closed_range() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/klines")
+        .match_query(Matcher::Exact(
+            "symbol=BTCUSDT&interval=5m&startTime=1640000000000&endTime=1640000599999".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"[
+            [1640000000000, "43000.00", "43100.00", "42900.00", "43050.00", "100.5",
+             1640000299999, "4320000.00", 1000, "50.25", "2160000.00", "0"],
+            [1640000300000, "43050.00", "43200.00", "43000.00", "43150.00", "80.0",
+             1640000599999, "3450000.00", 800, "40.00", "1725000.00", "0"]
+        ]"#)
+        .expect(1)
+        .create_async()
+        .await;
+    
+    let client = CachingClient::new(create_mock_client(&server).await, 16);
+    let interval = binance_connector::Interval::Minutes5;
+    
+    let first = client
+        .get_klines_range("BTCUSDT", interval, 1640000000000, 1640000599999)
+        .await
+        .unwrap();
+    let second = client
+        .get_klines_range("BTCUSDT", interval, 1640000000000, 1640000599999)
+        .await
+        .unwrap();
+    
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 2);
+    assert_eq!(second[1].close, 43150.0);
+    assert_eq!(client.len(), 1);
+    
+    // Only one request reached the server
+    mock.assert_async().await;
+    
+    client.clear();
+    assert!(client.is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_exchange_info_order_count_filters() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/exchangeInfo")
+        .with_status(200)
+        .with_body(r#"{
+            "timezone": "UTC",
+            "serverTime": 1640000000000,
+            "symbols": [{
+                "symbol": "BTCUSDT",
+                "status": "TRADING",
+                "baseAsset": "BTC",
+                "baseAssetPrecision": 8,
+                "quoteAsset": "USDT",
+                "quoteAssetPrecision": 8,
+                "orderTypes": ["LIMIT", "MARKET"],
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "minPrice": "0.01", "maxPrice": "1000000.00", "tickSize": "0.01"},
+                    {"filterType": "LOT_SIZE", "minQty": "0.00001", "maxQty": "9000.0", "stepSize": "0.00001"},
+                    {"filterType": "MAX_NUM_ORDERS", "maxNumOrders": 200},
+                    {"filterType": "MAX_NUM_ALGO_ORDERS", "maxNumAlgoOrders": 5},
+                    {"filterType": "TRAILING_DELTA", "minTrailingAboveDelta": 10}
+                ]
+            }]
+        }"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let symbols = client.get_exchange_info().await.unwrap();
+    
+    assert_eq!(symbols.len(), 1);
+    let btc = &symbols[0];
+    assert_eq!(btc.base_asset, "BTC");
+    assert_eq!(btc.max_open_orders(), Some(200));
+    assert_eq!(btc.max_open_algo_orders(), Some(5));
+    assert_eq!(
+        btc.filters[0],
+        SymbolFilter::PriceFilter { min_price: 0.01, max_price: 1_000_000.0, tick_size: 0.01 }
+    );
+    assert_eq!(btc.filters[4], SymbolFilter::Other("TRAILING_DELTA".to_string()));
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_user_agent_header() {
+    let mut server = Server::new_async().await;
+    
+    let default_ua = server.mock("GET", "/api/v3/ping")
+        .match_header("user-agent", concat!("binance-connector-rs/", env!("CARGO_PKG_VERSION")))
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    assert!(client.ping().await.unwrap());
+    default_ua.assert_async().await;
+    
+    let custom_ua = server.mock("GET", "/api/v3/ping")
+        .match_header("user-agent", "my-bot/1.0")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+    
+    let custom = client
+        .clone_with_config(|c| c.user_agent = Some("my-bot/1.0".to_string()))
+        .unwrap();
+    assert!(custom.ping().await.unwrap());
+    custom_ua.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_spreads_ranked() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/ticker/bookTicker")
+        .with_status(200)
+        .with_body(r#"[
+            {"symbol": "ALTUSDT", "bidPrice": "0.9900", "bidQty": "100.0", "askPrice": "1.0100", "askQty": "100.0"},
+            {"symbol": "DEADUSDT", "bidPrice": "0.00000000", "bidQty": "0.0", "askPrice": "2.0000", "askQty": "5.0"},
+            {"symbol": "BTCUSDT", "bidPrice": "42999.00", "bidQty": "1.5", "askPrice": "43001.00", "askQty": "1.2"}
+        ]"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let spreads = client.spreads_ranked().await.unwrap();
+    
+    let symbols: Vec<&str> = spreads.iter().map(|(s, _)| s.as_str()).collect();
+    assert_eq!(symbols, vec!["BTCUSDT", "ALTUSDT"]);
+    assert!((spreads[0].1 - 2.0 / 43000.0 * 10_000.0).abs() < 1e-9);
+    assert!((spreads[1].1 - 200.0).abs() < 1e-9);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_adaptive_rate_limit_slows_near_limit() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("X-MBX-USED-WEIGHT-1M", "1170")
+        .with_body(r#"{"symbol": "BTCUSDT", "price": "43250.50"}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    
+    let mut config = BinanceConfig::new(false);
+    config.base_url = Some(server.url());
+    config.adaptive_rate_limit = true;
+    let client = BinanceClient::new(config).unwrap();
+    
+    // 1170 of 1200 is 97.5% of the limit
+    client.get_ticker_price("BTCUSDT").await.unwrap();
+    
+    let start = std::time::Instant::now();
+    client.get_ticker_price("BTCUSDT").await.unwrap();
+    assert!(start.elapsed() >= std::time::Duration::from_millis(800));
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_klines_all_pages_full_history() {
+    use futures::TryStreamExt;
+    
+    let mut server = Server::new_async().await;
+    
+    // Listed at 1640000000000; the first page is full, the second catches up
+    let listing = 1_640_000_000_000_i64;
+    let candle = |open: i64| format!(
+        r#"[{}, "1.0", "2.0", "0.5", "1.5", "10.0", {}, "15.0", 5, "4.0", "6.0", "0"]"#,
+        open,
+        open + 59_999
+    );
+    let first_page: Vec<String> = (0..1000).map(|i| candle(listing + i * 60_000)).collect();
+    let second_start = listing + 1000 * 60_000;
+    let second_page = [
+        candle(second_start),
+        candle(second_start + 60_000),
+        // Still forming
+        candle(4_102_444_800_000),
+    ];
+    
+    let first = server.mock("GET", "/api/v3/klines")
+        .match_query(Matcher::Exact("symbol=BTCUSDT&interval=1m&startTime=0&limit=1000".into()))
+        .with_status(200)
+        .with_body(format!("[{}]", first_page.join(",")))
+        .create_async()
+        .await;
+    let second = server.mock("GET", "/api/v3/klines")
+        .match_query(Matcher::Exact(format!(
+            "symbol=BTCUSDT&interval=1m&startTime={}&limit=1000",
+            second_start
+        )))
+        .with_status(200)
+        .with_body(format!("[{}]", second_page.join(",")))
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let klines: Vec<_> = client
+        .get_klines_all("BTCUSDT", binance_connector::Interval::Minutes1)
+        .try_collect()
+        .await
+        .unwrap();
+    
+    assert_eq!(klines.len(), 1002);
+    assert_eq!(klines[0].open_time.timestamp_millis(), listing);
+    assert_eq!(
+        klines.last().unwrap().open_time.timestamp_millis(),
+        second_start + 60_000
+    );
+    assert!(klines.windows(2).all(|w| w[0].open_time < w[1].open_time));
+    
+    first.assert_async().await;
+    second.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_klines_backfill_plan_execute() {
+    use futures::TryStreamExt;
+    
+    let mut server = Server::new_async().await;
+    
+    let start = 1_640_995_200_000_i64;
+    let end = start + 2 * 60_000;
+    let candle = |open: i64| format!(
+        r#"[{}, "1.0", "2.0", "0.5", "1.5", "10.0", {}, "15.0", 5, "4.0", "6.0", "0"]"#,
+        open,
+        open + 59_999
+    );
+    
+    let mut pages = Vec::new();
+    for (page_start, opens) in [(start, vec![start, start + 60_000]), (end, vec![end])] {
+        let body: Vec<String> = opens.into_iter().map(candle).collect();
+        pages.push(
+            server.mock("GET", "/api/v3/klines")
+                .match_query(Matcher::Exact(format!(
+                    "symbol=BTCUSDT&interval=1m&startTime={}&limit=2&endTime={}",
+                    page_start, end
+                )))
+                .with_status(200)
+                .with_body(format!("[{}]", body.join(",")))
+                .create_async()
+                .await,
+        );
+    }
+    
+    let mut config = BinanceConfig::new(false);
+    config.base_url = Some(server.url());
+    config.max_kline_limit = 2;
+    let client = BinanceClient::new(config).unwrap();
+    
+    let plan = client.plan_klines_backfill("btcusdt", binance_connector::Interval::Minutes1, start, end);
+    assert_eq!((plan.candles, plan.pages, plan.total_weight), (3, 2, 4));
+    
+    let klines: Vec<_> = plan.execute().try_collect().await.unwrap();
+    let opens: Vec<i64> = klines.iter().map(|k| k.open_time.timestamp_millis()).collect();
+    assert_eq!(opens, vec![start, start + 60_000, end]);
+    
+    for page in pages {
+        page.assert_async().await;
+    }
+}
+
+#[tokio::test]
+async fn test_mock_validate_symbols() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/exchangeInfo")
+        .with_status(200)
+        .with_body(r#"{
+            "symbols": [
+                {
+                    "symbol": "BTCUSDT", "status": "TRADING", "baseAsset": "BTC", "quoteAsset": "USDT",
+                    "baseAssetPrecision": 8, "quoteAssetPrecision": 8, "orderTypes": ["LIMIT"]
+                },
+                {
+                    "symbol": "LUNAUSDT", "status": "BREAK", "baseAsset": "LUNA", "quoteAsset": "USDT",
+                    "baseAssetPrecision": 8, "quoteAssetPrecision": 8, "orderTypes": ["LIMIT"]
+                }
+            ]
+        }"#)
+        .expect(3)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let valid = client
+        .validate_symbols(&["BTCUSDT", "btcusdt", "LUNAUSDT", "FOOBAR"])
+        .await
+        .unwrap();
+    
+    assert_eq!(valid.len(), 4);
+    assert!(valid["BTCUSDT"]);
+    assert!(valid["btcusdt"]);
+    // Listed but not trading
+    assert!(!valid["LUNAUSDT"]);
+    // Not listed
+    assert!(!valid["FOOBAR"]);
+    
+    assert!(client.require_symbols(&["BTCUSDT"]).await.is_ok());
+    let result = client.require_symbols(&["BTCUSDT", "FOOBAR", "LUNAUSDT"]).await;
+    assert!(matches!(result, Err(binance_connector::Error::InvalidSymbol(s)) if s == "FOOBAR"));
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_exchange_info_permissions() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/exchangeInfo")
+        .with_status(200)
+        .with_body(r#"{
+            "symbols": [
+                {
+                    "symbol": "MRGUSDT", "status": "TRADING", "baseAsset": "MRG", "quoteAsset": "USDT",
+                    "baseAssetPrecision": 8, "quoteAssetPrecision": 8, "orderTypes": ["LIMIT"],
+                    "filters": [], "permissions": ["MARGIN"]
+                },
+                {
+                    "symbol": "BTCUSDT", "status": "TRADING", "baseAsset": "BTC", "quoteAsset": "USDT",
+                    "baseAssetPrecision": 8, "quoteAssetPrecision": 8, "orderTypes": ["LIMIT"],
+                    "filters": [], "permissions": [],
+                    "permissionSets": [["SPOT", "MARGIN", "TRD_GRP_004"]]
+                }
+            ]
+        }"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let symbols = client.get_exchange_info().await.unwrap();
+    
+    assert_eq!(symbols[0].permissions, vec!["MARGIN"]);
+    assert!(!symbols[0].allows_spot());
+    
+    // Unknown permission strings are kept as-is
+    assert_eq!(symbols[1].permissions, vec!["SPOT", "MARGIN", "TRD_GRP_004"]);
+    assert!(symbols[1].allows_spot());
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_24h_ticker_multi() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/ticker/24hr")
+        .match_query(Matcher::UrlEncoded("symbols".into(), r#"["BTCUSDT","ETHUSDT"]"#.into()))
+        .with_status(200)
+        .with_body(r#"[
+            {
+                "symbol": "BTCUSDT", "priceChange": "1000.00", "priceChangePercent": "2.5",
+                "weightedAvgPrice": "43000.00", "prevClosePrice": "42000.00", "lastPrice": "43000.00",
+                "bidPrice": "42999.00", "askPrice": "43001.00", "openPrice": "42000.00",
+                "highPrice": "43500.00", "lowPrice": "41500.00", "volume": "1000.0",
+                "quoteVolume": "43000000.0", "openTime": 1640000000000, "closeTime": 1640086400000,
+                "firstId": 1, "lastId": 1000, "count": 1000
+            },
+            {
+                "symbol": "ETHUSDT", "priceChange": "-50.00", "priceChangePercent": "-1.5",
+                "weightedAvgPrice": "3300.00", "prevClosePrice": "3350.00", "lastPrice": "3300.00",
+                "bidPrice": "3299.90", "askPrice": "3300.10", "openPrice": "3350.00",
+                "highPrice": "3400.00", "lowPrice": "3250.00", "volume": "5000.0",
+                "quoteVolume": "16500000.0", "openTime": 1640000000000, "closeTime": 1640086400000,
+                "firstId": 1, "lastId": 500, "count": 500
+            }
+        ]"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let tickers = client.get_ticker_24h_multi(&["BTCUSDT", "ETHUSDT"]).await.unwrap();
+    
+    assert_eq!(tickers.len(), 2);
+    assert_eq!(tickers[0].symbol, "BTCUSDT");
+    assert_eq!(tickers[1].symbol, "ETHUSDT");
+    assert_eq!(tickers[1].price_change_percent, -1.5);
+    
+    // Empty input never reaches the server
+    assert!(client.get_ticker_24h_multi(&[]).await.unwrap().is_empty());
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_top_symbols_by_volume() {
+    let ticker = |symbol: &str, quote_volume: &str| format!(
+        r#"{{"symbol":"{}","priceChange":"0","priceChangePercent":"0","weightedAvgPrice":"1",
+            "prevClosePrice":"1","lastPrice":"1","bidPrice":"1","askPrice":"1","openPrice":"1",
+            "highPrice":"1","lowPrice":"1","volume":"1","quoteVolume":"{}",
+            "openTime":1640000000000,"closeTime":1640086400000,"firstId":1,"lastId":2,"count":2}}"#,
+        symbol, quote_volume
+    );
+    let body = format!(
+        "[{}]",
+        [
+            ticker("ETHUSDT", "800000.0"),
+            ticker("BTCUSDT", "2500000.0"),
+            ticker("ETHBTC", "9000000.0"),
+            ticker("SOLUSDT", "300000.0"),
+            ticker("BNBUSDT", "450000.0"),
+        ]
+        .join(",")
+    );
+    
+    let mut server = Server::new_async().await;
+    let mock = server.mock("GET", "/api/v3/ticker/24hr")
+        .match_query(Matcher::Missing)
+        .with_status(200)
+        .with_body(body)
+        .expect(2)
+        .create_async()
+        .await;
+    
+    // The all-symbols request weighs 80; a larger budget keeps the test fast
+    let mut config = BinanceConfig::new(false);
+    config.base_url = Some(server.url());
+    config.requests_per_minute = 60_000;
+    let client = BinanceClient::new(config).unwrap();
+    
+    let top = client.top_symbols_by_volume(3, Some("usdt")).await.unwrap();
+    let symbols: Vec<&str> = top.iter().map(|t| t.symbol.as_str()).collect();
+    assert_eq!(symbols, vec!["BTCUSDT", "ETHUSDT", "BNBUSDT"]);
+    
+    let top = client.top_symbols_by_volume(10, None).await.unwrap();
+    assert_eq!(top.len(), 5);
+    assert_eq!(top[0].symbol, "ETHBTC");
+    assert_eq!(top[0].quote_volume, 9_000_000.0);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_health_report() {
+    let mut server = Server::new_async().await;
+    
+    let ping = server.mock("GET", "/api/v3/ping")
+        .with_status(200)
+        .with_header("X-MBX-USED-WEIGHT-1M", "42")
+        .with_body_from_request(|_| {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            b"{}".to_vec()
+        })
+        .create_async()
+        .await;
+    
+    // Server clock runs 5s ahead of ours
+    let time = server.mock("GET", "/api/v3/time")
+        .with_status(200)
+        .with_body_from_request(|_| {
+            let server_time = chrono::Utc::now().timestamp_millis() + 5_000;
+            format!(r#"{{"serverTime":{}}}"#, server_time).into()
+        })
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let report = client.health_report().await.unwrap();
+    
+    assert!(report.reachable);
+    assert!(report.latency >= std::time::Duration::from_millis(100));
+    assert!((report.clock_skew_ms - 5_000).abs() < 500, "skew {}", report.clock_skew_ms);
+    assert_eq!(report.used_weight, Some(42));
+    // Reporting does not adjust the client's clock offset
+    assert_eq!(client.time_offset_ms(), 0);
+    
+    ping.assert_async().await;
+    time.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_response_body_size_cap() {
+    let mut server = Server::new_async().await;
+    
+    let tickers: Vec<String> = (0..100)
+        .map(|i| format!(r#"{{"symbol":"SYM{}USDT","price":"1.00"}}"#, i))
+        .collect();
+    let body = format!("[{}]", tickers.join(","));
+    
+    let mock = server.mock("GET", "/api/v3/ticker/price")
+        .with_status(200)
+        .with_body(&body)
+        .expect(2)
+        .create_async()
+        .await;
+    
+    let mut config = BinanceConfig::new(false);
+    config.base_url = Some(server.url());
+    config.enable_retries = false;
+    config.max_response_bytes = Some(1024);
+    let client = BinanceClient::new(config.clone()).unwrap();
+    
+    match client.get_all_ticker_prices().await {
+        Err(binance_connector::Error::DeserializationError(msg)) => {
+            assert!(msg.contains("1024"), "{}", msg);
+        }
+        other => panic!("Expected DeserializationError, got {:?}", other),
+    }
+    
+    // A cap above the body size lets the response through
+    config.max_response_bytes = Some(body.len());
+    let client = BinanceClient::new(config).unwrap();
+    assert_eq!(client.get_all_ticker_prices().await.unwrap().len(), 100);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_retries_only_transient_failures() {
+    let mut server = Server::new_async().await;
+    
+    let unavailable = server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()))
+        .with_status(503)
+        .expect(3)
+        .create_async()
+        .await;
+    let invalid = server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::UrlEncoded("symbol".into(), "INVALID".into()))
+        .with_status(400)
+        .with_body(r#"{"code":-1121,"msg":"Invalid symbol."}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    
+    let mut config = BinanceConfig::new(false);
+    config.base_url = Some(server.url());
+    config.backoff.max_attempts = 3;
+    config.backoff.initial = std::time::Duration::from_millis(1);
+    let client = BinanceClient::new(config).unwrap();
+    
+    let err = client.get_ticker_price("BTCUSDT").await.unwrap_err();
+    assert!(err.is_transient());
+    let err = client.get_ticker_price("INVALID").await.unwrap_err();
+    assert!(!err.is_retryable());
+    
+    unavailable.assert_async().await;
+    invalid.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_request_deadline_bounds_retries() {
+    let mut server = Server::new_async().await;
+    
+    let unavailable = server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::Any)
+        .with_status(503)
+        .expect_at_least(1)
+        .expect_at_most(4)
+        .create_async()
+        .await;
+    
+    // Ten retries 400ms apart would take about 4s
+    let mut config = BinanceConfig::new(false);
+    config.base_url = Some(server.url());
+    config.backoff.max_attempts = 11;
+    config.retry_jitter = false;
+    config.backoff.initial = std::time::Duration::from_millis(400);
+    config.backoff.multiplier = 1.0;
+    config.request_deadline_seconds = Some(1);
+    let client = BinanceClient::new(config).unwrap();
+    
+    let started = std::time::Instant::now();
+    let err = client.get_ticker_price("BTCUSDT").await.unwrap_err();
+    
+    assert!(matches!(err, binance_connector::Error::Timeout(1)));
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    unavailable.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_request_deadline_bounds_order_wait() {
+    let mut server = Server::new_async().await;
+
+    let time = server.mock("GET", "/api/v3/time")
+        .with_status(200)
+        .with_body(r#"{"serverTime": 1640000000000}"#)
+        .create_async()
+        .await;
+    let order = server.mock("POST", "/api/v3/order")
+        .expect(0)
+        .create_async()
+        .await;
+
+    // The time sync takes the only request this minute, so the order waits
+    let mut config = BinanceConfig::with_auth("test_key".to_string(), "test_secret".to_string(), false);
+    config.base_url = Some(server.url());
+    config.requests_per_minute = 1;
+    config.request_deadline_seconds = Some(1);
+    let client = BinanceClient::new(config).unwrap();
+
+    let started = std::time::Instant::now();
+    let err = client
+        .place_order(&btcusdt(), OrderSide::Buy, OrderType::Market, 0.01, None, NewOrderRespType::Ack)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, binance_connector::Error::Timeout(1)));
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    time.assert_async().await;
+    order.assert_async().await;
+}
+
+const CANCELED_ORDER: &str = r#"{
+    "symbol": "BTCUSDT",
+    "origClientOrderId": "quote-1",
+    "orderId": 100,
+    "orderListId": -1,
+    "clientOrderId": "cancel-1",
+    "transactTime": 1640000000000,
+    "price": "42900.00",
+    "origQty": "0.10",
+    "executedQty": "0.00",
+    "cummulativeQuoteQty": "0.00",
+    "status": "CANCELED",
+    "timeInForce": "GTC",
+    "type": "LIMIT",
+    "side": "BUY"
+}"#;
+
+#[tokio::test]
+async fn test_mock_cancel_replace_order() {
+    let mut server = Server::new_async().await;
+    
+    let body = format!(
+        r#"{{
+            "cancelResult": "SUCCESS",
+            "newOrderResult": "SUCCESS",
+            "cancelResponse": {},
+            "newOrderResponse": {{
+                "symbol": "BTCUSDT",
+                "orderId": 101,
+                "orderListId": -1,
+                "clientOrderId": "quote-2",
+                "transactTime": 1640000000001,
+                "price": "42950.00",
+                "origQty": "0.10",
+                "executedQty": "0.00",
+                "cummulativeQuoteQty": "0.00",
+                "status": "NEW",
+                "timeInForce": "GTC",
+                "type": "LIMIT",
+                "side": "BUY",
+                "fills": []
+            }}
+        }}"#,
+        CANCELED_ORDER
+    );
+    let mock = server.mock("POST", "/api/v3/order/cancelReplace")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("cancelReplaceMode".into(), "STOP_ON_FAILURE".into()),
+            Matcher::UrlEncoded("cancelOrderId".into(), "100".into()),
+            Matcher::UrlEncoded("type".into(), "LIMIT".into()),
+            Matcher::UrlEncoded("price".into(), "42950".into()),
+        ]))
+        .with_status(200)
+        .with_body(body)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    let result = client
+        .cancel_replace_order(
+            &btcusdt(),
+            100,
+            OrderSide::Buy,
+            0.1,
+            42950.0,
+            CancelReplaceMode::StopOnFailure,
+        )
+        .await
+        .unwrap();
+    
+    assert!(result.is_success());
+    assert_eq!(result.cancel_order.unwrap().status, OrderStatus::Canceled);
+    let new_order = result.new_order.unwrap();
+    assert_eq!(new_order.order_id, 101);
+    assert_eq!(new_order.status, OrderStatus::New);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_cancel_replace_partial_failure() {
+    let mut server = Server::new_async().await;
+    
+    let body = format!(
+        r#"{{
+            "code": -2021,
+            "msg": "Order cancel-replace partially failed.",
+            "data": {{
+                "cancelResult": "SUCCESS",
+                "newOrderResult": "FAILURE",
+                "cancelResponse": {},
+                "newOrderResponse": {{
+                    "code": -2010,
+                    "msg": "Order would immediately match and take."
+                }}
+            }}
+        }}"#,
+        CANCELED_ORDER
+    );
+    let mock = server.mock("POST", "/api/v3/order/cancelReplace")
+        .match_query(Matcher::Any)
+        .with_status(409)
+        .with_body(body)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    let result = client
+        .cancel_replace_order(
+            &btcusdt(),
+            100,
+            OrderSide::Buy,
+            0.1,
+            43100.0,
+            CancelReplaceMode::AllowFailure,
+        )
+        .await
+        .unwrap();
+    
+    assert!(!result.is_success());
+    assert_eq!(result.cancel_result, CancelReplaceStatus::Success);
+    assert_eq!(result.new_order_result, CancelReplaceStatus::Failure);
+    assert_eq!(result.cancel_order.unwrap().order_id, 100);
+    assert!(result.new_order.is_none());
+    assert_eq!(result.new_order_error.unwrap().code, -2010);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_agg_trades_window() {
+    let mut server = Server::new_async().await;
+    
+    let start = 1_640_000_000_000_i64;
+    let hour = 3_600_000_i64;
+    
+    let first = server.mock("GET", "/api/v3/aggTrades")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("startTime".into(), start.to_string()),
+            Matcher::UrlEncoded("endTime".into(), (start + hour - 1).to_string()),
+            Matcher::UrlEncoded("limit".into(), "1000".into()),
+        ]))
+        .with_status(200)
+        .with_body(format!(
+            r#"[
+                {{"a":1,"p":"43000.00","q":"0.1","f":10,"l":10,"T":{},"m":true,"M":true}},
+                {{"a":2,"p":"43001.00","q":"0.2","f":11,"l":12,"T":{},"m":false,"M":true}}
+            ]"#,
+            start + 1_000,
+            start + hour - 1
+        ))
+        .create_async()
+        .await;
+    // Second window repeats the boundary trade, which must be deduplicated
+    let second = server.mock("GET", "/api/v3/aggTrades")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("startTime".into(), (start + hour).to_string()),
+            Matcher::UrlEncoded("endTime".into(), (start + 2 * hour - 1).to_string()),
+        ]))
+        .with_status(200)
+        .with_body(format!(
+            r#"[
+                {{"a":2,"p":"43001.00","q":"0.2","f":11,"l":12,"T":{},"m":false,"M":true}},
+                {{"a":3,"p":"43002.00","q":"0.3","f":13,"l":13,"T":{},"m":true,"M":true}}
+            ]"#,
+            start + hour - 1,
+            start + hour + 5_000
+        ))
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let trades = client
+        .get_agg_trades_window("BTCUSDT", start, start + 2 * hour - 1)
+        .await
+        .unwrap();
+    
+    let ids: Vec<i64> = trades.iter().map(|t| t.id).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+    assert_eq!(trades[0].symbol, "BTCUSDT");
+    assert_eq!(trades[1].last_trade_id, 12);
+    assert!(trades.windows(2).all(|w| w[0].time <= w[1].time));
+    
+    first.assert_async().await;
+    second.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_get_historical_trades_from_id() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/historicalTrades")
+        .match_header("X-MBX-APIKEY", "test_key")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+            Matcher::UrlEncoded("limit".into(), "2".into()),
+            Matcher::UrlEncoded("fromId".into(), "28457".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"[
+            {"id":28457,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","time":1499865549590,"isBuyerMaker":true,"isBestMatch":true},
+            {"id":28458,"price":"4.00000200","qty":"1.00000000","quoteQty":"4.000002","time":1499865549591,"isBuyerMaker":false,"isBestMatch":true}
+        ]"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    let trades = client.get_historical_trades("btcusdt", 2, 28457).await.unwrap();
+    
+    mock.assert_async().await;
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades[0].id, 28457);
+    assert_eq!(trades[1].symbol, "BTCUSDT");
+    
+    // Without an API key the request is never sent
+    let client = create_mock_client(&server).await;
+    let result = client.get_historical_trades("BTCUSDT", 2, 28457).await;
+    assert!(matches!(result, Err(binance_connector::Error::ConfigError(_))));
+}
+
+#[tokio::test]
+async fn test_mock_klines_retry_empty() {
+    let mut server = Server::new_async().await;
+    
+    let empty = server.mock("GET", "/api/v3/klines")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+    let populated = server.mock("GET", "/api/v3/klines")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"[
+            [1640000000000, "43000.00", "43100.00", "42900.00", "43050.00", "100.5",
+             1640000299999, "4320000.00", 1000, "50.25", "2160000.00", "0"]
+        ]"#)
+        .expect(1)
+        .create_async()
+        .await;
+    
+    let mut config = BinanceConfig::new(false);
+    config.base_url = Some(server.url());
+    config.retry_empty_klines = true;
+    config.backoff.initial = std::time::Duration::from_millis(1);
+    let client = BinanceClient::new(config).unwrap();
+    
+    let klines = client
+        .get_klines("BTCUSDT", binance_connector::Interval::Minutes5, 1)
+        .await
+        .unwrap();
+    assert_eq!(klines.len(), 1);
+    
+    empty.assert_async().await;
+    populated.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_klines_empty_future_range_not_retried() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/klines")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+    
+    let mut config = BinanceConfig::new(false);
+    config.base_url = Some(server.url());
+    config.retry_empty_klines = true;
+    config.backoff.initial = std::time::Duration::from_millis(1);
+    let client = BinanceClient::new(config).unwrap();
+    
+    // Starts in 2100, so no candle can exist yet
+    let klines = client
+        .get_klines_range("BTCUSDT", binance_connector::Interval::Minutes5, 4102444800000, 4102448400000)
+        .await
+        .unwrap();
+    assert!(klines.is_empty());
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_estimate_cost_uses_best_ask() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/ticker/bookTicker")
+        .match_query(Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()))
+        .with_status(200)
+        .with_body(r#"{
+            "symbol": "BTCUSDT",
+            "bidPrice": "42999.00",
+            "bidQty": "1.0",
+            "askPrice": "43001.00",
+            "askQty": "2.0"
+        }"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let symbol: binance_connector::Symbol = serde_json::from_value(serde_json::json!({
+        "symbol": "BTCUSDT",
+        "status": "TRADING",
+        "base_asset": "BTC",
+        "quote_asset": "USDT",
+        "base_asset_precision": 8,
+        "quote_asset_precision": 8,
+        "order_types": ["LIMIT", "MARKET"]
+    }))
+    .unwrap();
+    let rates = binance_connector::CommissionRates { maker: 0.001, taker: 0.001 };
+    
+    let cost = client
+        .estimate_cost(&symbol, OrderSide::Buy, 0.5, None, rates)
+        .await
+        .unwrap();
+    assert!((cost - 0.5 * 43001.0 * 1.001).abs() < 1e-6);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_all_ticker_prices_stream() {
+    use futures::TryStreamExt;
+    
+    let mut server = Server::new_async().await;
+    
+    let body = format!(
+        "[{}]",
+        (0..2000)
+            .map(|i| format!(r#"{{"symbol":"SYM{}USDT","price":"{}.5"}}"#, i, i))
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    let mock = server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::Missing)
+        .with_status(200)
+        .with_body(body)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let tickers: Vec<_> = client.get_all_ticker_prices_stream().try_collect().await.unwrap();
+    
+    assert_eq!(tickers.len(), 2000);
+    assert_eq!(tickers[0].symbol, "SYM0USDT");
+    assert_eq!(tickers[1999].symbol, "SYM1999USDT");
+    assert_eq!(tickers[1999].price, 1999.5);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_all_ticker_prices_stream_error() {
+    use futures::TryStreamExt;
+    
+    let mut server = Server::new_async().await;
+    
+    let _mock = server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::Missing)
+        .with_status(400)
+        .with_body(r#"{"code":-1100,"msg":"Illegal characters found in parameter."}"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let result: Result<Vec<_>, _> = client.get_all_ticker_prices_stream().try_collect().await;
+    
+    assert!(matches!(
+        result,
+        Err(binance_connector::Error::ApiError { code: -1100, .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_mock_weight_remaining_1m() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/ping")
+        .with_status(200)
+        .with_header("X-MBX-USED-WEIGHT-1M", "1150")
+        .with_body("{}")
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    assert_eq!(client.weight_remaining_1m(), None);
+    
+    client.ping().await.unwrap();
+    assert_eq!(client.weight_remaining_1m(), Some(50));
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_get_json_custom_endpoint() {
+    #[derive(serde::Deserialize)]
+    struct TradingDay {
+        symbol: String,
+        #[serde(rename = "lastPrice")]
+        last_price: String,
+    }
+    
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/ticker/tradingDay")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+            Matcher::UrlEncoded("timeZone".into(), "+08:00".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"symbol": "BTCUSDT", "lastPrice": "43250.50", "count": 1000}"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_client(&server).await;
+    let day: TradingDay = client
+        .get_json("/api/v3/ticker/tradingDay", &[("symbol", "BTCUSDT"), ("timeZone", "+08:00")])
+        .await
+        .unwrap();
+    
+    mock.assert_async().await;
+    assert_eq!(day.symbol, "BTCUSDT");
+    assert_eq!(day.last_price, "43250.50");
+}
+
+#[tokio::test]
+async fn test_mock_post_signed_json_custom_endpoint() {
+    #[derive(serde::Deserialize)]
+    struct Transfer {
+        #[serde(rename = "tranId")]
+        tran_id: i64,
+    }
+    
+    let mut server = Server::new_async().await;
+    
+    let _time = server.mock("GET", "/api/v3/time")
+        .with_status(200)
+        .with_body(format!(r#"{{"serverTime": {}}}"#, chrono::Utc::now().timestamp_millis()))
+        .create_async()
+        .await;
+    let mock = server.mock("POST", "/sapi/v1/asset/transfer")
+        .match_header("X-MBX-APIKEY", "test_key")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("type".into(), "MAIN_UMFUTURE".into()),
+            Matcher::UrlEncoded("asset".into(), "USDT".into()),
+            Matcher::Regex("timestamp=\\d+".into()),
+            Matcher::Regex("signature=[0-9a-f]{64}".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"tranId": 13526853623}"#)
+        .create_async()
+        .await;
+    
+    let client = create_mock_auth_client(&server).await;
+    let transfer: Transfer = client
+        .post_signed_json("/sapi/v1/asset/transfer", &[("type", "MAIN_UMFUTURE"), ("asset", "USDT")])
+        .await
+        .unwrap();
+    
+    mock.assert_async().await;
+    assert_eq!(transfer.tran_id, 13526853623);
+}
+
+#[tokio::test]
+async fn test_max_concurrent_requests() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    
+    // Minimal HTTP server that answers slowly and tracks requests in flight
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+    
+    let (current, peak) = (Arc::clone(&in_flight), Arc::clone(&max_in_flight));
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let (current, peak) = (Arc::clone(&current), Arc::clone(&peak));
+            tokio::spawn(async move {
+                let mut head = Vec::new();
+                loop {
+                    while !head.ends_with(b"\r\n\r\n") {
+                        match socket.read_u8().await {
+                            Ok(byte) => head.push(byte),
+                            Err(_) => return,
+                        }
+                    }
+                    head.clear();
+                    
+                    let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    current.fetch_sub(1, Ordering::SeqCst);
+                    
+                    let body = r#"{"symbol": "BTCUSDT", "price": "43250.50"}"#;
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    if socket.write_all(response.as_bytes()).await.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    
+    let mut config = BinanceConfig::new(false);
+    config.base_url = Some(format!("http://{}", addr));
+    config.max_concurrent_requests = Some(2);
+    let client = BinanceClient::new(config).unwrap();
+    
+    let requests = (0..6).map(|_| client.get_ticker_price("BTCUSDT"));
+    for result in futures::future::join_all(requests).await {
+        assert_eq!(result.unwrap().price, 43250.50);
+    }
+    
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_mock_local_address() {
+    let mut server = Server::new_async().await;
+    
+    let mock = server.mock("GET", "/api/v3/ticker/price")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"symbol": "BTCUSDT", "price": "43250.50"}"#)
+        .create_async()
+        .await;
+    
+    let config = BinanceConfig::builder()
+        .base_url(server.url())
+        .local_address(std::net::Ipv4Addr::LOCALHOST.into())
+        .build()
+        .unwrap();
+    assert_eq!(config.local_address, Some("127.0.0.1".parse().unwrap()));
+    
+    let client = BinanceClient::new(config).unwrap();
+    assert_eq!(client.get_ticker_price("BTCUSDT").await.unwrap().price, 43250.50);
+    
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_mock_api_key_rotation() {
+    let mut server = Server::new_async().await;
+    
+    let mut mocks = Vec::new();
+    for (key, expected, weight) in [("key_a", 2, "11"), ("key_b", 1, "12")] {
+        mocks.push(
+            server.mock("GET", "/api/v3/allOrders")
+                .match_header("X-MBX-APIKEY", key)
+                .match_query(Matcher::Any)
+                .with_status(200)
+                .with_header("X-MBX-USED-WEIGHT-1M", weight)
+                .with_body("[]")
+                .expect(expected)
+                .create_async()
+                .await,
+        );
+    }
+    
+    let config = BinanceConfig::builder()
+        .base_url(server.url())
+        .enable_retries(false)
+        .add_api_key("key_a", "secret_a")
+        .add_api_key("key_b", "secret_b")
+        .build()
+        .unwrap();
+    let client = BinanceClient::new(config).unwrap();
+    assert_eq!(client.used_weight_by_key(), vec![None, None]);
+    
+    // key_a, key_b, key_a
+    for _ in 0..3 {
+        assert!(client.get_all_orders("BTCUSDT", None, None, None, None).await.unwrap().is_empty());
+    }
+    
+    assert_eq!(client.used_weight_by_key(), vec![Some(11), Some(12)]);
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+}
@@ -38,7 +38,7 @@ async fn test_ticker_stream_connection() {
 async fn test_kline_stream_connection() {
     let ws = get_test_ws();
     
-    let mut stream = ws.kline_stream("ETHUSDT", Interval::Minutes1).await
+    let mut stream = ws.kline_stream("ETHUSDT", Interval::Minutes1, None).await
         .expect("Failed to connect to kline stream");
     
     let result = timeout(Duration::from_secs(10), stream.recv()).await;
@@ -76,7 +76,7 @@ async fn test_trade_stream_connection() {
     assert!(trade.quantity > 0.0);
     
     println!("✅ Received trade: {} ${} × {}",
-        if trade.is_buyer_maker { "SELL" } else { "BUY" },
+        trade.aggressor_side(),
         trade.price,
         trade.quantity
     );
@@ -110,6 +110,27 @@ async fn test_depth_stream_connection() {
         best_bid, best_ask, best_ask - best_bid);
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_live_order_book() {
+    let client = binance_connector::BinanceClient::new(BinanceConfig::new(false))
+        .expect("Failed to create client");
+    
+    let (snapshot, mut updates) = client.live_order_book("BTCUSDT", 20).await
+        .expect("Failed to start live order book");
+    
+    assert_eq!(snapshot.bids.len(), 20);
+    assert_eq!(snapshot.asks.len(), 20);
+    
+    let result = timeout(Duration::from_secs(10), updates.recv()).await;
+    let book = result.unwrap().unwrap().expect("Should be Ok result");
+    
+    assert!(book.last_update_id > snapshot.last_update_id);
+    assert!(book.asks[0].price > book.bids[0].price, "Book should not be crossed");
+    
+    println!("✅ Live book: update {} microprice={:?}", book.last_update_id, book.microprice());
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_mini_ticker_stream() {
@@ -126,9 +147,10 @@ async fn test_mini_ticker_stream() {
     let ticker = message.expect("Should be Ok result");
     
     assert_eq!(ticker.symbol, "BNBUSDT");
-    assert!(ticker.price > 0.0);
+    assert!(ticker.price() > 0.0);
+    assert!(ticker.high_price >= ticker.low_price);
     
-    println!("✅ Received mini ticker: ${}", ticker.price);
+    println!("✅ Received mini ticker: ${}", ticker.price());
 }
 
 #[tokio::test]
@@ -156,7 +178,7 @@ async fn test_multiple_messages() {
 async fn test_stream_closed_candle_detection() {
     let ws = get_test_ws();
     
-    let mut stream = ws.kline_stream("BTCUSDT", Interval::Minutes1).await.unwrap();
+    let mut stream = ws.kline_stream("BTCUSDT", Interval::Minutes1, None).await.unwrap();
     
     // Wait for a closed candle (max 2 minutes)
     let mut found_closed = false;